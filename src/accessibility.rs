@@ -0,0 +1,292 @@
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::debug::DebugTimeScale;
+use crate::screenshot::{self, Toast};
+use crate::settings::Settings;
+use crate::split_paddle::{self, SplitPaddle};
+use crate::{Ball, Block, Collider, Paddle, BALL_SIZE};
+
+pub const STANDARD_BACKGROUND: Color = Color::srgb(0.13, 0.1, 0.2);
+const HIGH_CONTRAST_BACKGROUND: Color = Color::srgb(0.02, 0.02, 0.02);
+const OUTLINE_COLOR: Color = Color::WHITE;
+const OUTLINE_WIDTH: f32 = 4.0;
+
+pub const GAME_SPEED_MIN: f32 = 0.6;
+pub const GAME_SPEED_MAX: f32 = 1.0;
+const GAME_SPEED_STEP: f32 = 0.1;
+
+/// Ball size multipliers cycled with Ctrl+B.
+const BALL_SCALE_PRESETS: [f32; 3] = [1.0, 1.25, 1.5];
+/// Paddle width multipliers cycled with Ctrl+P.
+const PADDLE_SCALE_PRESETS: [f32; 4] = [1.0, 1.25, 1.5, 2.0];
+
+/// Gizmo group for the high-contrast outlines, so their line width doesn't leak into
+/// the debug overlay.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct OutlineGizmos;
+
+/// Block colors, with presets that stay distinguishable under the common forms of
+/// color blindness. Cycled with F2.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Palette {
+    #[default]
+    Standard,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl Palette {
+    pub fn name(self) -> &'static str {
+        match self {
+            Palette::Standard => "Standard",
+            Palette::Deuteranopia => "Deuteranopia",
+            Palette::Protanopia => "Protanopia",
+            Palette::Tritanopia => "Tritanopia",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Palette::Standard => Palette::Deuteranopia,
+            Palette::Deuteranopia => Palette::Protanopia,
+            Palette::Protanopia => Palette::Tritanopia,
+            Palette::Tritanopia => Palette::Standard,
+        }
+    }
+
+    /// Color of a block with `health` hits left.
+    // The colorblind presets pick from the Okabe-Ito palette, spaced out in lightness as
+    // well as hue so neighbouring health levels never collapse into one color.
+    pub fn block_color(self, health: u32) -> Color {
+        let [one, two, more] = match self {
+            Palette::Standard => [
+                Color::srgb(0.8, 0.2, 0.2),
+                Color::srgb(0.85, 0.5, 0.2),
+                Color::srgb(0.9, 0.8, 0.3),
+            ],
+            Palette::Deuteranopia => [
+                Color::srgb(0.0, 0.45, 0.7),
+                Color::srgb(0.9, 0.62, 0.0),
+                Color::srgb(0.94, 0.89, 0.26),
+            ],
+            Palette::Protanopia => [
+                Color::srgb(0.0, 0.45, 0.7),
+                Color::srgb(0.34, 0.71, 0.91),
+                Color::srgb(0.94, 0.89, 0.26),
+            ],
+            Palette::Tritanopia => [
+                Color::srgb(0.84, 0.37, 0.0),
+                Color::srgb(0.8, 0.47, 0.65),
+                Color::srgb(0.0, 0.62, 0.45),
+            ],
+        };
+        match health {
+            0 | 1 => one,
+            2 => two,
+            _ => more,
+        }
+    }
+
+    /// Cool, warm and hot colors for speed feedback, fading in that order as the ball
+    /// speeds up. The colorblind presets keep the ends apart in lightness as well.
+    pub fn heat_colors(self) -> [Color; 3] {
+        match self {
+            Palette::Standard => [
+                Color::srgb(0.35, 0.7, 1.0),
+                Color::srgb(1.0, 0.85, 0.3),
+                Color::srgb(1.0, 0.25, 0.1),
+            ],
+            Palette::Deuteranopia | Palette::Protanopia => [
+                Color::srgb(0.34, 0.71, 0.91),
+                Color::srgb(0.9, 0.62, 0.0),
+                Color::srgb(0.84, 0.37, 0.0),
+            ],
+            Palette::Tritanopia => [
+                Color::srgb(0.0, 0.62, 0.45),
+                Color::srgb(0.8, 0.47, 0.65),
+                Color::srgb(0.84, 0.37, 0.0),
+            ],
+        }
+    }
+}
+
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_gizmo_group::<OutlineGizmos>()
+            .add_systems(Startup, configure_outlines)
+            .add_systems(
+                Update,
+                (
+                    (
+                        cycle_palette,
+                        toggle_high_contrast,
+                        toggle_reduced_motion,
+                        adjust_game_speed,
+                        cycle_sizes,
+                    ),
+                    (apply_background, resize_ball)
+                        .run_if(resource_changed::<Settings>),
+                    apply_time_scale
+                        .run_if(resource_changed::<Settings>.or(resource_changed::<DebugTimeScale>)),
+                    draw_outlines.run_if(|settings: Res<Settings>| settings.high_contrast),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Run condition for purely decorative effects, which are left out in high-contrast and
+/// reduced-motion modes. Anything that spawns or animates effects should check this.
+pub fn decorative_effects(settings: Res<Settings>) -> bool {
+    !settings.high_contrast && !settings.reduced_motion
+}
+
+fn configure_outlines(mut config_store: ResMut<GizmoConfigStore>) {
+    let (config, _) = config_store.config_mut::<OutlineGizmos>();
+    config.line.width = OUTLINE_WIDTH;
+}
+
+fn cycle_palette(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if input.just_pressed(Key::F2) {
+        settings.palette = settings.palette.next();
+        settings.save();
+        let message = format!("Palette: {}", settings.palette.name());
+        screenshot::show_toast(&mut commands, &toast_query, message);
+    }
+}
+
+fn toggle_high_contrast(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if input.just_pressed(Key::F1) {
+        settings.high_contrast = !settings.high_contrast;
+        settings.save();
+        let state = if settings.high_contrast { "on" } else { "off" };
+        screenshot::show_toast(&mut commands, &toast_query, format!("High contrast {state}"));
+    }
+}
+
+fn toggle_reduced_motion(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if input.just_pressed(Key::F5) {
+        settings.reduced_motion = !settings.reduced_motion;
+        settings.save();
+        let state = if settings.reduced_motion { "on" } else { "off" };
+        screenshot::show_toast(&mut commands, &toast_query, format!("Reduced motion {state}"));
+    }
+}
+
+fn adjust_game_speed(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !input.pressed(Key::Control) {
+        return;
+    }
+    let mut step = 0.0;
+    if input.just_pressed(Key::Character("]".into())) {
+        step += GAME_SPEED_STEP;
+    }
+    if input.just_pressed(Key::Character("[".into())) {
+        step -= GAME_SPEED_STEP;
+    }
+    if step != 0.0 {
+        settings.game_speed = (settings.game_speed + step).clamp(GAME_SPEED_MIN, GAME_SPEED_MAX);
+        settings.save();
+        let percent = (settings.game_speed * 100.0).round();
+        screenshot::show_toast(&mut commands, &toast_query, format!("Game speed {percent}%"));
+    }
+}
+
+// Everything paced by virtual time slows down together: the ball, level and run timers,
+// and in-game animations. Menus and toasts run on real time.
+fn apply_time_scale(
+    settings: Res<Settings>,
+    debug_scale: Res<DebugTimeScale>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    let game_speed = settings.game_speed.clamp(GAME_SPEED_MIN, GAME_SPEED_MAX);
+    virtual_time.set_relative_speed(game_speed * debug_scale.0);
+}
+
+fn apply_background(settings: Res<Settings>, mut clear_color: ResMut<ClearColor>) {
+    clear_color.0 = if settings.high_contrast {
+        HIGH_CONTRAST_BACKGROUND
+    } else {
+        STANDARD_BACKGROUND
+    };
+}
+
+fn draw_outlines(
+    mut gizmos: Gizmos<OutlineGizmos>,
+    outlined_query: Query<(&Transform, &Collider, Has<SplitPaddle>), Or<(With<Paddle>, With<Block>)>>,
+    ball_query: Query<(&Transform, &Collider), With<Ball>>,
+) {
+    for (transform, collider, split) in &outlined_query {
+        for part in split_paddle::parts(transform.translation.truncate(), collider, split) {
+            gizmos.rect_2d(part.center(), part.half_size() * 2.0, OUTLINE_COLOR);
+        }
+    }
+    for (transform, collider) in &ball_query {
+        gizmos.circle_2d(transform.translation.truncate(), collider.size.x / 2.0, OUTLINE_COLOR);
+    }
+}
+
+fn next_preset(presets: &[f32], current: f32) -> f32 {
+    let index = presets
+        .iter()
+        .position(|preset| *preset == current)
+        .map_or(0, |index| (index + 1) % presets.len());
+    presets[index]
+}
+
+fn cycle_sizes(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !input.pressed(Key::Control) {
+        return;
+    }
+    let message = if input.just_pressed(Key::Character("b".into())) {
+        settings.ball_scale = next_preset(&BALL_SCALE_PRESETS, settings.ball_scale);
+        format!("Ball size {}x", settings.ball_scale)
+    } else if input.just_pressed(Key::Character("p".into())) {
+        settings.paddle_scale = next_preset(&PADDLE_SCALE_PRESETS, settings.paddle_scale);
+        format!("Paddle width {}x", settings.paddle_scale)
+    } else {
+        return;
+    };
+    settings.save();
+    screenshot::show_toast(&mut commands, &toast_query, message);
+}
+
+// Keeps sprites and colliders in step, so everything that reads the ball size from its
+// `Collider` sees the change immediately. The paddle follows the settings on its own.
+fn resize_ball(settings: Res<Settings>, mut ball_query: Query<(&mut Sprite, &mut Collider), With<Ball>>) {
+    for (mut sprite, mut collider) in &mut ball_query {
+        collider.size = Vec2::splat(BALL_SIZE * settings.ball_scale);
+        sprite.custom_size = Some(collider.size);
+    }
+}
+
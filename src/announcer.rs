@@ -0,0 +1,181 @@
+use bevy::asset::UntypedAssetId;
+use bevy::audio::Volume;
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+
+use crate::config::GameConfig;
+use crate::mods::ModRegistry;
+use crate::scoring::ComboChanged;
+use crate::screenshot::{self, Toast};
+use crate::settings::Settings;
+use crate::{Ball, GameState, Serving, Velocity};
+
+/// Real seconds before the same line can be said again.
+const LINE_COOLDOWN: f32 = 8.0;
+/// Real seconds of quiet kept between any two lines, so callouts never talk over each other.
+const ANNOUNCER_GAP: f32 = 1.5;
+/// Combo multiplier that gets a callout when it's reached.
+const COMBO_CALLOUT: u32 = 5;
+/// Voice volumes cycled through with Ctrl+V, and sound effect volumes with Ctrl+E.
+pub const VOLUME_STEPS: [f32; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoiceLine {
+    LevelClear,
+    MaxSpeed,
+    Combo,
+    MultiBall,
+}
+
+impl VoiceLine {
+    const ALL: [VoiceLine; 4] = [
+        VoiceLine::LevelClear,
+        VoiceLine::MaxSpeed,
+        VoiceLine::Combo,
+        VoiceLine::MultiBall,
+    ];
+
+    /// Path under `assets/`, or a content pack, of the line's recording.
+    fn path(self) -> &'static str {
+        match self {
+            VoiceLine::LevelClear => "voice/level_clear.wav",
+            VoiceLine::MaxSpeed => "voice/max_speed.wav",
+            VoiceLine::Combo => "voice/combo.wav",
+            VoiceLine::MultiBall => "voice/multi_ball.wav",
+        }
+    }
+}
+
+/// Asks the announcer to say a line; it's dropped if the line is still cooling down.
+#[derive(Message)]
+pub struct Announce(pub VoiceLine);
+
+/// Recordings kept loaded for the whole session so callouts play without a delay.
+#[derive(Resource)]
+pub struct VoiceLines(Vec<(VoiceLine, Handle<AudioSource>)>);
+
+impl VoiceLines {
+    pub fn ids(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        self.0.iter().map(|(_, handle)| handle.id().untyped())
+    }
+}
+
+/// Real time each line was last said, and when anything was last said.
+#[derive(Resource, Default)]
+struct AnnouncerCooldowns {
+    lines: Vec<(VoiceLine, f32)>,
+    last_line: Option<f32>,
+}
+
+pub struct AnnouncerPlugin;
+
+impl Plugin for AnnouncerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AnnouncerCooldowns>()
+            .add_message::<Announce>()
+            .add_systems(Startup, load_voice_lines)
+            .add_systems(OnEnter(GameState::LevelComplete), announce_level_clear)
+            .add_systems(
+                Update,
+                (
+                    cycle_voice_volume,
+                    (announce_max_speed, announce_combo).run_if(in_state(GameState::Playing)),
+                    play_announcements,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn load_voice_lines(mut commands: Commands, asset_server: Res<AssetServer>, registry: Res<ModRegistry>) {
+    let lines = VoiceLine::ALL
+        .into_iter()
+        .map(|line| (line, asset_server.load(registry.asset_path(line.path()))))
+        .collect();
+    commands.insert_resource(VoiceLines(lines));
+}
+
+fn cycle_voice_volume(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !input.pressed(Key::Control) || !input.just_pressed(Key::Character("v".into())) {
+        return;
+    }
+    let current = VOLUME_STEPS
+        .iter()
+        .position(|step| *step >= settings.voice_volume)
+        .unwrap_or(0);
+    settings.voice_volume = VOLUME_STEPS[(current + 1) % VOLUME_STEPS.len()];
+    settings.save();
+    let message = format!("Announcer volume {}%", (settings.voice_volume * 100.0).round());
+    screenshot::show_toast(&mut commands, &toast_query, message);
+}
+
+fn announce_level_clear(mut announcements: MessageWriter<Announce>) {
+    announcements.write(Announce(VoiceLine::LevelClear));
+}
+
+// Called out as the ball reaches the top of the speed range, not for as long as it stays there.
+fn announce_max_speed(
+    config: Res<GameConfig>,
+    ball_query: Query<&Velocity, (With<Ball>, Without<Serving>)>,
+    mut at_max: Local<bool>,
+    mut announcements: MessageWriter<Announce>,
+) {
+    let now_at_max = ball_query
+        .iter()
+        .any(|velocity| config.speed_fraction(velocity.0.length()) >= 0.999);
+    if now_at_max && !*at_max {
+        announcements.write(Announce(VoiceLine::MaxSpeed));
+    }
+    *at_max = now_at_max;
+}
+
+fn announce_combo(mut combo_events: MessageReader<ComboChanged>, mut announcements: MessageWriter<Announce>) {
+    if combo_events.read().any(|event| event.multiplier == COMBO_CALLOUT) {
+        announcements.write(Announce(VoiceLine::Combo));
+    }
+}
+
+fn play_announcements(
+    time: Res<Time<Real>>,
+    settings: Res<Settings>,
+    voice_lines: Option<Res<VoiceLines>>,
+    mut cooldowns: ResMut<AnnouncerCooldowns>,
+    mut announcements: MessageReader<Announce>,
+    mut commands: Commands,
+) {
+    let now = time.elapsed_secs();
+    for Announce(line) in announcements.read() {
+        let Some(voice_lines) = voice_lines.as_ref() else {
+            continue;
+        };
+        if settings.voice_volume <= 0.0 {
+            continue;
+        }
+        if cooldowns.last_line.is_some_and(|last| now - last < ANNOUNCER_GAP) {
+            continue;
+        }
+        let said_recently = cooldowns
+            .lines
+            .iter()
+            .any(|(said, at)| said == line && now - at < LINE_COOLDOWN);
+        if said_recently {
+            continue;
+        }
+        let Some((_, source)) = voice_lines.0.iter().find(|(voice_line, _)| voice_line == line) else {
+            continue;
+        };
+
+        commands.spawn((
+            AudioPlayer::new(source.clone()),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(settings.voice_volume)),
+        ));
+        cooldowns.lines.retain(|(said, _)| said != line);
+        cooldowns.lines.push((*line, now));
+        cooldowns.last_line = Some(now);
+    }
+}
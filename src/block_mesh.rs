@@ -0,0 +1,95 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+
+use crate::hazard::{self, Hazard};
+use crate::settings::Settings;
+use crate::shield::{Shield, SHIELD_COLOR};
+use crate::{Block, BlockHealth, Collider, GameEntity, GameState};
+
+/// The single mesh every block in the level is drawn with. Blocks themselves have no
+/// sprite, so the wall costs one draw call however many blocks it has.
+#[derive(Component)]
+struct BlockMesh;
+
+pub struct BlockMeshPlugin;
+
+impl Plugin for BlockMeshPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), spawn_block_mesh)
+            .add_systems(Update, rebuild_block_mesh.run_if(in_state(GameState::Playing)));
+    }
+}
+
+fn spawn_block_mesh(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    // Starts empty; the blocks are filled in once they've been spawned.
+    let mesh = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<[f32; 3]>::new())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, Vec::<[f32; 4]>::new())
+        .with_inserted_indices(Indices::U32(Vec::new()));
+    commands.spawn((
+        Mesh2d(meshes.add(mesh)),
+        // White, so the vertex colors show through unchanged.
+        MeshMaterial2d(materials.add(Color::WHITE)),
+        Transform::default(),
+        BlockMesh,
+        GameEntity,
+    ));
+}
+
+// Only rebuilt when a block is placed, damaged or broken, or the palette changes.
+fn rebuild_block_mesh(
+    settings: Res<Settings>,
+    changed_query: Query<(), (With<Block>, Or<(Changed<BlockHealth>, Changed<Transform>)>)>,
+    mut removed: RemovedComponents<Block>,
+    block_query: Query<(&Transform, &Collider, &BlockHealth, Has<Hazard>, Option<&Shield>), With<Block>>,
+    mesh_query: Query<&Mesh2d, With<BlockMesh>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let removed_any = removed.read().count() > 0;
+    if !settings.is_changed() && changed_query.is_empty() && !removed_any {
+        return;
+    }
+    let _span = info_span!("rebuild_block_mesh", blocks = block_query.iter().len()).entered();
+    let Some(mesh) = mesh_query.single().ok().and_then(|mesh| meshes.get_mut(&mesh.0)) else {
+        return;
+    };
+
+    let block_count = block_query.iter().count();
+    let mut positions = Vec::with_capacity(block_count * 4);
+    let mut colors = Vec::with_capacity(block_count * 4);
+    let mut indices = Vec::with_capacity(block_count * 6);
+    let mut push_quad = |min: Vec2, max: Vec2, z: f32, color: Color| {
+        let color = color.to_linear().to_f32_array();
+        let first = positions.len() as u32;
+        let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+        for corner in corners {
+            positions.push(corner.extend(z).to_array());
+            colors.push(color);
+        }
+        indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+    };
+    for (transform, collider, health, is_hazard, shield) in &block_query {
+        let center = transform.translation;
+        let half = collider.size / 2.0;
+        let color = if is_hazard {
+            hazard::HAZARD_COLOR
+        } else {
+            settings.palette.block_color(health.0)
+        };
+        push_quad(center.truncate() - half, center.truncate() + half, center.z, color);
+        // The plate goes on top, a little in front so it isn't hidden by the block.
+        if let Some(shield) = shield {
+            let (min, max) = shield.plate(center.truncate(), half);
+            push_quad(min, max, center.z + 0.01, SHIELD_COLOR);
+        }
+    }
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_indices(Indices::U32(indices));
+}
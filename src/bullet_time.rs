@@ -0,0 +1,77 @@
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+
+use crate::GameState;
+
+/// Relative speed of the ball while bullet time is active.
+const SLOW_SCALE: f32 = 0.4;
+/// Focus drained per second while slowing time; a full meter lasts 2.5 seconds.
+const FOCUS_DRAIN: f32 = 0.4;
+const FOCUS_RECHARGE: f32 = 0.2;
+/// Seconds after letting go before the meter starts refilling.
+const RECHARGE_DELAY: f32 = 1.0;
+
+/// Hold-Shift slow motion for the ball, limited by a focus meter from 0.0 to 1.0.
+#[derive(Resource, Clone, PartialEq)]
+pub struct BulletTime {
+    pub focus: f32,
+    pub active: bool,
+    recharge_delay: f32,
+}
+
+impl Default for BulletTime {
+    fn default() -> Self {
+        BulletTime {
+            focus: 1.0,
+            active: false,
+            recharge_delay: 0.0,
+        }
+    }
+}
+
+impl BulletTime {
+    /// Factor to apply to the frame delta when moving the ball.
+    pub fn ball_time_scale(&self) -> f32 {
+        if self.active { SLOW_SCALE } else { 1.0 }
+    }
+}
+
+pub struct BulletTimePlugin;
+
+impl Plugin for BulletTimePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BulletTime>()
+            .add_systems(OnEnter(GameState::Playing), reset_bullet_time)
+            .add_systems(Update, update_bullet_time.run_if(in_state(GameState::Playing)))
+            .add_systems(OnExit(GameState::Playing), end_bullet_time);
+    }
+}
+
+fn reset_bullet_time(mut bullet_time: ResMut<BulletTime>) {
+    *bullet_time = BulletTime::default();
+}
+
+fn update_bullet_time(
+    input: Res<ButtonInput<Key>>,
+    time: Res<Time>,
+    mut bullet_time: ResMut<BulletTime>,
+) {
+    let dt = time.delta_secs();
+    let mut next = bullet_time.clone();
+    next.active = input.pressed(Key::Shift) && next.focus > 0.0;
+
+    if next.active {
+        next.focus = (next.focus - FOCUS_DRAIN * dt).max(0.0);
+        next.recharge_delay = RECHARGE_DELAY;
+    } else if next.recharge_delay > 0.0 {
+        next.recharge_delay -= dt;
+    } else {
+        next.focus = (next.focus + FOCUS_RECHARGE * dt).min(1.0);
+    }
+    // Only marked changed while something is happening, so the HUD can skip idle frames.
+    bullet_time.set_if_neq(next);
+}
+
+fn end_bullet_time(mut bullet_time: ResMut<BulletTime>) {
+    bullet_time.active = false;
+}
@@ -0,0 +1,172 @@
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+
+use crate::seed;
+use crate::{Ball, Collider, GameState, Paddle, Serving, StartButton, Velocity, PADDLE_HEIGHT};
+
+// Codes avoid 'a' and 'd', which already pick the difficulty on the splash screen. They
+// also can't start with a letter that opens another screen from it: 'r' for replays, 'c'
+// for credits, 'h' for high scores or 't' for the tutorial. Those shortcuts hold off
+// while the keys typed last could still become a code, but the first letter of one
+// always looks like the shortcut.
+const RAINBOW_BALL_CODE: &str = "ferris";
+const INVINCIBLE_PADDLE_CODE: &str = "invincible";
+const CODES: [&str; 2] = [RAINBOW_BALL_CODE, INVINCIBLE_PADDLE_CODE];
+
+const CHEAT_TEXT_Y: f32 = -260.0;
+/// Degrees per second the rainbow ball cycles through.
+const RAINBOW_SPEED: f32 = 180.0;
+
+/// Cheats unlocked by typing a code on the splash screen. They last until the game closes.
+#[derive(Resource, Default)]
+pub struct Cheats {
+    pub rainbow_ball: bool,
+    pub invincible_paddle: bool,
+}
+
+impl Cheats {
+    /// Runs with any cheat enabled don't count towards high scores.
+    pub fn any(&self) -> bool {
+        self.rainbow_ball || self.invincible_paddle
+    }
+
+    fn label(&self) -> String {
+        let mut names = Vec::new();
+        if self.rainbow_ball {
+            names.push("rainbow ball");
+        }
+        if self.invincible_paddle {
+            names.push("invincible paddle");
+        }
+        if names.is_empty() {
+            String::new()
+        } else {
+            format!("Cheats: {}", names.join(", "))
+        }
+    }
+}
+
+/// The most recently typed characters, just long enough to hold the longest code.
+#[derive(Resource, Default)]
+pub struct TypedKeys(String);
+
+#[derive(Component)]
+struct CheatText;
+
+pub struct CheatsPlugin;
+
+impl Plugin for CheatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Cheats>()
+            .init_resource::<TypedKeys>()
+            .add_systems(OnEnter(GameState::Splash), (spawn_cheat_text, clear_typed_keys))
+            .add_systems(
+                Update,
+                (detect_cheat_codes, update_cheat_text.run_if(resource_changed::<Cheats>))
+                    .chain()
+                    .run_if(in_state(GameState::Splash).and(seed::not_entering_seed)),
+            )
+            .add_systems(
+                Update,
+                rainbow_ball.run_if(in_state(GameState::Playing).and(|cheats: Res<Cheats>| cheats.rainbow_ball)),
+            )
+            .add_systems(
+                FixedUpdate,
+                invincible_paddle
+                    .after(crate::BallCollision)
+                    .run_if(in_state(GameState::Playing).and(|cheats: Res<Cheats>| cheats.invincible_paddle)),
+            );
+    }
+}
+
+// Tagged as a `StartButton` so it is cleared along with the rest of the splash screen.
+fn spawn_cheat_text(mut commands: Commands, cheats: Res<Cheats>) {
+    commands.spawn((
+        Text2d(cheats.label()),
+        TextFont {
+            font_size: 24.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, CHEAT_TEXT_Y, 2.0),
+        CheatText,
+        StartButton,
+    ));
+}
+
+/// Run condition for splash screen letter shortcuts, which would otherwise go off partway
+/// through a cheat code. Systems using it should run after `detect_cheat_codes`, so the
+/// key just pressed is taken into account.
+pub fn not_typing_code(typed: Res<TypedKeys>) -> bool {
+    !typed
+        .0
+        .char_indices()
+        .any(|(start, _)| CODES.iter().any(|code| code.starts_with(&typed.0[start..])))
+}
+
+fn clear_typed_keys(mut typed: ResMut<TypedKeys>) {
+    typed.0.clear();
+}
+
+pub fn detect_cheat_codes(
+    mut keyboard_events: MessageReader<KeyboardInput>,
+    mut typed: ResMut<TypedKeys>,
+    mut cheats: ResMut<Cheats>,
+) {
+    let max_len = CODES.iter().map(|code| code.len()).max().unwrap_or(0);
+    for event in keyboard_events.read() {
+        if !event.state.is_pressed() || event.repeat {
+            continue;
+        }
+        let Key::Character(ref characters) = event.logical_key else {
+            continue;
+        };
+        typed.0.push_str(&characters.to_lowercase());
+        if typed.0.len() > max_len {
+            let excess = typed.0.len() - max_len;
+            typed.0.drain(..excess);
+        }
+
+        if typed.0.ends_with(RAINBOW_BALL_CODE) {
+            cheats.rainbow_ball = true;
+            typed.0.clear();
+        } else if typed.0.ends_with(INVINCIBLE_PADDLE_CODE) {
+            cheats.invincible_paddle = true;
+            typed.0.clear();
+        }
+    }
+}
+
+fn update_cheat_text(cheats: Res<Cheats>, mut text_query: Query<&mut Text2d, With<CheatText>>) {
+    for mut text in &mut text_query {
+        text.0 = cheats.label();
+    }
+}
+
+fn rainbow_ball(time: Res<Time>, mut ball_query: Query<&mut Sprite, With<Ball>>) {
+    let hue = (time.elapsed_secs() * RAINBOW_SPEED) % 360.0;
+    for mut sprite in &mut ball_query {
+        sprite.color = Color::hsl(hue, 1.0, 0.65);
+    }
+}
+
+// Anything that gets past the paddle's top edge is sent straight back up, as if the
+// paddle had been there to meet it.
+fn invincible_paddle(
+    paddle_query: Query<&Transform, With<Paddle>>,
+    mut ball_query: Query<
+        (&mut Transform, &mut Velocity, &Collider),
+        (With<Ball>, Without<Serving>, Without<Paddle>),
+    >,
+) {
+    let Ok(paddle_transform) = paddle_query.single() else {
+        return;
+    };
+    let paddle_top = paddle_transform.translation.y + PADDLE_HEIGHT / 2.0;
+    for (mut transform, mut velocity, collider) in &mut ball_query {
+        let half_height = collider.size.y / 2.0;
+        if velocity.0.y < 0.0 && transform.translation.y - half_height < paddle_top {
+            transform.translation.y = paddle_top + half_height;
+            velocity.0.y = velocity.0.y.abs();
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+use clap::Parser;
+
+use crate::config::Difficulty;
+use crate::level::CurrentLevel;
+use crate::mutators::Mutator;
+use crate::settings::Settings;
+
+/// Options for launching straight into a particular setup, for testing and speedrun
+/// practice: `pong --level 5 --difficulty hard --windowed`.
+#[derive(Parser, Resource, Debug)]
+#[command(about = "Rusty Pong")]
+pub struct LaunchOptions {
+    /// Level to start on, counting from 1. Skips the splash screen.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub level: Option<u32>,
+    /// Difficulty to play on, remembered like one picked on the splash screen.
+    #[arg(long, value_enum)]
+    pub difficulty: Option<Difficulty>,
+    /// Starts in a window even if fullscreen is saved in the settings.
+    #[arg(long)]
+    pub windowed: bool,
+    /// Mutator to turn on, remembered in the settings. Can be given more than once.
+    #[arg(long = "mutator", value_enum)]
+    pub mutators: Vec<Mutator>,
+}
+
+impl LaunchOptions {
+    /// Applies the options that override saved settings.
+    pub fn apply_to(&self, settings: &mut Settings) {
+        if let Some(difficulty) = self.difficulty {
+            settings.difficulty = difficulty;
+        }
+        if self.windowed {
+            settings.fullscreen = false;
+        }
+        for mutator in &self.mutators {
+            if !settings.mutator(*mutator) {
+                settings.mutators.push(*mutator);
+            }
+        }
+    }
+
+    pub fn starting_level(&self) -> CurrentLevel {
+        CurrentLevel(self.level.map_or(0, |level| level as usize - 1))
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::thread;
+
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::imageops::FilterType;
+use image::{Delay, Frame, RgbaImage};
+
+use crate::screenshot::{self, Toast, SCREENSHOT_DIR};
+
+/// Frames recorded per second of real time. Low enough that capturing doesn't cost
+/// much, high enough that a rally still reads as motion.
+const CLIP_FPS: u32 = 10;
+const CLIP_SECONDS: u32 = 10;
+/// Frames are shrunk to this width before buffering to keep the ring buffer small.
+const CLIP_WIDTH: u32 = 640;
+
+/// The most recent `CLIP_SECONDS` of gameplay, oldest frame first.
+#[derive(Resource, Default)]
+struct ClipBuffer {
+    frames: VecDeque<RgbaImage>,
+    since_last_capture: f32,
+}
+
+pub struct ClipPlugin;
+
+impl Plugin for ClipPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClipBuffer>()
+            .add_systems(Update, (capture_clip_frame, export_clip));
+    }
+}
+
+fn capture_clip_frame(time: Res<Time<Real>>, mut clip: ResMut<ClipBuffer>, mut commands: Commands) {
+    clip.since_last_capture += time.delta_secs();
+    let interval = 1.0 / CLIP_FPS as f32;
+    if clip.since_last_capture < interval {
+        return;
+    }
+    clip.since_last_capture %= interval;
+    commands.spawn(Screenshot::primary_window()).observe(buffer_frame);
+}
+
+fn buffer_frame(captured: On<ScreenshotCaptured>, mut clip: ResMut<ClipBuffer>) {
+    let Ok(image) = captured.image.clone().try_into_dynamic() else {
+        return;
+    };
+    let height = CLIP_WIDTH * image.height() / image.width().max(1);
+    let frame = image.resize_exact(CLIP_WIDTH, height.max(1), FilterType::Triangle).to_rgba8();
+
+    clip.frames.push_back(frame);
+    while clip.frames.len() > (CLIP_FPS * CLIP_SECONDS) as usize {
+        clip.frames.pop_front();
+    }
+}
+
+// Encoding a hundred frames takes a moment, so it happens off the main thread.
+fn export_clip(
+    input: Res<ButtonInput<Key>>,
+    clip: Res<ClipBuffer>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !input.just_pressed(Key::F3) || clip.frames.is_empty() {
+        return;
+    }
+    if let Err(err) = fs::create_dir_all(SCREENSHOT_DIR) {
+        error!("failed to create {SCREENSHOT_DIR}: {err}");
+        return;
+    }
+
+    let path = format!("{SCREENSHOT_DIR}/clip-{}.gif", screenshot::timestamp());
+    let frames: Vec<RgbaImage> = clip.frames.iter().cloned().collect();
+    let output = path.clone();
+    thread::spawn(move || {
+        let file = match File::create(&output) {
+            Ok(file) => file,
+            Err(err) => {
+                error!("failed to create {output}: {err}");
+                return;
+            }
+        };
+        let mut encoder = GifEncoder::new(file);
+        if let Err(err) = encoder.set_repeat(Repeat::Infinite) {
+            error!("failed to write {output}: {err}");
+            return;
+        }
+        let delay = Delay::from_numer_denom_ms(1000, CLIP_FPS);
+        let frames = frames
+            .into_iter()
+            .map(|buffer| Frame::from_parts(buffer, 0, 0, delay));
+        if let Err(err) = encoder.encode_frames(frames) {
+            error!("failed to write {output}: {err}");
+        }
+    });
+    screenshot::show_toast(&mut commands, &toast_query, format!("Saving {path}"));
+}
@@ -0,0 +1,268 @@
+#[cfg(not(feature = "rapier"))]
+use std::collections::HashMap;
+
+#[cfg(not(feature = "rapier"))]
+use bevy::math::bounding::{Aabb2d, BoundingVolume, IntersectsVolume, RayCast2d};
+use bevy::prelude::*;
+
+/// Side length of a `BlockGrid` cell, a little larger than a block.
+#[cfg(not(feature = "rapier"))]
+const GRID_CELL_SIZE: f32 = 100.0;
+
+/// A face of a box, from the box's own point of view. For the arena's walls, which wall.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Side {
+    /// The face with the given outward normal.
+    pub fn from_normal(normal: Vec2) -> Self {
+        if normal.x.abs() > normal.y.abs() {
+            if normal.x > 0.0 {
+                Side::Right
+            } else {
+                Side::Left
+            }
+        } else if normal.y > 0.0 {
+            Side::Top
+        } else {
+            Side::Bottom
+        }
+    }
+}
+
+/// Sent when the ball bounces off a paddle, with the ball's speed as it arrived.
+#[derive(Message)]
+pub struct BallHitPaddle {
+    pub ball: Entity,
+    pub side: Side,
+    pub speed: f32,
+}
+
+/// Sent when the ball bounces off one of the arena's walls.
+#[derive(Message)]
+pub struct BallHitWall {
+    pub ball: Entity,
+    pub side: Side,
+}
+
+/// Sent when the ball strikes a block, before any damage is done to it.
+#[derive(Message)]
+pub struct BallHitBlock {
+    pub ball: Entity,
+    pub entity: Entity,
+    pub side: Side,
+    pub speed: f32,
+}
+
+/// Uniform grid over the blocks' boxes, so the ball only needs testing against the
+/// blocks near its path instead of every block in the level.
+#[cfg(not(feature = "rapier"))]
+#[derive(Resource, Default)]
+pub struct BlockGrid {
+    cells: HashMap<IVec2, Vec<Entity>>,
+}
+
+#[cfg(not(feature = "rapier"))]
+impl BlockGrid {
+    fn cells_covering(area: Aabb2d) -> impl Iterator<Item = IVec2> {
+        let min = (area.min / GRID_CELL_SIZE).floor().as_ivec2();
+        let max = (area.max / GRID_CELL_SIZE).floor().as_ivec2();
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    pub fn insert(&mut self, entity: Entity, area: Aabb2d) {
+        for cell in Self::cells_covering(area) {
+            self.cells.entry(cell).or_default().push(entity);
+        }
+    }
+
+    /// Every block whose box shares a cell with `area`, each listed once.
+    pub fn nearby(&self, area: Aabb2d) -> Vec<Entity> {
+        let mut entities: Vec<Entity> = Self::cells_covering(area)
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+            .collect();
+        entities.sort_unstable();
+        entities.dedup();
+        entities
+    }
+}
+
+/// Where a moving box first touches another box along its path.
+#[cfg(not(feature = "rapier"))]
+#[derive(Clone, Copy, Debug)]
+pub struct SweepHit {
+    /// Fraction of the displacement travelled before contact, from 0 to 1.
+    pub time: f32,
+    /// Outward normal of the face that was hit.
+    pub normal: Vec2,
+    /// How far the boxes already overlapped along the normal, for hits at time 0.
+    pub penetration: f32,
+}
+
+/// Sweeps the `moving` box along `displacement` against a stationary `target`.
+///
+/// Boxes that already overlap at the start count as a hit at time 0 on the face with
+/// the least penetration, but only while moving further in; a box on its way out is
+/// left alone so it can't get stuck. Pushing the moving box out by `penetration` along
+/// the normal separates them.
+#[cfg(not(feature = "rapier"))]
+pub fn sweep_aabb(moving: Aabb2d, displacement: Vec2, target: Aabb2d) -> Option<SweepHit> {
+    // Grow the target by the moving box so the sweep becomes a ray cast from its center.
+    let start = moving.center();
+    let expanded = target.grow(moving.half_size());
+    let offset = start - expanded.center();
+
+    if moving.intersects(&target) {
+        let penetration = expanded.half_size() - offset.abs();
+        let (normal, penetration) = if penetration.x < penetration.y {
+            (Vec2::new(offset.x.signum(), 0.0), penetration.x)
+        } else {
+            (Vec2::new(0.0, offset.y.signum()), penetration.y)
+        };
+        return (displacement.dot(normal) < 0.0).then_some(SweepHit {
+            time: 0.0,
+            normal,
+            penetration,
+        });
+    }
+
+    let direction = Dir2::new(displacement).ok()?;
+    let length = displacement.length();
+    let distance = RayCast2d::new(start, direction, length).aabb_intersection_at(&expanded)?;
+
+    // The face hit is the one the contact point lies on, i.e. its furthest axis.
+    let contact = (start + direction * distance - expanded.center()) / expanded.half_size();
+    let normal = if contact.x.abs() >= contact.y.abs() {
+        Vec2::new(contact.x.signum(), 0.0)
+    } else {
+        Vec2::new(0.0, contact.y.signum())
+    };
+    Some(SweepHit {
+        time: distance / length,
+        normal,
+        penetration: 0.0,
+    })
+}
+
+/// Turns a velocity away from the face with the given normal.
+#[cfg(not(feature = "rapier"))]
+pub fn bounce(velocity: Vec2, normal: Vec2) -> Vec2 {
+    let into_face = velocity.dot(normal);
+    if into_face < 0.0 {
+        velocity - 2.0 * into_face * normal
+    } else {
+        velocity
+    }
+}
+
+#[cfg(all(test, not(feature = "rapier")))]
+mod tests {
+    use super::*;
+
+    fn square(center: Vec2, half_size: f32) -> Aabb2d {
+        Aabb2d::new(center, Vec2::splat(half_size))
+    }
+
+    fn approx(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn sweep_head_on() {
+        let target = Aabb2d::new(Vec2::new(0.0, 50.0), Vec2::new(20.0, 5.0));
+        let hit = sweep_aabb(square(Vec2::ZERO, 5.0), Vec2::new(0.0, 100.0), target).unwrap();
+        assert!(approx(hit.time, 0.4));
+        assert_eq!(hit.normal, Vec2::NEG_Y);
+        assert_eq!(hit.penetration, 0.0);
+    }
+
+    #[test]
+    fn sweep_into_corner_hits_side_face() {
+        let target = square(Vec2::new(50.0, 50.0), 10.0);
+        let hit = sweep_aabb(square(Vec2::ZERO, 5.0), Vec2::new(100.0, 100.0), target).unwrap();
+        assert!(approx(hit.time, 0.35));
+        assert_eq!(hit.normal, Vec2::NEG_X);
+    }
+
+    #[test]
+    fn sweep_clipping_an_edge() {
+        let moving = square(Vec2::ZERO, 5.0);
+        let displacement = Vec2::new(100.0, 0.0);
+
+        let clipped = sweep_aabb(moving, displacement, square(Vec2::new(50.0, 14.0), 10.0)).unwrap();
+        assert!(approx(clipped.time, 0.35));
+        assert_eq!(clipped.normal, Vec2::NEG_X);
+
+        assert!(sweep_aabb(moving, displacement, square(Vec2::new(50.0, 20.0), 10.0)).is_none());
+    }
+
+    #[test]
+    fn sweep_already_overlapping() {
+        let moving = square(Vec2::ZERO, 5.0);
+        let target = square(Vec2::new(0.0, 8.0), 5.0);
+
+        let hit = sweep_aabb(moving, Vec2::new(0.0, 10.0), target).unwrap();
+        assert_eq!(hit.time, 0.0);
+        assert_eq!(hit.normal, Vec2::NEG_Y);
+        assert!(approx(hit.penetration, 2.0));
+
+        assert!(sweep_aabb(moving, Vec2::new(0.0, -10.0), target).is_none());
+    }
+
+    #[test]
+    fn sweep_without_moving() {
+        let moving = square(Vec2::ZERO, 5.0);
+        assert!(sweep_aabb(moving, Vec2::ZERO, square(Vec2::new(0.0, 50.0), 5.0)).is_none());
+        assert!(sweep_aabb(moving, Vec2::ZERO, square(Vec2::new(0.0, 8.0), 5.0)).is_none());
+    }
+
+    #[test]
+    fn bounce_reflects_only_into_the_face() {
+        assert_eq!(bounce(Vec2::new(3.0, -4.0), Vec2::Y), Vec2::new(3.0, 4.0));
+        assert_eq!(bounce(Vec2::new(-3.0, 4.0), Vec2::X), Vec2::new(3.0, 4.0));
+        assert_eq!(bounce(Vec2::new(3.0, 4.0), Vec2::Y), Vec2::new(3.0, 4.0));
+    }
+
+    #[test]
+    fn side_from_normal() {
+        assert_eq!(Side::from_normal(Vec2::X), Side::Right);
+        assert_eq!(Side::from_normal(Vec2::NEG_X), Side::Left);
+        assert_eq!(Side::from_normal(Vec2::Y), Side::Top);
+        assert_eq!(Side::from_normal(Vec2::NEG_Y), Side::Bottom);
+        assert_eq!(Side::from_normal(Vec2::new(0.9, -0.2)), Side::Right);
+        // Diagonals count as top or bottom.
+        assert_eq!(Side::from_normal(Vec2::new(-1.0, -1.0)), Side::Bottom);
+    }
+
+    #[test]
+    fn grid_finds_nearby_blocks_once() {
+        let mut world = World::new();
+        let (near, wide, far) = (world.spawn_empty().id(), world.spawn_empty().id(), world.spawn_empty().id());
+        let mut grid = BlockGrid::default();
+        grid.insert(near, square(Vec2::new(50.0, 50.0), 10.0));
+        // Spans the cells on either side of x = 100.
+        grid.insert(wide, Aabb2d::new(Vec2::new(100.0, 20.0), Vec2::new(20.0, 10.0)));
+        grid.insert(far, square(Vec2::new(550.0, 550.0), 10.0));
+
+        let nearby = grid.nearby(Aabb2d::new(Vec2::new(100.0, 50.0), Vec2::new(60.0, 10.0)));
+        let mut expected = vec![near, wide];
+        expected.sort_unstable();
+        assert_eq!(nearby, expected);
+
+        assert!(grid.nearby(square(Vec2::new(-250.0, 50.0), 5.0)).is_empty());
+
+        grid.clear();
+        assert!(grid.nearby(square(Vec2::new(50.0, 50.0), 10.0)).is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::new_game_plus::{self, NewGamePlus};
+use crate::settings::Settings;
+use crate::GameState;
+use crate::{BALL_SPEED_MAX, BALL_START_SPEED, PADDLE_WIDTH};
+
+pub const CONFIG_PATH: &str = "assets/config.ron";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default, clap::ValueEnum)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Hard,
+            Difficulty::Normal => Difficulty::Easy,
+            Difficulty::Hard => Difficulty::Normal,
+        }
+    }
+}
+
+/// How much each kind of hit speeds the ball up (above 1.0) or slows it down (below).
+#[derive(Reflect, Deserialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct SpeedRamp {
+    /// Bouncing off the top of the paddle.
+    pub paddle: f32,
+    /// Bouncing off the underside of the paddle.
+    pub paddle_underside: f32,
+    pub block: f32,
+    /// Bouncing off the top or bottom of the arena.
+    pub wall: f32,
+    /// Bumping the ball with a well-timed press.
+    pub bump: f32,
+}
+
+impl Default for SpeedRamp {
+    fn default() -> Self {
+        SpeedRamp {
+            paddle: 1.15,
+            paddle_underside: 1.3,
+            block: 1.1,
+            wall: 0.9,
+            bump: 1.5,
+        }
+    }
+}
+
+/// Gameplay tuning for the difficulty currently selected in `Settings`.
+#[derive(Resource, Reflect, Deserialize, Clone, Debug)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct GameConfig {
+    pub ball_start_speed: f32,
+    pub ball_speed_max: f32,
+    pub paddle_width: f32,
+    /// How far, in radians, the paddle's surface tilts at its ends, as if its top were
+    /// convex. At 0.0 the top is flat and the bounce angle comes from where the ball
+    /// lands alone; above it, the ball reflects off the tilted surface instead.
+    pub paddle_curvature: f32,
+    /// Hits a block takes to break.
+    pub block_hp: u32,
+    pub speed_ramp: SpeedRamp,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            ball_start_speed: BALL_START_SPEED,
+            ball_speed_max: BALL_SPEED_MAX,
+            paddle_width: PADDLE_WIDTH,
+            paddle_curvature: 0.0,
+            block_hp: 1,
+            speed_ramp: SpeedRamp::default(),
+        }
+    }
+}
+
+impl GameConfig {
+    /// Where `speed` sits between the start and max speed, from 0.0 to 1.0.
+    pub fn speed_fraction(&self, speed: f32) -> f32 {
+        ((speed - self.ball_start_speed) / (self.ball_speed_max - self.ball_start_speed))
+            .clamp(0.0, 1.0)
+    }
+
+    /// Scales a ball velocity by `multiplier`, keeping its speed between the start and max speed.
+    pub fn ramp_speed(&self, velocity: Vec2, multiplier: f32) -> Vec2 {
+        let speed = (velocity.length() * multiplier).clamp(self.ball_start_speed, self.ball_speed_max);
+        velocity.normalize_or_zero() * speed
+    }
+}
+
+/// Values a difficulty replaces; anything left as `None` keeps the base value.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+struct ConfigOverrides {
+    ball_start_speed: Option<f32>,
+    ball_speed_max: Option<f32>,
+    paddle_width: Option<f32>,
+    paddle_curvature: Option<f32>,
+    block_hp: Option<u32>,
+    speed_ramp: Option<SpeedRamp>,
+}
+
+/// The parsed contents of `config.ron`, kept around so the active `GameConfig` can be
+/// rebuilt when the difficulty changes.
+#[derive(Resource, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct ConfigFile {
+    base: GameConfig,
+    easy: ConfigOverrides,
+    normal: ConfigOverrides,
+    hard: ConfigOverrides,
+}
+
+impl ConfigFile {
+    pub fn load() -> Self {
+        let config = fs::read_to_string(CONFIG_PATH)
+            .map_err(|err| err.to_string())
+            .and_then(|contents| ron::from_str(&contents).map_err(|err| err.to_string()));
+        config.unwrap_or_else(|err| {
+            error!("failed to load {CONFIG_PATH}: {err}");
+            ConfigFile::default()
+        })
+    }
+
+    /// Tuning for `difficulty`, made tougher for each New Game+ `cycle` on top.
+    pub fn resolve(&self, difficulty: Difficulty, cycle: u32) -> GameConfig {
+        let overrides = match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Normal => &self.normal,
+            Difficulty::Hard => &self.hard,
+        };
+        let base = &self.base;
+        let speed_scale = 1.0 + new_game_plus::SPEED_STEP * cycle as f32;
+        GameConfig {
+            ball_start_speed: overrides.ball_start_speed.unwrap_or(base.ball_start_speed) * speed_scale,
+            ball_speed_max: overrides.ball_speed_max.unwrap_or(base.ball_speed_max) * speed_scale,
+            paddle_width: overrides.paddle_width.unwrap_or(base.paddle_width),
+            paddle_curvature: overrides.paddle_curvature.unwrap_or(base.paddle_curvature),
+            block_hp: overrides.block_hp.unwrap_or(base.block_hp).max(1) + new_game_plus::EXTRA_BLOCK_HP * cycle,
+            speed_ramp: overrides.speed_ramp.unwrap_or(base.speed_ramp),
+        }
+    }
+}
+
+pub struct ConfigPlugin;
+
+impl Plugin for ConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ConfigFile::load())
+            .init_resource::<GameConfig>()
+            .add_systems(PreStartup, apply_difficulty)
+            // A New Game+ picked on the win screen has to be in place before its first level
+            // is laid out.
+            .add_systems(OnExit(GameState::GameWon), apply_difficulty)
+            .add_systems(
+                Update,
+                apply_difficulty.run_if(resource_changed::<Settings>.or(resource_changed::<NewGamePlus>)),
+            );
+    }
+}
+
+fn apply_difficulty(
+    file: Res<ConfigFile>,
+    settings: Res<Settings>,
+    cycle: Res<NewGamePlus>,
+    mut config: ResMut<GameConfig>,
+) {
+    *config = file.resolve(settings.difficulty, cycle.0);
+}
@@ -0,0 +1,315 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use serde::{Deserialize, Serialize};
+
+use crate::display::GameCamera;
+use crate::replay::{ReplayFrame, ReplayPlayback};
+use crate::screenshot::{self, Toast};
+use crate::settings::Settings;
+use crate::touch;
+
+/// Stick deflection ignored around the center, so a resting stick doesn't drift the paddle.
+const STICK_DEADZONE: f32 = 0.15;
+
+/// Which device drives the paddle besides the keyboard, cycled with Ctrl+I.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ControlScheme {
+    #[default]
+    Keyboard,
+    Gamepad,
+    /// The paddle follows the cursor and left click bumps.
+    Mouse,
+}
+
+impl ControlScheme {
+    fn name(self) -> &'static str {
+        match self {
+            ControlScheme::Keyboard => "Keyboard",
+            ControlScheme::Gamepad => "Gamepad",
+            ControlScheme::Mouse => "Mouse",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            ControlScheme::Keyboard => ControlScheme::Gamepad,
+            ControlScheme::Gamepad => ControlScheme::Mouse,
+            ControlScheme::Mouse => ControlScheme::Keyboard,
+        }
+    }
+}
+
+/// Which player a paddle belongs to, and so which `InputProfile` in the settings moves it.
+/// Player 0 also gets the gamepad and mouse, depending on the control scheme.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct PlayerId(pub usize);
+
+/// Keyboard bindings for one player. Keys are written as the character they type
+/// (`"a"`) or by name (`"ArrowLeft"`, `"Space"`, `"Enter"`).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct InputProfile {
+    pub left: Vec<String>,
+    pub right: Vec<String>,
+    pub bump: Vec<String>,
+    /// Only used with advanced controls on.
+    #[serde(default)]
+    pub up: Vec<String>,
+    #[serde(default)]
+    pub down: Vec<String>,
+}
+
+impl InputProfile {
+    /// A single player on both A/D and the arrow keys. Players sharing a keyboard
+    /// split these up between their own profiles.
+    pub fn defaults() -> Vec<InputProfile> {
+        let keys = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        vec![InputProfile {
+            left: keys(&["a", "ArrowLeft"]),
+            right: keys(&["d", "ArrowRight"]),
+            bump: keys(&["Space"]),
+            up: keys(&["w"]),
+            down: keys(&["s"]),
+        }]
+    }
+
+    fn keys(&self) -> impl Iterator<Item = &String> {
+        self.left
+            .iter()
+            .chain(&self.right)
+            .chain(&self.bump)
+            .chain(&self.up)
+            .chain(&self.down)
+    }
+}
+
+fn parse_key(name: &str) -> Key {
+    match name {
+        "Space" => Key::Space,
+        "Enter" => Key::Enter,
+        "Tab" => Key::Tab,
+        "ArrowLeft" => Key::ArrowLeft,
+        "ArrowRight" => Key::ArrowRight,
+        "ArrowUp" => Key::ArrowUp,
+        "ArrowDown" => Key::ArrowDown,
+        _ => Key::Character(name.to_lowercase().into()),
+    }
+}
+
+/// The gamepad gameplay listens to: the first one connected, until it is unplugged.
+#[derive(Resource, Default)]
+pub struct ActiveGamepad(pub Option<Entity>);
+
+/// Paddle and bump input from the keyboard plus whichever device the control scheme adds,
+/// or from the replay being played back. Touch input is handled separately, since it
+/// always works.
+#[derive(SystemParam)]
+pub struct GameplayInput<'w, 's> {
+    keys: Res<'w, ButtonInput<Key>>,
+    settings: Res<'w, Settings>,
+    active_gamepad: Res<'w, ActiveGamepad>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    window_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    camera_query: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<GameCamera>>,
+    playback: Option<Res<'w, ReplayPlayback>>,
+}
+
+impl GameplayInput<'_, '_> {
+    fn replay_frame(&self) -> Option<&ReplayFrame> {
+        self.playback.as_ref().and_then(|playback| playback.current())
+    }
+
+    fn gamepad(&self) -> Option<&Gamepad> {
+        if self.settings.control_scheme != ControlScheme::Gamepad {
+            return None;
+        }
+        self.active_gamepad.0.and_then(|entity| self.gamepads.get(entity).ok())
+    }
+
+    fn profile(&self, player: PlayerId) -> Option<&InputProfile> {
+        self.settings.input_profiles.get(player.0)
+    }
+
+    fn any_pressed(&self, names: &[String]) -> bool {
+        names.iter().any(|name| self.keys.pressed(parse_key(name)))
+    }
+
+    fn any_just_pressed(&self, names: &[String]) -> bool {
+        names.iter().any(|name| self.keys.just_pressed(parse_key(name)))
+    }
+
+    fn mouse_scheme(&self) -> bool {
+        self.settings.control_scheme == ControlScheme::Mouse
+    }
+
+    /// World x coordinate of the cursor when the paddle is mouse-controlled and the
+    /// cursor is over the window.
+    pub fn mouse_target_x(&self) -> Option<f32> {
+        if let Some(frame) = self.replay_frame() {
+            return frame.target_x;
+        }
+        if !self.mouse_scheme() {
+            return None;
+        }
+        let cursor = self.window_query.single().ok()?.cursor_position()?;
+        let (camera, camera_transform) = self.camera_query.single().ok()?;
+        touch::to_world(camera, camera_transform, cursor).map(|world| world.x)
+    }
+
+    /// Horizontal movement from -1.0 (left) to 1.0 (right). Keys and the D-pad are
+    /// all-or-nothing, while the stick gives anything in between for fine positioning.
+    pub fn direction(&self, player: PlayerId) -> f32 {
+        if let Some(frame) = self.replay_frame() {
+            return frame.players.get(player.0).map_or(0.0, |input| input.direction);
+        }
+        let mut direction = 0.0;
+        if let Some(profile) = self.profile(player) {
+            if self.any_pressed(&profile.left) {
+                direction -= 1.0;
+            }
+            if self.any_pressed(&profile.right) {
+                direction += 1.0;
+            }
+        }
+        if let Some(gamepad) = self.gamepad().filter(|_| player == PlayerId(0)) {
+            if gamepad.pressed(GamepadButton::DPadLeft) {
+                direction -= 1.0;
+            }
+            if gamepad.pressed(GamepadButton::DPadRight) {
+                direction += 1.0;
+            }
+            // Rescaled so movement starts from zero at the edge of the deadzone.
+            let stick = gamepad.left_stick().x;
+            let deflection = ((stick.abs() - STICK_DEADZONE) / (1.0 - STICK_DEADZONE)).max(0.0);
+            direction += deflection * stick.signum();
+        }
+        direction.clamp(-1.0, 1.0)
+    }
+
+    /// Vertical movement from -1.0 (down) to 1.0 (up), from the player's up and down keys
+    /// or the D-pad. Always 0.0 unless advanced controls are on.
+    pub fn vertical(&self, player: PlayerId) -> f32 {
+        if let Some(frame) = self.replay_frame() {
+            return frame.players.get(player.0).map_or(0.0, |input| input.vertical);
+        }
+        if !self.settings.advanced_controls {
+            return 0.0;
+        }
+        let mut vertical = 0.0;
+        if let Some(profile) = self.profile(player) {
+            if self.any_pressed(&profile.up) {
+                vertical += 1.0;
+            }
+            if self.any_pressed(&profile.down) {
+                vertical -= 1.0;
+            }
+        }
+        if let Some(gamepad) = self.gamepad().filter(|_| player == PlayerId(0)) {
+            if gamepad.pressed(GamepadButton::DPadUp) {
+                vertical += 1.0;
+            }
+            if gamepad.pressed(GamepadButton::DPadDown) {
+                vertical -= 1.0;
+            }
+        }
+        vertical.clamp(-1.0, 1.0)
+    }
+
+    /// The player's bump keys, plus left click or the gamepad's south button for player
+    /// 0. Serves and bumps.
+    pub fn bump_just_pressed(&self, player: PlayerId) -> bool {
+        if let Some(frame) = self.replay_frame() {
+            return frame.players.get(player.0).is_some_and(|input| input.bump);
+        }
+        if self.profile(player).is_some_and(|profile| self.any_just_pressed(&profile.bump)) {
+            return true;
+        }
+        player == PlayerId(0)
+            && ((self.mouse_scheme() && self.mouse.just_pressed(MouseButton::Left))
+                || self
+                    .gamepad()
+                    .is_some_and(|gamepad| gamepad.just_pressed(GamepadButton::South)))
+    }
+
+    pub fn pause_just_pressed(&self) -> bool {
+        self.keys.just_pressed(Key::Escape)
+            || self
+                .gamepad()
+                .is_some_and(|gamepad| gamepad.just_pressed(GamepadButton::Start))
+    }
+}
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveGamepad>()
+            .add_systems(Startup, warn_on_shared_keys)
+            .add_systems(Update, (bind_gamepad, cycle_control_scheme, toggle_advanced_controls));
+    }
+}
+
+// Two profiles binding the same key would move both paddles at once, so point it out
+// rather than silently letting one player steal the other's input.
+fn warn_on_shared_keys(settings: Res<Settings>) {
+    let profiles = &settings.input_profiles;
+    for (i, profile) in profiles.iter().enumerate() {
+        for other in &profiles[i + 1..] {
+            for key in profile.keys().filter(|key| other.keys().any(|other_key| other_key == *key)) {
+                error!("key {key:?} is bound in more than one input profile");
+            }
+        }
+    }
+}
+
+pub fn bind_gamepad(
+    mut connection_events: MessageReader<GamepadConnectionEvent>,
+    gamepads: Query<Entity, With<Gamepad>>,
+    mut active: ResMut<ActiveGamepad>,
+) {
+    for event in connection_events.read() {
+        match event.connection {
+            GamepadConnection::Connected { .. } => {
+                if active.0.is_none() {
+                    active.0 = Some(event.gamepad);
+                }
+            }
+            GamepadConnection::Disconnected => {
+                if active.0 == Some(event.gamepad) {
+                    active.0 = gamepads.iter().find(|entity| *entity != event.gamepad);
+                }
+            }
+        }
+    }
+}
+
+fn cycle_control_scheme(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if input.pressed(Key::Control) && input.just_pressed(Key::Character("i".into())) {
+        settings.control_scheme = settings.control_scheme.next();
+        settings.save();
+        let message = format!("Controls: {}", settings.control_scheme.name());
+        screenshot::show_toast(&mut commands, &toast_query, message);
+    }
+}
+
+fn toggle_advanced_controls(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if input.pressed(Key::Control) && input.just_pressed(Key::Character("u".into())) {
+        settings.advanced_controls = !settings.advanced_controls;
+        settings.save();
+        let state = if settings.advanced_controls { "on" } else { "off" };
+        screenshot::show_toast(&mut commands, &toast_query, format!("Advanced controls {state}"));
+    }
+}
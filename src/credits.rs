@@ -0,0 +1,166 @@
+use std::fs;
+
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::cheats;
+use crate::display::GameCamera;
+use crate::menu::{self, MenuActivated};
+use crate::seed;
+use crate::{GameState, SplashScreen, StartButton, WINDOW_HEIGHT};
+
+const CREDITS_PATH: &str = "assets/credits.ron";
+/// How fast the credits roll up the screen, in pixels per second.
+const SCROLL_SPEED: f32 = 60.0;
+const LINE_SPACING: f32 = 34.0;
+/// Extra space above each heading after the first.
+const SECTION_GAP: f32 = 40.0;
+
+#[derive(Deserialize)]
+struct CreditsSection {
+    heading: String,
+    lines: Vec<String>,
+}
+
+/// Everything on the credits screen, scrolled up as one.
+#[derive(Component)]
+struct CreditsRoll {
+    /// Distance from the first line to the last.
+    height: f32,
+}
+
+#[derive(Component)]
+struct CreditsHint;
+
+pub struct CreditsPlugin;
+
+impl Plugin for CreditsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Splash), spawn_credits_hint)
+            .add_systems(OnExit(GameState::Splash), despawn_credits_hint)
+            .add_systems(
+                Update,
+                open_credits
+                    .after(cheats::detect_cheat_codes)
+                    .run_if(in_state(GameState::Splash).and(seed::not_entering_seed).and(cheats::not_typing_code)),
+            )
+            .add_systems(OnEnter(GameState::Credits), spawn_credits)
+            .add_systems(Update, roll_credits.run_if(in_state(GameState::Credits)))
+            .add_systems(OnExit(GameState::Credits), despawn_credits);
+    }
+}
+
+fn load_credits() -> Vec<CreditsSection> {
+    let credits = fs::read_to_string(CREDITS_PATH)
+        .map_err(|err| err.to_string())
+        .and_then(|contents| ron::from_str(&contents).map_err(|err| err.to_string()));
+    credits.unwrap_or_else(|err| {
+        error!("failed to load {CREDITS_PATH}: {err}");
+        Vec::new()
+    })
+}
+
+fn spawn_credits_hint(mut commands: Commands) {
+    commands.spawn((
+        Text2d("C: Credits".to_string()),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, 280.0, 2.0),
+        CreditsHint,
+    ));
+}
+
+fn despawn_credits_hint(mut commands: Commands, hint_query: Query<Entity, With<CreditsHint>>) {
+    for entity in &hint_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn open_credits(
+    input: Res<ButtonInput<Key>>,
+    mut commands: Commands,
+    splash_query: Query<Entity, Or<(With<SplashScreen>, With<StartButton>)>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if input.pressed(Key::Control) || !input.just_pressed(Key::Character("c".into())) {
+        return;
+    }
+    for entity in &splash_query {
+        commands.entity(entity).despawn();
+    }
+    next_state.set(GameState::Credits);
+}
+
+// Starts just below the bottom of the screen and rolls up from there.
+fn spawn_credits(mut commands: Commands) {
+    let mut y = 0.0;
+    let mut lines = Vec::new();
+    for (index, section) in load_credits().into_iter().enumerate() {
+        if index > 0 {
+            y -= SECTION_GAP;
+        }
+        lines.push((section.heading, 32.0, y));
+        for line in section.lines {
+            y -= LINE_SPACING;
+            lines.push((line, 22.0, y));
+        }
+        y -= LINE_SPACING;
+    }
+
+    commands
+        .spawn((
+            Transform::from_xyz(0.0, -WINDOW_HEIGHT / 2.0 - LINE_SPACING, 2.0),
+            Visibility::default(),
+            CreditsRoll { height: -y },
+        ))
+        .with_children(|parent| {
+            for (line, font_size, y) in lines {
+                parent.spawn((
+                    Text2d(line),
+                    TextFont {
+                        font_size,
+                        ..default()
+                    },
+                    Transform::from_xyz(0.0, y, 0.0),
+                ));
+            }
+        });
+}
+
+fn roll_credits(
+    time: Res<Time>,
+    input: Res<ButtonInput<Key>>,
+    gamepads: Query<&Gamepad>,
+    mut activated: MessageReader<MenuActivated>,
+    mut roll_query: Query<(&mut Transform, &CreditsRoll)>,
+    camera_query: Query<Entity, With<GameCamera>>,
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let mut finished = false;
+    for (mut transform, roll) in &mut roll_query {
+        transform.translation.y += SCROLL_SPEED * time.delta_secs();
+        finished |= transform.translation.y - roll.height > WINDOW_HEIGHT / 2.0 + LINE_SPACING;
+    }
+    let dismissed = input.just_pressed(Key::Escape)
+        || input.just_pressed(Key::Space)
+        || menu::gamepad_just_pressed(&gamepads, GamepadButton::East)
+        || activated.read().count() > 0;
+    if !finished && !dismissed {
+        return;
+    }
+    // The splash screen brings its own camera.
+    for entity in &camera_query {
+        commands.entity(entity).despawn();
+    }
+    next_state.set(GameState::Splash);
+}
+
+fn despawn_credits(mut commands: Commands, roll_query: Query<Entity, With<CreditsRoll>>) {
+    for entity in &roll_query {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+
+use crate::collision::{BallHitWall, Side};
+use crate::display::ARENA;
+use crate::mutators::Mutator;
+use crate::screenshot::{self, Toast};
+use crate::settings::Settings;
+use crate::{Ball, GameState, SideWall, SIDE_WALL_THICKNESS};
+
+const SEGMENTS_PER_WALL: usize = 8;
+/// Ball impacts a segment takes before it breaks open.
+const SEGMENT_HEALTH: u32 = 3;
+/// Space between neighbouring segments, so the wall reads as bricks.
+const SEGMENT_GAP: f32 = 4.0;
+
+/// One stretch of a side wall under the crumbling-walls mutator, drawn as a child of the
+/// wall so it follows it when the level moves the walls.
+#[derive(Component)]
+struct WallSegment {
+    index: usize,
+    health: u32,
+}
+
+/// Segments of each side wall that have been broken open, counted from the bottom. The
+/// ball goes straight through these, and out of the arena.
+#[derive(Resource, Default)]
+pub struct WallHoles {
+    left: Vec<usize>,
+    right: Vec<usize>,
+}
+
+impl WallHoles {
+    fn on(&self, side: Side) -> &[usize] {
+        match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+            Side::Top | Side::Bottom => &[],
+        }
+    }
+
+    /// Whether the wall on `side` has been broken open anywhere.
+    pub fn breached(&self, side: Side) -> bool {
+        !self.on(side).is_empty()
+    }
+
+    /// Whether a ball centered at height `y` fits through a hole in the wall on `side`
+    /// without touching any segment still standing.
+    pub fn lets_through(&self, side: Side, y: f32, half_size: f32) -> bool {
+        let holes = self.on(side);
+        let (Some(bottom), Some(top)) = (segment_at(y - half_size), segment_at(y + half_size)) else {
+            return false;
+        };
+        (bottom..=top).all(|index| holes.contains(&index))
+    }
+}
+
+/// The crumbling-walls mutator, which builds the side walls from segments that chip
+/// with every impact and leave a hole once broken.
+pub struct CrumblingWallsPlugin;
+
+impl Plugin for CrumblingWallsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WallHoles>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                (
+                    reset_holes,
+                    spawn_segments.after(crate::setup_game).run_if(crumbling_walls),
+                ),
+            )
+            .add_systems(Update, chip_segments.run_if(in_state(GameState::Playing).and(crumbling_walls)));
+    }
+}
+
+fn crumbling_walls(settings: Res<Settings>) -> bool {
+    settings.mutator(Mutator::CrumblingWalls)
+}
+
+fn segment_height() -> f32 {
+    ARENA.height / SEGMENTS_PER_WALL as f32
+}
+
+fn segment_at(y: f32) -> Option<usize> {
+    let index = ((y + ARENA.half_height()) / segment_height()).floor();
+    (0.0..SEGMENTS_PER_WALL as f32)
+        .contains(&index)
+        .then_some(index as usize)
+}
+
+fn segment_y(index: usize) -> f32 {
+    -ARENA.half_height() + segment_height() * (index as f32 + 0.5)
+}
+
+fn segment_size() -> Vec2 {
+    Vec2::new(SIDE_WALL_THICKNESS, segment_height() - SEGMENT_GAP)
+}
+
+fn segment_color(health: u32) -> Color {
+    let lightness = 0.35 + 0.65 * health as f32 / SEGMENT_HEALTH as f32;
+    Color::srgb(lightness, lightness, lightness)
+}
+
+fn reset_holes(mut holes: ResMut<WallHoles>) {
+    *holes = WallHoles::default();
+}
+
+// The wall's own sprite is hidden, leaving the segments to draw it.
+fn spawn_segments(mut commands: Commands, mut wall_query: Query<(Entity, &mut Sprite), With<SideWall>>) {
+    for (wall, mut sprite) in &mut wall_query {
+        sprite.color = Color::NONE;
+        commands.entity(wall).with_children(|parent| {
+            for index in 0..SEGMENTS_PER_WALL {
+                parent.spawn((
+                    Sprite {
+                        color: segment_color(SEGMENT_HEALTH),
+                        custom_size: Some(segment_size()),
+                        ..default()
+                    },
+                    Transform::from_xyz(0.0, segment_y(index), 0.1),
+                    WallSegment {
+                        index,
+                        health: SEGMENT_HEALTH,
+                    },
+                ));
+            }
+        });
+    }
+}
+
+fn chip_segments(
+    mut wall_hits: MessageReader<BallHitWall>,
+    mut holes: ResMut<WallHoles>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+    ball_query: Query<&Transform, With<Ball>>,
+    wall_query: Query<(&SideWall, &Children)>,
+    mut segment_query: Query<(&mut WallSegment, &mut Sprite, &mut Visibility)>,
+) {
+    for hit in wall_hits.read() {
+        let (side, holes) = match hit.side {
+            Side::Left => (-1.0, &mut holes.left),
+            Side::Right => (1.0, &mut holes.right),
+            Side::Top | Side::Bottom => continue,
+        };
+        let Some(index) = ball_query
+            .get(hit.ball)
+            .ok()
+            .and_then(|transform| segment_at(transform.translation.y))
+        else {
+            continue;
+        };
+        let Some((_, children)) = wall_query.iter().find(|(wall, _)| wall.side == side) else {
+            continue;
+        };
+        let mut segments = segment_query.iter_many_mut(children);
+        while let Some((mut segment, mut sprite, mut visibility)) = segments.fetch_next() {
+            if segment.index != index || segment.health == 0 {
+                continue;
+            }
+            segment.health -= 1;
+            sprite.color = segment_color(segment.health);
+            if segment.health == 0 {
+                *visibility = Visibility::Hidden;
+                holes.push(index);
+                screenshot::show_toast(&mut commands, &toast_query, "The wall gave way!".to_string());
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::math::bounding::{Aabb2d, IntersectsVolume};
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::accessibility;
+use crate::display::ARENA;
+use crate::hazard::HAZARD_COLOR;
+use crate::pool::{self, EntityPool};
+use crate::settings::Settings;
+use crate::split_paddle::{self, SplitPaddle};
+use crate::{pause, BlockDestroyed, Collider, GameEntity, GameState, Paddle};
+
+const DEBRIS_SIZE: Vec2 = Vec2::new(9.0, 6.0);
+const DEBRIS_GRAVITY: f32 = 900.0;
+/// Share of its falling speed a chunk keeps when it bounces.
+const DEBRIS_RESTITUTION: f32 = 0.45;
+/// Seconds a chunk takes to fade out once it has bounced.
+const DEBRIS_FADE: f32 = 0.6;
+/// Seconds a chunk lasts at most, for ones that never land on anything.
+const DEBRIS_LIFETIME: f32 = 3.0;
+
+/// A cosmetic chunk of a broken block. It falls, bounces once off the paddle or the
+/// bottom of the arena and then fades away; nothing else collides with it.
+#[derive(Component)]
+pub struct Debris {
+    velocity: Vec2,
+    /// Radians per second.
+    spin: f32,
+    color: Color,
+    age: f32,
+    /// Seconds left to fade once it has bounced.
+    fading: Option<f32>,
+}
+
+pub struct DebrisPlugin;
+
+impl Plugin for DebrisPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EntityPool<Debris>>()
+            .add_systems(OnEnter(GameState::Playing), pool::clear_pool::<Debris>)
+            .add_systems(
+                Update,
+                (
+                    spawn_debris.run_if(accessibility::decorative_effects),
+                    move_debris,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing).and(pause::not_paused)),
+            );
+    }
+}
+
+fn spawn_debris(
+    settings: Res<Settings>,
+    mut commands: Commands,
+    mut pool: ResMut<EntityPool<Debris>>,
+    mut destroyed: MessageReader<BlockDestroyed>,
+) {
+    let mut rng = rand::rng();
+    for block in destroyed.read() {
+        let color = if block.hazard {
+            HAZARD_COLOR
+        } else {
+            settings.palette.block_color(1)
+        };
+        for _ in 0..rng.random_range(2..=4) {
+            let offset = Vec2::new(rng.random_range(-20.0..20.0), rng.random_range(-5.0..5.0));
+            // Thrown outward from the middle of the block, and a little upward.
+            let velocity = Vec2::new(offset.x * 6.0, rng.random_range(40.0..160.0));
+            pool.spawn(
+                &mut commands,
+                (
+                    Sprite {
+                        color,
+                        custom_size: Some(DEBRIS_SIZE),
+                        ..default()
+                    },
+                    Transform::from_translation((block.position + offset).extend(0.5))
+                        .with_rotation(Quat::from_rotation_z(rng.random_range(0.0..std::f32::consts::TAU))),
+                    Debris {
+                        velocity,
+                        spin: rng.random_range(-8.0..8.0),
+                        color,
+                        age: 0.0,
+                        fading: None,
+                    },
+                    GameEntity,
+                ),
+            );
+        }
+    }
+}
+
+fn move_debris(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut pool: ResMut<EntityPool<Debris>>,
+    mut debris_query: Query<(Entity, &mut Transform, &mut Sprite, &mut Debris), Without<Paddle>>,
+    paddle_query: Query<(&Transform, &Collider, Has<SplitPaddle>), With<Paddle>>,
+) {
+    let dt = time.delta_secs();
+    let floor = -ARENA.half_height() + DEBRIS_SIZE.y / 2.0;
+    for (entity, mut transform, mut sprite, mut debris) in &mut debris_query {
+        debris.age += dt;
+        debris.velocity.y -= DEBRIS_GRAVITY * dt;
+        transform.translation += (debris.velocity * dt).extend(0.0);
+        transform.rotate_z(debris.spin * dt);
+
+        let position = transform.translation.truncate();
+        if let Some(fading) = debris.fading {
+            let fading = fading - dt;
+            debris.fading = Some(fading);
+            sprite.color = debris.color.with_alpha((fading / DEBRIS_FADE).max(0.0));
+            if fading <= 0.0 || position.y < -ARENA.half_height() - DEBRIS_SIZE.y {
+                pool.release(&mut commands, entity);
+            }
+            continue;
+        }
+        if debris.age > DEBRIS_LIFETIME || position.x.abs() > ARENA.half_width() {
+            pool.release(&mut commands, entity);
+            continue;
+        }
+
+        let chunk = Aabb2d::new(position, DEBRIS_SIZE / 2.0);
+        let landed_on = paddle_query.iter().find_map(|(paddle_transform, collider, split)| {
+            split_paddle::parts(paddle_transform.translation.truncate(), collider, split)
+                .into_iter()
+                .find(|part| part.intersects(&chunk))
+        });
+        let surface = match landed_on {
+            Some(part) => Some(part.max.y + DEBRIS_SIZE.y / 2.0),
+            None if position.y <= floor => Some(floor),
+            None => None,
+        };
+        if let Some(surface) = surface.filter(|_| debris.velocity.y < 0.0) {
+            transform.translation.y = surface;
+            debris.velocity.y = -debris.velocity.y * DEBRIS_RESTITUTION;
+            debris.velocity.x *= 0.7;
+            debris.spin *= 0.5;
+            debris.fading = Some(DEBRIS_FADE);
+        }
+    }
+}
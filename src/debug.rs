@@ -0,0 +1,191 @@
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+
+use crate::collision::{BallHitBlock, Side};
+use crate::level::{CurrentLevel, Levels};
+use crate::pause::{self, Paused};
+use crate::screenshot::{self, Toast};
+use crate::{curve_with_spin, Ball, Collider, Spin, Velocity, BALL_COLLISION_MARGIN};
+
+const COLLIDER_COLOR: Color = Color::srgb(0.2, 1.0, 0.2);
+const MARGIN_COLOR: Color = Color::srgb(1.0, 0.6, 0.1);
+const VELOCITY_COLOR: Color = Color::srgb(1.0, 0.2, 0.8);
+const HIT_FACE_COLOR: Color = Color::srgb(1.0, 1.0, 0.2);
+const CURVE_COLOR: Color = Color::srgb(0.3, 0.8, 1.0);
+/// Seconds ahead the spin's curve is traced, and how finely.
+const CURVE_SECONDS: f32 = 1.0;
+const CURVE_STEPS: usize = 30;
+/// Seconds of travel the velocity arrow covers, so its length reads as "where the
+/// ball will be shortly".
+const VELOCITY_ARROW_SECONDS: f32 = 0.25;
+/// Simulation speeds selected with Ctrl+1 through Ctrl+4.
+const TIME_SCALE_PRESETS: [f32; 4] = [0.25, 0.5, 1.0, 2.0];
+
+/// Whether collision boxes and the ball's velocity are drawn over the game. Toggled with F4.
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub enabled: bool,
+}
+
+/// Debug pause (F7) that holds the simulation still and lets F8 advance it one tick at a time.
+///
+/// Virtual time is paused alongside, so anything driven by the frame delta freezes too,
+/// while rendering and the debug overlay carry on as normal. A step moves virtual time on
+/// by exactly one fixed timestep, so the fixed-update loop runs once.
+#[derive(Resource, Default)]
+pub struct FrameStep {
+    pub paused: bool,
+    /// Set for the frame after F8 was pressed; gameplay runs during it.
+    stepping: bool,
+    step_requested: bool,
+}
+
+/// Simulation speed picked with the debug hotkeys, applied on top of the game speed setting.
+#[derive(Resource)]
+pub struct DebugTimeScale(pub f32);
+
+impl Default for DebugTimeScale {
+    fn default() -> Self {
+        DebugTimeScale(1.0)
+    }
+}
+
+/// Run condition for gameplay systems that should stop while frame-stepping.
+pub fn simulation_running(frame_step: Res<FrameStep>) -> bool {
+    !frame_step.paused || frame_step.stepping
+}
+
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlay>()
+            .init_resource::<FrameStep>()
+            .init_resource::<DebugTimeScale>()
+            .add_systems(PreUpdate, begin_frame_step)
+            .add_systems(
+                Update,
+                (
+                    (toggle_debug_overlay, frame_step_input, set_time_scale),
+                    draw_debug_overlay.run_if(|overlay: Res<DebugOverlay>| overlay.enabled),
+                )
+                    .chain(),
+            )
+            .add_systems(Last, end_frame_step);
+    }
+}
+
+fn toggle_debug_overlay(input: Res<ButtonInput<Key>>, mut overlay: ResMut<DebugOverlay>) {
+    if input.just_pressed(Key::F4) {
+        overlay.enabled = !overlay.enabled;
+    }
+}
+
+fn frame_step_input(
+    input: Res<ButtonInput<Key>>,
+    paused: Res<Paused>,
+    mut frame_step: ResMut<FrameStep>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    if input.just_pressed(Key::F7) {
+        frame_step.paused = !frame_step.paused;
+        pause::sync_virtual_time(&paused, &frame_step, &mut virtual_time);
+    } else if frame_step.paused && input.just_pressed(Key::F8) {
+        frame_step.step_requested = true;
+    }
+}
+
+fn set_time_scale(
+    input: Res<ButtonInput<Key>>,
+    mut time_scale: ResMut<DebugTimeScale>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !input.pressed(Key::Control) {
+        return;
+    }
+    for (i, scale) in TIME_SCALE_PRESETS.into_iter().enumerate() {
+        if input.just_pressed(Key::Character((i + 1).to_string().into())) {
+            time_scale.0 = scale;
+            screenshot::show_toast(&mut commands, &toast_query, format!("Time scale {scale}x"));
+        }
+    }
+}
+
+// Virtual time stays paused and is moved on by hand, after `First` has already worked
+// out this frame's (zero) delta, so the fixed loop sees one timestep whatever the frame
+// took.
+fn begin_frame_step(
+    mut frame_step: ResMut<FrameStep>,
+    fixed_time: Res<Time<Fixed>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut time: ResMut<Time>,
+) {
+    frame_step.stepping = std::mem::take(&mut frame_step.step_requested);
+    if frame_step.stepping {
+        virtual_time.advance_by(fixed_time.timestep());
+        *time = virtual_time.as_generic();
+    }
+}
+
+fn end_frame_step(mut frame_step: ResMut<FrameStep>) {
+    frame_step.stepping = false;
+}
+
+fn draw_debug_overlay(
+    mut gizmos: Gizmos,
+    collider_query: Query<(&Transform, &Collider)>,
+    ball_query: Query<(&Transform, &Velocity, &Spin, &Collider), With<Ball>>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+    mut block_hits: MessageReader<BallHitBlock>,
+) {
+    for (transform, collider) in &collider_query {
+        gizmos.rect_2d(transform.translation.truncate(), collider.size, COLLIDER_COLOR);
+    }
+
+    // Paddle and wall checks use the ball's box grown by the margin on every side.
+    let curveball = levels.get(current_level.0).curveball;
+    for (transform, velocity, spin, collider) in &ball_query {
+        let position = transform.translation.truncate();
+        gizmos.rect_2d(position, collider.size + BALL_COLLISION_MARGIN * 2.0, MARGIN_COLOR);
+        if velocity.0 != Vec2::ZERO {
+            gizmos.arrow_2d(position, position + velocity.0 * VELOCITY_ARROW_SECONDS, VELOCITY_COLOR);
+        }
+        if velocity.0 != Vec2::ZERO && spin.0 != 0.0 {
+            gizmos.linestrip_2d(curve_path(position, velocity, spin, curveball), CURVE_COLOR);
+        }
+    }
+
+    // The face of each block the ball struck this frame, easiest to follow while frame-stepping.
+    for hit in block_hits.read() {
+        let Ok((transform, collider)) = collider_query.get(hit.entity) else {
+            continue;
+        };
+        let center = transform.translation.truncate();
+        let half = collider.size / 2.0;
+        let (start, end) = match hit.side {
+            Side::Top => (Vec2::new(-half.x, half.y), half),
+            Side::Bottom => (-half, Vec2::new(half.x, -half.y)),
+            Side::Left => (-half, Vec2::new(-half.x, half.y)),
+            Side::Right => (Vec2::new(half.x, -half.y), half),
+        };
+        gizmos.line_2d(center + start, center + end, HIT_FACE_COLOR);
+    }
+}
+
+/// Where spin alone would carry the ball over the next `CURVE_SECONDS`, ignoring
+/// anything it might hit.
+fn curve_path(position: Vec2, velocity: &Velocity, spin: &Spin, curveball: bool) -> Vec<Vec2> {
+    let dt = CURVE_SECONDS / CURVE_STEPS as f32;
+    let mut velocity = Velocity(velocity.0);
+    let mut spin = Spin(spin.0);
+    let mut point = position;
+    let mut points = vec![point];
+    for _ in 0..CURVE_STEPS {
+        curve_with_spin(&mut velocity, &mut spin, dt, curveball);
+        point += velocity.0 * dt;
+        points.push(point);
+    }
+    points
+}
@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::EguiPlugin;
+use bevy_inspector_egui::quick::{ResourceInspectorPlugin, StateInspectorPlugin, WorldInspectorPlugin};
+
+use crate::config::GameConfig;
+use crate::scoring::GameScore;
+use crate::GameState;
+
+/// Live inspector windows for tuning gameplay, only built with the `dev-tools` feature.
+pub struct DevToolsPlugin;
+
+impl Plugin for DevToolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(EguiPlugin::default())
+            .add_plugins(WorldInspectorPlugin::new())
+            .add_plugins(ResourceInspectorPlugin::<GameConfig>::default())
+            .add_plugins(ResourceInspectorPlugin::<GameScore>::default())
+            .add_plugins(StateInspectorPlugin::<GameState>::default());
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy::prelude::*;
+
+use crate::force_zone::Streak;
+use crate::{Ball, Block, GameState};
+
+pub const ACTIVE_BLOCKS: DiagnosticPath = DiagnosticPath::const_new("game/active_blocks");
+pub const ACTIVE_BALLS: DiagnosticPath = DiagnosticPath::const_new("game/active_balls");
+/// Effect entities currently on screen, not counting hidden ones waiting in a pool.
+pub const PARTICLES_ALIVE: DiagnosticPath = DiagnosticPath::const_new("game/particles_alive");
+
+/// Entity counts recorded every frame of play, so they show up next to the frame time in
+/// profiles and in the diagnostics store.
+pub struct GameDiagnosticsPlugin;
+
+impl Plugin for GameDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(ACTIVE_BLOCKS))
+            .register_diagnostic(Diagnostic::new(ACTIVE_BALLS))
+            .register_diagnostic(Diagnostic::new(PARTICLES_ALIVE))
+            .add_systems(Update, measure_entities.run_if(in_state(GameState::Playing)));
+    }
+}
+
+fn measure_entities(
+    mut diagnostics: Diagnostics,
+    block_query: Query<(), With<Block>>,
+    ball_query: Query<(), With<Ball>>,
+    streak_query: Query<(), With<Streak>>,
+) {
+    diagnostics.add_measurement(&ACTIVE_BLOCKS, || block_query.iter().len() as f64);
+    diagnostics.add_measurement(&ACTIVE_BALLS, || ball_query.iter().len() as f64);
+    diagnostics.add_measurement(&PARTICLES_ALIVE, || streak_query.iter().len() as f64);
+}
@@ -0,0 +1,298 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bevy::camera::visibility::RenderLayers;
+use bevy::camera::{ScalingMode, Viewport};
+use bevy::ecs::system::NonSendMarker;
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+use bevy::window::{
+    MonitorSelection, PresentMode, PrimaryWindow, WindowMode, WindowResizeConstraints, WindowResolution,
+};
+use bevy::winit::WINIT_WINDOWS;
+use winit::window::Icon;
+
+use crate::mods::ModRegistry;
+use crate::settings::Settings;
+use crate::{WINDOW_HEIGHT, WINDOW_WIDTH};
+
+/// Frame caps cycled through with F10; `None` means uncapped.
+const FPS_CAP_PRESETS: [Option<u32>; 5] = [None, Some(30), Some(60), Some(120), Some(144)];
+
+const UI_SCALE_STEP: f32 = 0.1;
+const UI_SCALE_MIN: f32 = 0.5;
+const UI_SCALE_MAX: f32 = 2.5;
+
+const WINDOW_TITLE: &str = "Rusty Pong";
+const WINDOW_ICON: &str = "ferris.png";
+/// Smallest the window can be resized to, half the arena in each direction.
+const WINDOW_MIN_SIZE: Vec2 = Vec2::new(WINDOW_WIDTH / 2.0, WINDOW_HEIGHT / 2.0);
+
+/// Render layer nothing else lives on, so the letterbox camera only clears the bars.
+const LETTERBOX_LAYER: usize = 31;
+
+/// Size of the playable area in world units, centered on the origin.
+///
+/// The game camera always shows exactly this much of the world, independent of the
+/// window size or the OS scale factor.
+pub struct Arena {
+    pub width: f32,
+    pub height: f32,
+}
+
+pub const ARENA: Arena = Arena {
+    width: WINDOW_WIDTH,
+    height: WINDOW_HEIGHT,
+};
+
+impl Arena {
+    pub const fn half_width(&self) -> f32 {
+        self.width / 2.0
+    }
+
+    pub const fn half_height(&self) -> f32 {
+        self.height / 2.0
+    }
+}
+
+/// Marks the camera that renders the arena. Its viewport is kept at the arena's aspect
+/// ratio, with black bars filling the rest of the window.
+#[derive(Component)]
+pub struct GameCamera;
+
+/// Image still loading to be set as the window icon once it's ready.
+#[derive(Resource)]
+struct PendingWindowIcon(Handle<Image>);
+
+/// Tracks when the previous frame ended so the limiter knows how long to sleep.
+#[derive(Resource)]
+struct FrameLimiter {
+    last_frame: Instant,
+}
+
+pub struct DisplayPlugin;
+
+impl Plugin for DisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FrameLimiter {
+            last_frame: Instant::now(),
+        })
+        .add_systems(Startup, (spawn_letterbox_camera, apply_display_settings, load_window_icon))
+        .add_systems(
+            Update,
+            (
+                (toggle_fullscreen, toggle_vsync, cycle_fps_cap, adjust_ui_scale),
+                apply_display_settings.run_if(resource_changed::<Settings>),
+                fit_camera_viewport,
+            )
+                .chain(),
+        )
+        .add_systems(Update, set_window_icon.run_if(resource_exists::<PendingWindowIcon>))
+        .add_systems(Last, limit_frame_rate);
+    }
+}
+
+/// The game's window as it first opens, before the rest of the display settings apply.
+pub fn primary_window(settings: &Settings) -> Window {
+    Window {
+        title: WINDOW_TITLE.to_string(),
+        resolution: WindowResolution::new(WINDOW_WIDTH as u32, WINDOW_HEIGHT as u32),
+        resize_constraints: WindowResizeConstraints {
+            min_width: WINDOW_MIN_SIZE.x,
+            min_height: WINDOW_MIN_SIZE.y,
+            ..default()
+        },
+        resizable: settings.resizable_window,
+        mode: window_mode(settings.fullscreen),
+        present_mode: present_mode(settings.vsync),
+        ..default()
+    }
+}
+
+pub fn spawn_game_camera(commands: &mut Commands) {
+    commands.spawn((
+        Camera2d,
+        IsDefaultUiCamera,
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: WINDOW_WIDTH,
+                height: WINDOW_HEIGHT,
+            },
+            ..OrthographicProjection::default_2d()
+        }),
+        GameCamera,
+    ));
+}
+
+// Renders before the game camera and covers the whole window, so whatever the game
+// viewport doesn't cover is cleared to black.
+fn spawn_letterbox_camera(mut commands: Commands) {
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: -1,
+            clear_color: ClearColorConfig::Custom(Color::BLACK),
+            ..default()
+        },
+        RenderLayers::layer(LETTERBOX_LAYER),
+    ));
+}
+
+fn window_mode(fullscreen: bool) -> WindowMode {
+    if fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    }
+}
+
+fn present_mode(vsync: bool) -> PresentMode {
+    if vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    }
+}
+
+fn apply_display_settings(
+    settings: Res<Settings>,
+    mut ui_scale: ResMut<UiScale>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    ui_scale.0 = settings.ui_scale;
+
+    if let Ok(mut window) = window_query.single_mut() {
+        let mode = window_mode(settings.fullscreen);
+        if window.mode != mode {
+            window.mode = mode;
+        }
+        let present_mode = present_mode(settings.vsync);
+        if window.present_mode != present_mode {
+            window.present_mode = present_mode;
+        }
+        if window.resizable != settings.resizable_window {
+            window.resizable = settings.resizable_window;
+        }
+    }
+}
+
+fn load_window_icon(mut commands: Commands, asset_server: Res<AssetServer>, registry: Res<ModRegistry>) {
+    commands.insert_resource(PendingWindowIcon(asset_server.load(registry.asset_path(WINDOW_ICON))));
+}
+
+// The icon is handed straight to winit, since Bevy's `Window` has no field for it.
+fn set_window_icon(
+    mut commands: Commands,
+    pending: Res<PendingWindowIcon>,
+    images: Res<Assets<Image>>,
+    _main_thread: NonSendMarker,
+) {
+    let Some(image) = images.get(&pending.0) else {
+        return;
+    };
+    commands.remove_resource::<PendingWindowIcon>();
+    let Some(rgba) = image.clone().try_into_dynamic().ok().map(|image| image.into_rgba8()) else {
+        error!("failed to read {WINDOW_ICON} as a window icon");
+        return;
+    };
+    let (width, height) = rgba.dimensions();
+    match Icon::from_rgba(rgba.into_raw(), width, height) {
+        Ok(icon) => WINIT_WINDOWS.with_borrow(|windows| {
+            for window in windows.windows.values() {
+                window.set_window_icon(Some(icon.clone()));
+            }
+        }),
+        Err(err) => error!("failed to set the window icon: {err}"),
+    }
+}
+
+fn toggle_fullscreen(input: Res<ButtonInput<Key>>, mut settings: ResMut<Settings>) {
+    let alt_enter = input.pressed(Key::Alt) && input.just_pressed(Key::Enter);
+    if input.just_pressed(Key::F11) || alt_enter {
+        settings.fullscreen = !settings.fullscreen;
+        settings.save();
+    }
+}
+
+fn toggle_vsync(input: Res<ButtonInput<Key>>, mut settings: ResMut<Settings>) {
+    if input.just_pressed(Key::F9) {
+        settings.vsync = !settings.vsync;
+        settings.save();
+    }
+}
+
+fn adjust_ui_scale(input: Res<ButtonInput<Key>>, mut settings: ResMut<Settings>) {
+    if !input.pressed(Key::Control) {
+        return;
+    }
+    let mut step = 0.0;
+    if input.just_pressed(Key::Character("=".into())) || input.just_pressed(Key::Character("+".into())) {
+        step += UI_SCALE_STEP;
+    }
+    if input.just_pressed(Key::Character("-".into())) {
+        step -= UI_SCALE_STEP;
+    }
+    if step != 0.0 {
+        settings.ui_scale = (settings.ui_scale + step).clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+        settings.save();
+    }
+}
+
+fn cycle_fps_cap(input: Res<ButtonInput<Key>>, mut settings: ResMut<Settings>) {
+    if input.just_pressed(Key::F10) {
+        let current = FPS_CAP_PRESETS
+            .iter()
+            .position(|cap| *cap == settings.fps_cap)
+            .unwrap_or(0);
+        settings.fps_cap = FPS_CAP_PRESETS[(current + 1) % FPS_CAP_PRESETS.len()];
+        settings.save();
+    }
+}
+
+// Sleeps off whatever is left of the frame budget. This runs after the rest of the
+// frame's work, so the cap holds regardless of vsync.
+fn limit_frame_rate(settings: Res<Settings>, mut limiter: ResMut<FrameLimiter>) {
+    if let Some(cap) = settings.fps_cap.filter(|cap| *cap > 0) {
+        let target = Duration::from_secs_f64(1.0 / cap as f64);
+        let elapsed = limiter.last_frame.elapsed();
+        if elapsed < target {
+            thread::sleep(target - elapsed);
+        }
+    }
+    limiter.last_frame = Instant::now();
+}
+
+// Runs every frame rather than on resize events so freshly spawned cameras and
+// delayed fullscreen switches are picked up without extra bookkeeping.
+fn fit_camera_viewport(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut Camera, With<GameCamera>>,
+) {
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let window_size = window.physical_size();
+    if window_size.x == 0 || window_size.y == 0 {
+        return;
+    }
+
+    let scale = (window_size.x as f32 / WINDOW_WIDTH).min(window_size.y as f32 / WINDOW_HEIGHT);
+    let size = (Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT) * scale)
+        .as_uvec2()
+        .clamp(UVec2::ONE, window_size);
+    let position = (window_size - size) / 2;
+
+    for mut camera in &mut camera_query {
+        let fitted = camera
+            .viewport
+            .as_ref()
+            .is_some_and(|viewport| viewport.physical_position == position && viewport.physical_size == size);
+        if !fitted {
+            camera.viewport = Some(Viewport {
+                physical_position: position,
+                physical_size: size,
+                ..default()
+            });
+        }
+    }
+}
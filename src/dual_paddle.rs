@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::display::ARENA;
+use crate::mutators::Mutator;
+use crate::settings::Settings;
+use crate::{paddle_row_y, Collider, GameEntity, GameState, Paddle, PaddleVelocity};
+
+/// A second paddle across the top of the arena for the dual-paddle mutator. It has no
+/// input of its own and follows the player's paddle, mirrored across the middle, so the
+/// ball can be kept alive at either end. The ceiling stops bouncing the ball, so missing
+/// it up there loses the ball.
+#[derive(Component)]
+pub struct TopPaddle;
+
+pub struct DualPaddlePlugin;
+
+impl Plugin for DualPaddlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::Playing),
+            spawn_top_paddle.after(crate::setup_game).run_if(dual_paddle),
+        )
+        .add_systems(Update, follow_paddle.run_if(in_state(GameState::Playing)));
+    }
+}
+
+fn dual_paddle(settings: Res<Settings>) -> bool {
+    settings.mutator(Mutator::DualPaddle)
+}
+
+fn spawn_top_paddle(mut commands: Commands, paddle_query: Query<&Collider, With<Paddle>>) {
+    let Ok(collider) = paddle_query.single() else {
+        return;
+    };
+    commands.spawn((
+        Sprite {
+            color: Color::WHITE,
+            custom_size: Some(collider.size),
+            ..default()
+        },
+        Transform::from_xyz(0.0, -paddle_row_y(), 0.0),
+        TopPaddle,
+        PaddleVelocity::default(),
+        Collider { size: collider.size },
+        GameEntity,
+    ));
+}
+
+fn follow_paddle(
+    paddle_query: Query<(&Transform, &Collider, &PaddleVelocity), (With<Paddle>, Without<TopPaddle>)>,
+    mut top_query: Query<(&mut Transform, &mut Sprite, &mut Collider, &mut PaddleVelocity), With<TopPaddle>>,
+) {
+    let Ok((paddle_transform, paddle_collider, paddle_velocity)) = paddle_query.single() else {
+        return;
+    };
+    for (mut transform, mut sprite, mut collider, mut velocity) in &mut top_query {
+        transform.translation.x = paddle_transform.translation.x;
+        transform.translation.y = -paddle_row_y();
+        velocity.0 = paddle_velocity.0;
+        if collider.size != paddle_collider.size {
+            collider.size = paddle_collider.size;
+            sprite.custom_size = Some(collider.size);
+        }
+    }
+}
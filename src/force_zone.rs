@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::accessibility;
+use crate::bullet_time::BulletTime;
+use crate::level::{CurrentLevel, Levels};
+use crate::pool::{self, EntityPool};
+use crate::{debug, pause, Ball, BallCollision, GameEntity, GameState, Serving, Velocity};
+
+const ZONE_COLOR: Color = Color::srgba(0.6, 0.85, 1.0, 0.06);
+const STREAK_COLOR: Color = Color::srgba(0.75, 0.9, 1.0, 0.35);
+const STREAK_SIZE: Vec2 = Vec2::new(2.0, 16.0);
+/// Streaks spawned per second for every 10,000 square units of zone.
+const STREAK_DENSITY: f32 = 1.5;
+
+/// A rectangle from a level file that pushes the ball while it's inside.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ForceZoneData {
+    pub position: (f32, f32),
+    pub size: (f32, f32),
+    /// Acceleration applied to the ball, in units per second squared.
+    pub force: (f32, f32),
+}
+
+#[derive(Component)]
+struct ForceZone {
+    area: Rect,
+    force: Vec2,
+    /// Fractional streaks carried over between frames.
+    pending_streaks: f32,
+}
+
+#[derive(Component)]
+pub struct Streak {
+    zone: Entity,
+    velocity: Vec2,
+}
+
+pub struct ForceZonePlugin;
+
+impl Plugin for ForceZonePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EntityPool<Streak>>()
+            .add_systems(
+                OnEnter(GameState::Playing),
+                (pool::clear_pool::<Streak>, spawn_force_zones),
+            )
+            .add_systems(
+                Update,
+                (spawn_streaks.run_if(accessibility::decorative_effects), move_streaks)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                FixedUpdate,
+                apply_force_zones
+                    .before(BallCollision)
+                    .run_if(in_state(GameState::Playing).and(debug::simulation_running).and(pause::not_paused)),
+            );
+    }
+}
+
+fn spawn_force_zones(mut commands: Commands, levels: Res<Levels>, current_level: Res<CurrentLevel>) {
+    for data in &levels.get(current_level.0).force_zones {
+        let center = Vec2::new(data.position.0, data.position.1);
+        let size = Vec2::new(data.size.0, data.size.1);
+        commands.spawn((
+            Sprite {
+                color: ZONE_COLOR,
+                custom_size: Some(size),
+                ..default()
+            },
+            Transform::from_translation(center.extend(-0.5)),
+            ForceZone {
+                area: Rect::from_center_size(center, size),
+                force: Vec2::new(data.force.0, data.force.1),
+                pending_streaks: 0.0,
+            },
+            GameEntity,
+        ));
+    }
+}
+
+fn apply_force_zones(
+    time: Res<Time>,
+    bullet_time: Res<BulletTime>,
+    zone_query: Query<&ForceZone>,
+    mut ball_query: Query<(&Transform, &mut Velocity), (With<Ball>, Without<Serving>)>,
+) {
+    let dt = time.delta_secs() * bullet_time.ball_time_scale();
+    for (transform, mut velocity) in &mut ball_query {
+        let position = transform.translation.truncate();
+        for zone in &zone_query {
+            if zone.area.contains(position) {
+                velocity.0 += zone.force * dt;
+            }
+        }
+    }
+}
+
+fn spawn_streaks(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut pool: ResMut<EntityPool<Streak>>,
+    mut zone_query: Query<(Entity, &mut ForceZone)>,
+) {
+    let _span = info_span!("spawn_streaks").entered();
+    let mut rng = rand::rng();
+    for (entity, mut zone) in &mut zone_query {
+        let Some(direction) = zone.force.try_normalize() else {
+            continue;
+        };
+        let area = zone.area.width() * zone.area.height();
+        zone.pending_streaks += STREAK_DENSITY * area / 10_000.0 * time.delta_secs();
+
+        // Streaks drift with the wind, a bit faster the stronger it blows.
+        let speed = 60.0 + zone.force.length() * 0.5;
+        while zone.pending_streaks >= 1.0 {
+            zone.pending_streaks -= 1.0;
+            let position = Vec2::new(
+                rng.random_range(zone.area.min.x..zone.area.max.x),
+                rng.random_range(zone.area.min.y..zone.area.max.y),
+            );
+            pool.spawn(
+                &mut commands,
+                (
+                    Sprite {
+                        color: STREAK_COLOR,
+                        custom_size: Some(STREAK_SIZE),
+                        ..default()
+                    },
+                    Transform::from_translation(position.extend(-0.4))
+                        .with_rotation(Quat::from_rotation_arc_2d(Vec2::Y, direction)),
+                    Streak {
+                        zone: entity,
+                        velocity: direction * speed,
+                    },
+                    GameEntity,
+                ),
+            );
+        }
+    }
+}
+
+fn move_streaks(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut pool: ResMut<EntityPool<Streak>>,
+    zone_query: Query<&ForceZone>,
+    mut streak_query: Query<(Entity, &mut Transform, &Streak)>,
+) {
+    let _span = info_span!("move_streaks", count = streak_query.iter().len()).entered();
+    for (entity, mut transform, streak) in &mut streak_query {
+        transform.translation += (streak.velocity * time.delta_secs()).extend(0.0);
+        let inside = zone_query
+            .get(streak.zone)
+            .is_ok_and(|zone| zone.area.contains(transform.translation.truncate()));
+        if !inside {
+            pool.release(&mut commands, entity);
+        }
+    }
+}
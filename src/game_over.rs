@@ -0,0 +1,308 @@
+use bevy::input::keyboard::Key;
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+
+use crate::display::{self, GameCamera};
+use crate::highscores::{self, HighScores, PendingHighScore};
+use crate::level::CurrentLevel;
+use crate::menu::{MenuActivated, MenuItem};
+use crate::scoring::{Combo, GameScore};
+use crate::stats::{self, RunStats};
+use crate::{touch, BallLost, BumpEnergy, GameEntity, GameState, WINDOW_HEIGHT, WINDOW_WIDTH};
+
+/// Balls that can be lost in a run before it's over.
+const STARTING_LIVES: u32 = 3;
+const MAX_NAME_LENGTH: usize = 12;
+
+const NAME_ENTRY_Y: f32 = -40.0;
+const NAME_ENTRY_SIZE: Vec2 = Vec2::new(520.0, 40.0);
+const OPTION_TOP_Y: f32 = -120.0;
+const OPTION_SPACING: f32 = 55.0;
+const OPTION_SIZE: Vec2 = Vec2::new(300.0, 44.0);
+
+/// Balls left before the run ends. Each one that escapes the arena costs a life.
+#[derive(Resource)]
+pub struct Lives(pub u32);
+
+impl Default for Lives {
+    fn default() -> Self {
+        Lives(STARTING_LIVES)
+    }
+}
+
+#[derive(Component)]
+struct LivesText;
+
+#[derive(Component, Clone)]
+pub struct GameOverScreen;
+
+#[derive(Component)]
+struct NameEntryText;
+
+#[derive(Component, Clone, Copy, PartialEq)]
+enum GameOverOption {
+    Retry,
+    Menu,
+}
+
+impl GameOverOption {
+    const ALL: [GameOverOption; 2] = [GameOverOption::Retry, GameOverOption::Menu];
+
+    fn label(self) -> &'static str {
+        match self {
+            GameOverOption::Retry => "Retry",
+            GameOverOption::Menu => "Menu",
+        }
+    }
+
+    fn y(self) -> f32 {
+        OPTION_TOP_Y - self as usize as f32 * OPTION_SPACING
+    }
+}
+
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Lives>()
+            .add_systems(OnEnter(GameState::Playing), (reset_on_new_run, spawn_lives_text).chain())
+            .add_systems(
+                Update,
+                (lose_lives, update_lives_text.run_if(resource_changed::<Lives>))
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                (crate::clear_game_camera, highscores::stage_high_score, setup_game_over).chain(),
+            )
+            .add_systems(
+                Update,
+                (enter_name, choose_option).run_if(in_state(GameState::GameOver)),
+            );
+    }
+}
+
+fn lives_label(lives: &Lives) -> String {
+    format!("Lives: {}", lives.0)
+}
+
+// Same rule as the speedrun timer: only starting on the first level is a new run.
+fn reset_on_new_run(current_level: Res<CurrentLevel>, mut lives: ResMut<Lives>) {
+    if current_level.0 == 0 {
+        *lives = Lives::default();
+    }
+}
+
+fn spawn_lives_text(mut commands: Commands, lives: Res<Lives>) {
+    commands.spawn((
+        Text::new(lives_label(&lives)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(40.0),
+            top: Val::Px(175.0),
+            ..default()
+        },
+        LivesText,
+        GameEntity,
+    ));
+}
+
+fn lose_lives(
+    mut lost_events: MessageReader<BallLost>,
+    mut lives: ResMut<Lives>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let lost = lost_events.read().count() as u32;
+    if lost == 0 {
+        return;
+    }
+    lives.0 = lives.0.saturating_sub(lost);
+    if lives.0 == 0 {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+fn update_lives_text(lives: Res<Lives>, mut text_query: Query<&mut Text, With<LivesText>>) {
+    for mut text in &mut text_query {
+        **text = lives_label(&lives);
+    }
+}
+
+fn name_entry_label(name: &str) -> String {
+    format!("New high score! Name: {name}_")
+}
+
+fn spawn_options(commands: &mut Commands) {
+    for (order, option) in GameOverOption::ALL.into_iter().enumerate() {
+        commands.spawn((
+            Text2d(option.label().to_string()),
+            Transform::from_xyz(0.0, option.y(), 2.0),
+            MenuItem {
+                order: order as u32,
+                size: OPTION_SIZE,
+            },
+            option,
+            GameOverScreen,
+        ));
+    }
+}
+
+// The cleared arena stays behind the screen, like it does when the game is won.
+fn setup_game_over(
+    mut commands: Commands,
+    score: Res<GameScore>,
+    stats: Res<RunStats>,
+    pending: Res<PendingHighScore>,
+) {
+    display::spawn_game_camera(&mut commands);
+
+    commands.spawn((
+        Sprite {
+            color: Color::srgba(0.0, 0.0, 0.0, 0.8),
+            custom_size: Some(Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 0.0),
+        GameOverScreen,
+    ));
+    commands.spawn((
+        Text2d("Game over".to_string()),
+        Transform::from_xyz(0.0, 300.0, 2.0),
+        GameOverScreen,
+    ));
+    commands.spawn((
+        Text2d(format!("Final score: {}", score.0)),
+        Transform::from_xyz(0.0, 255.0, 2.0),
+        GameOverScreen,
+    ));
+    stats::spawn_summary(&mut commands, &stats, GameOverScreen, 2.0);
+
+    // The options wait until a high score has its name, so Enter can't do both at once.
+    if pending.0.is_some() {
+        commands.spawn((
+            Text2d(name_entry_label("")),
+            Transform::from_xyz(0.0, NAME_ENTRY_Y, 2.0),
+            NameEntryText,
+            GameOverScreen,
+        ));
+    } else {
+        spawn_options(&mut commands);
+    }
+}
+
+fn enter_name(
+    input: Res<ButtonInput<Key>>,
+    touches: Res<Touches>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    mut pending: ResMut<PendingHighScore>,
+    mut high_scores: ResMut<HighScores>,
+    mut commands: Commands,
+    mut text_query: Query<&mut Text2d, With<NameEntryText>>,
+) {
+    let Some(entry) = pending.0.as_mut() else {
+        return;
+    };
+    // Control is held for the game's shortcuts, which shouldn't end up in the name.
+    let keys = input.get_just_pressed().filter(|_| !input.pressed(Key::Control));
+    for key in keys {
+        match key {
+            Key::Character(text) => {
+                let typed = text.chars().filter(|c| c.is_alphanumeric());
+                entry.name.extend(typed);
+            }
+            Key::Space => entry.name.push(' '),
+            Key::Backspace => {
+                entry.name.pop();
+            }
+            _ => {}
+        }
+    }
+    entry.name = entry.name.chars().take(MAX_NAME_LENGTH).collect();
+
+    let tapped = camera_query.single().is_ok_and(|(camera, camera_transform)| {
+        touch::tapped_within(
+            &touches,
+            camera,
+            camera_transform,
+            Vec2::new(0.0, NAME_ENTRY_Y),
+            NAME_ENTRY_SIZE,
+        )
+    });
+    if !input.just_pressed(Key::Enter) && !tapped {
+        for mut text in &mut text_query {
+            text.0 = name_entry_label(&entry.name);
+        }
+        return;
+    }
+
+    let Some(mut entry) = pending.0.take() else {
+        return;
+    };
+    entry.name = entry.name.trim().to_string();
+    for mut text in &mut text_query {
+        text.0 = if entry.name.is_empty() {
+            "High score saved".to_string()
+        } else {
+            format!("High score saved for {}", entry.name)
+        };
+    }
+    high_scores.record(entry);
+    high_scores.save();
+    spawn_options(&mut commands);
+}
+
+fn choose_option(
+    touches: Res<Touches>,
+    camera_query: Query<(Entity, &Camera, &GlobalTransform), With<GameCamera>>,
+    mut activated: MessageReader<MenuActivated>,
+    option_query: Query<&GameOverOption>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+    screen_query: Query<Entity, With<GameOverScreen>>,
+    game_query: Query<Entity, With<GameEntity>>,
+    mut score: ResMut<GameScore>,
+    mut combo: ResMut<Combo>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut energy: ResMut<BumpEnergy>,
+) {
+    let tapped = camera_query.single().ok().and_then(|(_, camera, camera_transform)| {
+        option_query.iter().copied().find(|option| {
+            touch::tapped_within(
+                &touches,
+                camera,
+                camera_transform,
+                Vec2::new(0.0, option.y()),
+                OPTION_SIZE,
+            )
+        })
+    });
+    let chosen = activated
+        .read()
+        .find_map(|MenuActivated(entity)| option_query.get(*entity).ok().copied())
+        .or(tapped);
+    let Some(option) = chosen else {
+        return;
+    };
+
+    for entity in &screen_query {
+        commands.entity(entity).despawn();
+    }
+    for entity in &game_query {
+        commands.entity(entity).despawn();
+    }
+    score.0 = 0;
+    combo.count = 0;
+    current_level.0 = 0;
+    energy.0 = 1.0;
+    match option {
+        GameOverOption::Retry => next_state.set(GameState::Playing),
+        GameOverOption::Menu => {
+            // The splash screen brings its own camera.
+            for (entity, _, _) in &camera_query {
+                commands.entity(entity).despawn();
+            }
+            next_state.set(GameState::Splash);
+        }
+    }
+}
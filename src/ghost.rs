@@ -0,0 +1,149 @@
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+
+use crate::config::GameConfig;
+use crate::level::{CurrentLevel, LevelClock, Levels};
+use crate::replay::{self, GhostFrame, ReplayPlayback};
+use crate::screenshot::{self, Toast};
+use crate::settings::Settings;
+use crate::{GameEntity, GameState, BALL_SIZE, PADDLE_HEIGHT};
+
+const GHOST_COLOR: Color = Color::srgba(0.6, 0.8, 1.0, 0.3);
+
+/// The fastest saved clear of the current level on the current difficulty, raced against
+/// as a see-through paddle and ball. Toggled with Ctrl+G.
+///
+/// The ghost only retraces where things were drawn in that run; it doesn't collide with
+/// anything or react to the live game.
+#[derive(Resource, Default)]
+struct Ghost(Vec<GhostFrame>);
+
+#[derive(Component)]
+struct GhostPaddle;
+
+#[derive(Component)]
+struct GhostBall;
+
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Ghost>()
+            .add_systems(OnEnter(GameState::Playing), spawn_ghost)
+            .add_systems(Update, toggle_ghost)
+            .add_systems(Update, move_ghost.run_if(in_state(GameState::Playing)));
+    }
+}
+
+fn toggle_ghost(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if input.pressed(Key::Control) && input.just_pressed(Key::Character("g".into())) {
+        settings.ghost = !settings.ghost;
+        settings.save();
+        let state = if settings.ghost { "on" } else { "off" };
+        screenshot::show_toast(&mut commands, &toast_query, format!("Ghost {state}"));
+    }
+}
+
+fn spawn_ghost(
+    mut commands: Commands,
+    mut ghost: ResMut<Ghost>,
+    playback: Option<Res<ReplayPlayback>>,
+    current_level: Res<CurrentLevel>,
+    levels: Res<Levels>,
+    config: Res<GameConfig>,
+    settings: Res<Settings>,
+) {
+    ghost.0.clear();
+    if playback.is_some() {
+        return;
+    }
+    let level_name = &levels.get(current_level.0).name;
+    let best = replay::saved_replays()
+        .into_iter()
+        .filter_map(|path| replay::Replay::load(&path).ok())
+        .filter(|run| {
+            run.header.level_name == *level_name
+                && run.header.difficulty == settings.difficulty
+                && !run.ghost.is_empty()
+        })
+        .min_by(|a, b| a.header.clear_time.total_cmp(&b.header.clear_time));
+    let Some(best) = best else {
+        return;
+    };
+    ghost.0 = best.ghost;
+
+    let paddle_size = Vec2::new(config.paddle_width * best.header.paddle_scale, PADDLE_HEIGHT);
+    let ball_size = BALL_SIZE * best.header.ball_scale;
+    commands.spawn((
+        Sprite {
+            color: GHOST_COLOR,
+            custom_size: Some(paddle_size),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, -0.2),
+        Visibility::Hidden,
+        GhostPaddle,
+        GameEntity,
+    ));
+    commands.spawn((
+        Sprite {
+            color: GHOST_COLOR,
+            custom_size: Some(Vec2::splat(ball_size)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, -0.2),
+        Visibility::Hidden,
+        GhostBall,
+        GameEntity,
+    ));
+}
+
+/// Where the ghost was at `time`, blended between the recorded frames either side.
+fn sample(frames: &[GhostFrame], time: f32) -> Option<(Vec2, Option<Vec2>)> {
+    let next = frames.partition_point(|frame| frame.time <= time);
+    if next == 0 || next >= frames.len() {
+        return None;
+    }
+    let (a, b) = (frames[next - 1], frames[next]);
+    let t = ((time - a.time) / (b.time - a.time).max(f32::EPSILON)).clamp(0.0, 1.0);
+    let lerp = |from: (f32, f32), to: (f32, f32)| Vec2::new(from.0, from.1).lerp(Vec2::new(to.0, to.1), t);
+    let ball = match (a.ball, b.ball) {
+        (Some(from), Some(to)) => Some(lerp(from, to)),
+        (ball, _) => ball.map(|(x, y)| Vec2::new(x, y)),
+    };
+    Some((lerp(a.paddle, b.paddle), ball))
+}
+
+fn move_ghost(
+    ghost: Res<Ghost>,
+    clock: Res<LevelClock>,
+    settings: Res<Settings>,
+    mut paddle_query: Query<(&mut Transform, &mut Visibility), (With<GhostPaddle>, Without<GhostBall>)>,
+    mut ball_query: Query<(&mut Transform, &mut Visibility), (With<GhostBall>, Without<GhostPaddle>)>,
+) {
+    // Once the best run has cleared the level, the ghost is gone.
+    let sampled = sample(&ghost.0, clock.elapsed).filter(|_| settings.ghost);
+    for (mut transform, mut visibility) in &mut paddle_query {
+        match sampled {
+            Some((paddle, _)) => {
+                transform.translation = paddle.extend(transform.translation.z);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+    for (mut transform, mut visibility) in &mut ball_query {
+        match sampled.and_then(|(_, ball)| ball) {
+            Some(ball) => {
+                transform.translation = ball.extend(transform.translation.z);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
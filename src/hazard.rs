@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::projectile::{self, ProjectileEffect};
+use crate::{BlockDestroyed, GameState};
+
+pub const HAZARD_COLOR: Color = Color::srgb(0.7, 0.2, 0.9);
+
+const SHARD_SIZE: Vec2 = Vec2::new(6.0, 10.0);
+const SHARD_COLOR: Color = Color::srgb(0.85, 0.45, 1.0);
+const SHARD_SPEED: f32 = 260.0;
+/// Widest angle, in radians either side of straight down, a shard can fall at.
+const SHARD_SPREAD: f32 = 0.5;
+/// Points a shard knocks off the score when it hits the paddle.
+const SHARD_PENALTY: u32 = 25;
+
+/// Marks a block that breaks into a spray of shards the paddle has to dodge, placed with
+/// `!` in a level's rows.
+#[derive(Component)]
+pub struct Hazard;
+
+pub struct HazardPlugin;
+
+impl Plugin for HazardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, release_shards.run_if(in_state(GameState::Playing)));
+    }
+}
+
+fn release_shards(mut commands: Commands, mut destroyed: MessageReader<BlockDestroyed>) {
+    let mut rng = rand::rng();
+    for block in destroyed.read().filter(|block| block.hazard) {
+        let count = rng.random_range(2..=3);
+        for i in 0..count {
+            // Fanned out evenly, with a little jitter so no two sprays look alike.
+            let t = i as f32 / (count - 1) as f32;
+            let angle = SHARD_SPREAD * (2.0 * t - 1.0) + rng.random_range(-0.1..0.1);
+            let velocity = Vec2::new(angle.sin(), -angle.cos()) * SHARD_SPEED;
+            projectile::spawn_projectile(
+                &mut commands,
+                block.position,
+                velocity,
+                SHARD_SIZE,
+                SHARD_COLOR,
+                ProjectileEffect::CostScore(SHARD_PENALTY),
+            );
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::cheats::Cheats;
+use crate::config::Difficulty;
+use crate::level::CurrentLevel;
+use crate::new_game_plus::NewGamePlus;
+use crate::scoring::GameScore;
+use crate::settings::Settings;
+use crate::GameState;
+
+const HIGH_SCORES_PATH: &str = "highscores.ron";
+/// Entries kept on each board, and shown at most on any one screen.
+pub const MAX_ENTRIES: usize = 10;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScoreEntry {
+    /// Entered on the game-over screen; runs recorded on a win are left unnamed.
+    #[serde(default)]
+    pub name: String,
+    pub score: u32,
+    pub difficulty: Difficulty,
+    pub levels_cleared: usize,
+    /// New Game+ cycle the run was on, each one its own category on the table.
+    #[serde(default)]
+    pub new_game_plus: u32,
+    /// Slowest game speed used at any point in the run; below 1.0 means a slowed run.
+    #[serde(default = "full_speed")]
+    pub game_speed: f32,
+    /// Seconds since the Unix epoch when the run ended; 0 for entries saved before this
+    /// was tracked.
+    #[serde(default)]
+    pub recorded_at: u64,
+}
+
+fn full_speed() -> f32 {
+    1.0
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since_epoch| since_epoch.as_secs())
+}
+
+/// Slowest game speed seen since the current run started.
+#[derive(Resource)]
+pub struct RunGameSpeed(f32);
+
+impl Default for RunGameSpeed {
+    fn default() -> Self {
+        RunGameSpeed(full_speed())
+    }
+}
+
+/// Best local runs, highest score first, keeping up to `MAX_ENTRIES` for each difficulty
+/// on each New Game+ cycle.
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct HighScores {
+    pub entries: Vec<ScoreEntry>,
+}
+
+impl HighScores {
+    fn load() -> Self {
+        fs::read_to_string(HIGH_SCORES_PATH)
+            .ok()
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let contents = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(err) => {
+                error!("failed to serialize high scores: {err}");
+                return;
+            }
+        };
+        if let Err(err) = fs::write(HIGH_SCORES_PATH, contents) {
+            error!("failed to write {HIGH_SCORES_PATH}: {err}");
+        }
+    }
+
+    pub fn record(&mut self, entry: ScoreEntry) {
+        let (difficulty, cycle) = (entry.difficulty, entry.new_game_plus);
+        self.entries.push(entry);
+        self.entries.sort_by(|a, b| b.score.cmp(&a.score));
+        let mut kept = 0;
+        self.entries.retain(|entry| {
+            if entry.difficulty != difficulty || entry.new_game_plus != cycle {
+                return true;
+            }
+            kept += 1;
+            kept <= MAX_ENTRIES
+        });
+    }
+
+    /// Whether `score` would make it onto the `difficulty` table for New Game+ `cycle`.
+    fn qualifies(&self, score: u32, difficulty: Difficulty, cycle: u32) -> bool {
+        let scores: Vec<u32> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.difficulty == difficulty && entry.new_game_plus == cycle)
+            .map(|entry| entry.score)
+            .collect();
+        scores.len() < MAX_ENTRIES || scores.last().is_some_and(|lowest| score > *lowest)
+    }
+}
+
+/// A game-over score waiting on the player's name before it goes on the table.
+#[derive(Resource, Default)]
+pub struct PendingHighScore(pub Option<ScoreEntry>);
+
+pub struct HighScoresPlugin;
+
+impl Plugin for HighScoresPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HighScores::load())
+            .init_resource::<RunGameSpeed>()
+            .init_resource::<PendingHighScore>()
+            .add_systems(OnEnter(GameState::Playing), reset_run_game_speed)
+            .add_systems(Update, track_run_game_speed.run_if(in_state(GameState::Playing)))
+            .add_systems(OnEnter(GameState::GameWon), record_high_score);
+    }
+}
+
+fn reset_run_game_speed(current_level: Res<CurrentLevel>, mut run_speed: ResMut<RunGameSpeed>) {
+    if current_level.0 == 0 {
+        *run_speed = RunGameSpeed::default();
+    }
+}
+
+fn track_run_game_speed(settings: Res<Settings>, mut run_speed: ResMut<RunGameSpeed>) {
+    run_speed.0 = run_speed.0.min(settings.game_speed);
+}
+
+fn record_high_score(
+    score: Res<GameScore>,
+    settings: Res<Settings>,
+    current_level: Res<CurrentLevel>,
+    cheats: Res<Cheats>,
+    run_speed: Res<RunGameSpeed>,
+    cycle: Res<NewGamePlus>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    if cheats.any() {
+        return;
+    }
+    high_scores.record(ScoreEntry {
+        name: String::new(),
+        score: score.0,
+        difficulty: settings.difficulty,
+        levels_cleared: current_level.0 + 1,
+        new_game_plus: cycle.0,
+        game_speed: run_speed.0,
+        recorded_at: now(),
+    });
+    high_scores.save();
+}
+
+/// Holds a losing run's score for name entry if it's good enough for the table. Run by the
+/// game-over screen before it's laid out.
+pub fn stage_high_score(
+    score: Res<GameScore>,
+    settings: Res<Settings>,
+    current_level: Res<CurrentLevel>,
+    cheats: Res<Cheats>,
+    run_speed: Res<RunGameSpeed>,
+    cycle: Res<NewGamePlus>,
+    high_scores: Res<HighScores>,
+    mut pending: ResMut<PendingHighScore>,
+) {
+    let qualifies = !cheats.any() && score.0 > 0 && high_scores.qualifies(score.0, settings.difficulty, cycle.0);
+    pending.0 = qualifies.then(|| ScoreEntry {
+        name: String::new(),
+        score: score.0,
+        difficulty: settings.difficulty,
+        levels_cleared: current_level.0,
+        new_game_plus: cycle.0,
+        game_speed: run_speed.0,
+        recorded_at: now(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: u32, difficulty: Difficulty) -> ScoreEntry {
+        ScoreEntry {
+            name: String::new(),
+            score,
+            difficulty,
+            levels_cleared: 1,
+            new_game_plus: 0,
+            game_speed: 1.0,
+            recorded_at: 0,
+        }
+    }
+
+    fn board(high_scores: &HighScores, difficulty: Difficulty) -> Vec<u32> {
+        high_scores
+            .entries
+            .iter()
+            .filter(|entry| entry.difficulty == difficulty)
+            .map(|entry| entry.score)
+            .collect()
+    }
+
+    #[test]
+    fn each_difficulty_keeps_its_own_top_ten() {
+        let mut high_scores = HighScores::default();
+        for score in 1..=5 {
+            high_scores.record(entry(score, Difficulty::Easy));
+        }
+        for score in 1..=12 {
+            high_scores.record(entry(score * 100, Difficulty::Normal));
+        }
+        for score in 1..=15 {
+            high_scores.record(entry(score * 1000, Difficulty::Hard));
+        }
+
+        assert_eq!(board(&high_scores, Difficulty::Easy), vec![5, 4, 3, 2, 1]);
+        assert_eq!(
+            board(&high_scores, Difficulty::Normal),
+            (3..=12).rev().map(|score| score * 100).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            board(&high_scores, Difficulty::Hard),
+            (6..=15).rev().map(|score| score * 1000).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn qualifies_against_its_own_difficulty() {
+        let mut high_scores = HighScores::default();
+        for score in 1..=10 {
+            high_scores.record(entry(score * 1000, Difficulty::Hard));
+        }
+
+        assert!(!high_scores.qualifies(500, Difficulty::Hard, 0));
+        assert!(high_scores.qualifies(1500, Difficulty::Hard, 0));
+        assert!(high_scores.qualifies(500, Difficulty::Easy, 0));
+        assert!(high_scores.qualifies(500, Difficulty::Hard, 1));
+    }
+}
@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use bevy::prelude::*;
+
+use crate::config::{self, ConfigFile, GameConfig};
+use crate::level::{self, Levels};
+use crate::mods::ModRegistry;
+use crate::new_game_plus::NewGamePlus;
+use crate::settings::Settings;
+
+/// Seconds between checks of the watched files on disk.
+const POLL_INTERVAL: f32 = 1.0;
+
+/// When each watched file was last modified, as of the last check.
+#[derive(Resource, Default)]
+struct WatchedFiles(Vec<(PathBuf, Option<SystemTime>)>);
+
+#[derive(Resource)]
+struct PollTimer(Timer);
+
+/// Re-reads `config.ron` and the level files whenever they change on disk, so tuning can
+/// be edited while the game runs. Level changes take effect from the next level laid out.
+pub struct HotReloadPlugin;
+
+impl Plugin for HotReloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WatchedFiles>()
+            .insert_resource(PollTimer(Timer::from_seconds(POLL_INTERVAL, TimerMode::Repeating)))
+            .add_systems(Startup, watch_files)
+            .add_systems(Update, (reload_config, reload_levels).run_if(files_changed));
+    }
+}
+
+fn watched_paths(registry: &ModRegistry) -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(config::CONFIG_PATH), PathBuf::from(level::LEVELS_PATH)];
+    paths.extend(registry.files(level::LEVELS_FILE).map(|(_, path)| path));
+    paths
+}
+
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn watch_files(registry: Res<ModRegistry>, mut watched: ResMut<WatchedFiles>) {
+    watched.0 = watched_paths(&registry)
+        .into_iter()
+        .map(|path| {
+            let time = modified(&path);
+            (path, time)
+        })
+        .collect();
+}
+
+fn files_changed(time: Res<Time<Real>>, mut timer: ResMut<PollTimer>, mut watched: ResMut<WatchedFiles>) -> bool {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return false;
+    }
+    let mut changed = false;
+    for (path, last_modified) in &mut watched.0 {
+        let current = modified(path);
+        if current != *last_modified {
+            info!("{} changed, reloading", path.display());
+            *last_modified = current;
+            changed = true;
+        }
+    }
+    changed
+}
+
+// Both files are cheap to parse, so whichever changed, both are read again.
+fn reload_config(
+    mut file: ResMut<ConfigFile>,
+    settings: Res<Settings>,
+    cycle: Res<NewGamePlus>,
+    mut config: ResMut<GameConfig>,
+) {
+    *file = ConfigFile::load();
+    *config = file.resolve(settings.difficulty, cycle.0);
+}
+
+fn reload_levels(registry: Res<ModRegistry>, mut levels: ResMut<Levels>) {
+    *levels = Levels::load(&registry);
+}
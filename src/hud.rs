@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+
+use crate::bullet_time::BulletTime;
+use crate::config::GameConfig;
+use crate::{Ball, BumpEnergy, GameEntity, GameState, Velocity, BUMP_ENERGY_COST};
+
+const GAUGE_WIDTH: f32 = 160.0;
+const GAUGE_HEIGHT: f32 = 12.0;
+const GAUGE_BACKGROUND: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+const SPEED_FILL: Color = Color::srgb(0.95, 0.55, 0.2);
+const ENERGY_FILL: Color = Color::srgb(0.3, 0.75, 1.0);
+/// Energy fill color while there isn't enough left for a bump.
+const ENERGY_LOW_FILL: Color = Color::srgb(0.35, 0.35, 0.45);
+const FOCUS_FILL: Color = Color::srgb(0.7, 0.45, 1.0);
+const FOCUS_ACTIVE_FILL: Color = Color::srgb(0.95, 0.85, 1.0);
+
+/// Inner bar of the speed gauge; its width tracks the fastest ball.
+#[derive(Component)]
+struct SpeedGaugeFill;
+
+#[derive(Component)]
+struct EnergyGaugeFill;
+
+#[derive(Component)]
+struct FocusGaugeFill;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                update_speed_gauge,
+                // The meter carries over between levels, so a freshly spawned gauge is
+                // filled in before the energy next changes.
+                update_energy_gauge
+                    .run_if(resource_changed::<BumpEnergy>.or(any_match_filter::<Added<EnergyGaugeFill>>)),
+                update_focus_gauge.run_if(resource_changed::<BulletTime>),
+            )
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Spawns a labelled horizontal bar at `top` pixels from the top-left corner, tagged with
+/// `marker` on the fill so its width can be driven as a percentage.
+pub fn spawn_gauge<M: Component>(commands: &mut Commands, label: &str, top: f32, fill_color: Color, marker: M) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(40.0),
+                top: Val::Px(top),
+                column_gap: Val::Px(10.0),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            GameEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+            ));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(GAUGE_WIDTH),
+                        height: Val::Px(GAUGE_HEIGHT),
+                        ..default()
+                    },
+                    BackgroundColor(GAUGE_BACKGROUND),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(fill_color),
+                        marker,
+                    ));
+                });
+        });
+}
+
+pub fn spawn_speed_gauge(commands: &mut Commands) {
+    spawn_gauge(commands, "Speed", 100.0, SPEED_FILL, SpeedGaugeFill);
+}
+
+pub fn spawn_energy_gauge(commands: &mut Commands) {
+    spawn_gauge(commands, "Bump", 125.0, ENERGY_FILL, EnergyGaugeFill);
+}
+
+pub fn spawn_focus_gauge(commands: &mut Commands) {
+    spawn_gauge(commands, "Focus", 150.0, FOCUS_FILL, FocusGaugeFill);
+}
+
+fn update_speed_gauge(
+    config: Res<GameConfig>,
+    ball_query: Query<&Velocity, With<Ball>>,
+    mut fill_query: Query<&mut Node, With<SpeedGaugeFill>>,
+) {
+    let speed = ball_query
+        .iter()
+        .map(|velocity| velocity.0.length())
+        .fold(0.0, f32::max);
+    let width = Val::Percent(config.speed_fraction(speed) * 100.0);
+    // Touching the node would redo the UI layout, so it's left alone at a steady speed.
+    for mut node in &mut fill_query {
+        if node.width != width {
+            node.width = width;
+        }
+    }
+}
+
+fn update_energy_gauge(
+    energy: Res<BumpEnergy>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<EnergyGaugeFill>>,
+) {
+    for (mut node, mut color) in &mut fill_query {
+        node.width = Val::Percent(energy.0 * 100.0);
+        color.0 = if energy.0 >= BUMP_ENERGY_COST {
+            ENERGY_FILL
+        } else {
+            ENERGY_LOW_FILL
+        };
+    }
+}
+
+fn update_focus_gauge(
+    bullet_time: Res<BulletTime>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<FocusGaugeFill>>,
+) {
+    for (mut node, mut color) in &mut fill_query {
+        node.width = Val::Percent(bullet_time.focus * 100.0);
+        color.0 = if bullet_time.active {
+            FOCUS_ACTIVE_FILL
+        } else {
+            FOCUS_FILL
+        };
+    }
+}
@@ -0,0 +1,113 @@
+use bevy::input::keyboard::Key;
+use bevy::input::mouse::MouseMotion;
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+
+use crate::display::GameCamera;
+use crate::game_over::GameOverScreen;
+use crate::level::CurrentLevel;
+use crate::new_game_plus::NewGamePlus;
+use crate::scoring::{Combo, GameScore};
+use crate::screenshot::{self, Toast};
+use crate::settings::Settings;
+use crate::{BumpEnergy, GameEntity, GameState, RestartButton, WinScreen};
+
+/// Seconds without any input before an end or menu screen goes back to the splash.
+const IDLE_TIMEOUT: f32 = 120.0;
+
+/// Screens that are left for the splash after the timeout, like an arcade cabinet
+/// waiting for its next player.
+const IDLE_SCREENS: [GameState; 5] = [
+    GameState::GameWon,
+    GameState::GameOver,
+    GameState::Replays,
+    GameState::Leaderboards,
+    GameState::Credits,
+];
+
+/// Seconds since the last input on the current screen.
+#[derive(Resource, Default)]
+struct IdleTime(f32);
+
+pub struct IdlePlugin;
+
+impl Plugin for IdlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<IdleTime>()
+            .add_systems(Update, toggle_idle_timeout)
+            .add_systems(Update, reset_idle_time.run_if(state_changed::<GameState>))
+            .add_systems(
+                Update,
+                return_when_idle.run_if(on_idle_screen.and(|settings: Res<Settings>| settings.idle_timeout)),
+            );
+    }
+}
+
+fn on_idle_screen(state: Res<State<GameState>>) -> bool {
+    IDLE_SCREENS.contains(state.get())
+}
+
+fn toggle_idle_timeout(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if input.pressed(Key::Control) && input.just_pressed(Key::Character("k".into())) {
+        settings.idle_timeout = !settings.idle_timeout;
+        settings.save();
+        let state = if settings.idle_timeout { "on" } else { "off" };
+        screenshot::show_toast(&mut commands, &toast_query, format!("Idle timeout {state}"));
+    }
+}
+
+fn reset_idle_time(mut idle: ResMut<IdleTime>) {
+    idle.0 = 0.0;
+}
+
+// Leaves the screen the same way its "back to menu" option would: the run is over, and
+// the splash screen brings its own camera.
+fn return_when_idle(
+    time: Res<Time<Real>>,
+    keys: Res<ButtonInput<Key>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut mouse_motion: MessageReader<MouseMotion>,
+    touches: Res<Touches>,
+    gamepads: Query<&Gamepad>,
+    mut idle: ResMut<IdleTime>,
+    mut commands: Commands,
+    screen_query: Query<
+        Entity,
+        Or<(With<WinScreen>, With<RestartButton>, With<GameOverScreen>, With<GameEntity>, With<GameCamera>)>,
+    >,
+    mut score: ResMut<GameScore>,
+    mut combo: ResMut<Combo>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut energy: ResMut<BumpEnergy>,
+    mut cycle: ResMut<NewGamePlus>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let active = keys.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+        || mouse_motion.read().count() > 0
+        || touches.iter_just_pressed().next().is_some()
+        || gamepads.iter().any(|gamepad| gamepad.get_just_pressed().next().is_some());
+    if active {
+        idle.0 = 0.0;
+        return;
+    }
+    idle.0 += time.delta_secs();
+    if idle.0 < IDLE_TIMEOUT {
+        return;
+    }
+
+    for entity in &screen_query {
+        commands.entity(entity).despawn();
+    }
+    score.0 = 0;
+    combo.count = 0;
+    current_level.0 = 0;
+    energy.0 = 1.0;
+    cycle.0 = 0;
+    next_state.set(GameState::Splash);
+}
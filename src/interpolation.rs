@@ -0,0 +1,78 @@
+use bevy::app::RunFixedMainLoopSystems;
+use bevy::prelude::*;
+
+/// Smooths the movement of an entity simulated in `FixedUpdate` by drawing it between
+/// its last two simulated positions, based on how far into the next step the frame is.
+///
+/// The simulated position is put back before each run of the fixed loop. Anything that
+/// moves the entity outside the fixed loop (serving, snapshots) is taken as a teleport,
+/// so it's shown in place straight away rather than eased into; jumps made inside it,
+/// like going through a portal, call `teleport` for the same.
+#[derive(Component, Default)]
+pub struct Interpolated {
+    previous: Vec2,
+    current: Vec2,
+    /// Where the entity was last drawn, to tell a teleport from our own interpolation.
+    shown: Vec2,
+}
+
+impl Interpolated {
+    /// Shows a jump to `position` made during this fixed step in place, instead of
+    /// easing across the arena to it.
+    pub fn teleport(&mut self, position: Vec2) {
+        self.previous = position;
+    }
+}
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            RunFixedMainLoop,
+            (
+                restore_simulated_position.in_set(RunFixedMainLoopSystems::BeforeFixedMainLoop),
+                interpolate_position.in_set(RunFixedMainLoopSystems::AfterFixedMainLoop),
+            ),
+        )
+        .add_systems(FixedFirst, record_previous_position)
+        .add_systems(FixedLast, record_current_position);
+    }
+}
+
+fn set_xy(transform: &mut Transform, position: Vec2) {
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+}
+
+fn restore_simulated_position(mut query: Query<(&mut Transform, &mut Interpolated)>) {
+    for (mut transform, mut interpolated) in &mut query {
+        let position = transform.translation.truncate();
+        if position != interpolated.shown {
+            interpolated.previous = position;
+            interpolated.current = position;
+        }
+        set_xy(&mut transform, interpolated.current);
+    }
+}
+
+fn record_previous_position(mut query: Query<(&Transform, &mut Interpolated)>) {
+    for (transform, mut interpolated) in &mut query {
+        interpolated.previous = transform.translation.truncate();
+    }
+}
+
+fn record_current_position(mut query: Query<(&Transform, &mut Interpolated)>) {
+    for (transform, mut interpolated) in &mut query {
+        interpolated.current = transform.translation.truncate();
+    }
+}
+
+fn interpolate_position(fixed_time: Res<Time<Fixed>>, mut query: Query<(&mut Transform, &mut Interpolated)>) {
+    let fraction = fixed_time.overstep_fraction();
+    for (mut transform, mut interpolated) in &mut query {
+        let shown = interpolated.previous.lerp(interpolated.current, fraction);
+        set_xy(&mut transform, shown);
+        interpolated.shown = shown;
+    }
+}
@@ -0,0 +1,244 @@
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+
+use crate::cheats;
+use crate::config::Difficulty;
+use crate::display::GameCamera;
+use crate::highscores::{HighScores, ScoreEntry, MAX_ENTRIES};
+use crate::seed;
+use crate::{GameState, SplashScreen, StartButton};
+
+const BOARD_TOP_Y: f32 = 200.0;
+const BOARD_ROW_SPACING: f32 = 32.0;
+const OWN_RUN_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// Which part of the high score table is being shown.
+#[derive(Resource, Default, PartialEq)]
+struct LeaderboardFilter {
+    /// `None` shows every difficulty together.
+    difficulty: Option<Difficulty>,
+    new_game_plus: u32,
+}
+
+impl LeaderboardFilter {
+    fn matches(&self, entry: &ScoreEntry) -> bool {
+        entry.new_game_plus == self.new_game_plus
+            && self.difficulty.is_none_or(|difficulty| entry.difficulty == difficulty)
+    }
+
+    fn label(&self) -> String {
+        let difficulty = self.difficulty.map_or("All difficulties", Difficulty::name);
+        let category = if self.new_game_plus == 0 {
+            "Main game".to_string()
+        } else {
+            format!("New Game+ {}", self.new_game_plus)
+        };
+        format!("< {difficulty} >   ^ {category} v")
+    }
+}
+
+#[derive(Component)]
+struct LeaderboardScreen;
+
+/// Rows and rank line, rebuilt whenever the filter changes.
+#[derive(Component)]
+struct LeaderboardRow;
+
+#[derive(Component)]
+struct LeaderboardHint;
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LeaderboardFilter>()
+            .add_systems(OnEnter(GameState::Splash), spawn_leaderboard_hint)
+            .add_systems(OnExit(GameState::Splash), despawn_leaderboard_hint)
+            .add_systems(
+                Update,
+                open_leaderboard
+                    .after(cheats::detect_cheat_codes)
+                    .run_if(in_state(GameState::Splash).and(seed::not_entering_seed).and(cheats::not_typing_code)),
+            )
+            .add_systems(OnEnter(GameState::Leaderboards), (spawn_leaderboard, show_rows).chain())
+            .add_systems(
+                Update,
+                (browse_leaderboard, show_rows.run_if(resource_changed::<LeaderboardFilter>))
+                    .chain()
+                    .run_if(in_state(GameState::Leaderboards)),
+            )
+            .add_systems(OnExit(GameState::Leaderboards), despawn_leaderboard);
+    }
+}
+
+fn spawn_leaderboard_hint(mut commands: Commands) {
+    commands.spawn((
+        Text2d("H: High scores".to_string()),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, 255.0, 2.0),
+        LeaderboardHint,
+    ));
+}
+
+fn despawn_leaderboard_hint(mut commands: Commands, hint_query: Query<Entity, With<LeaderboardHint>>) {
+    for entity in &hint_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn open_leaderboard(
+    input: Res<ButtonInput<Key>>,
+    mut commands: Commands,
+    splash_query: Query<Entity, Or<(With<SplashScreen>, With<StartButton>)>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if input.pressed(Key::Control) || !input.just_pressed(Key::Character("h".into())) {
+        return;
+    }
+    for entity in &splash_query {
+        commands.entity(entity).despawn();
+    }
+    next_state.set(GameState::Leaderboards);
+}
+
+fn spawn_leaderboard(mut commands: Commands) {
+    commands.spawn((
+        Text2d("High scores\nLeft/Right: Difficulty   Up/Down: Category   Escape: Back".to_string()),
+        Transform::from_xyz(0.0, BOARD_TOP_Y + 110.0, 2.0),
+        LeaderboardScreen,
+    ));
+}
+
+fn row_label(rank: usize, entry: &ScoreEntry) -> String {
+    let name = if entry.name.is_empty() { "-" } else { &entry.name };
+    let slowed = if entry.game_speed < 1.0 {
+        format!("  ({:.0}% speed)", entry.game_speed * 100.0)
+    } else {
+        String::new()
+    };
+    format!(
+        "{rank:>2}. {name}  {} points  {}  {} levels{slowed}",
+        entry.score,
+        entry.difficulty.name(),
+        entry.levels_cleared
+    )
+}
+
+// The player's most recent run that's on this board is picked out, with its rank
+// underneath, so they can see where it landed.
+fn show_rows(
+    high_scores: Res<HighScores>,
+    filter: Res<LeaderboardFilter>,
+    mut commands: Commands,
+    row_query: Query<Entity, With<LeaderboardRow>>,
+) {
+    for entity in &row_query {
+        commands.entity(entity).despawn();
+    }
+    // Every difficulty together is cut to the best ten, like each board on its own.
+    let entries: Vec<&ScoreEntry> = high_scores
+        .entries
+        .iter()
+        .filter(|entry| filter.matches(entry))
+        .take(MAX_ENTRIES)
+        .collect();
+    let latest = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry.recorded_at > 0)
+        .max_by_key(|(_, entry)| entry.recorded_at)
+        .map(|(rank, _)| rank);
+
+    commands.spawn((
+        Text2d(filter.label()),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, BOARD_TOP_Y + 50.0, 2.0),
+        LeaderboardRow,
+        LeaderboardScreen,
+    ));
+    if entries.is_empty() {
+        commands.spawn((
+            Text2d("No scores here yet".to_string()),
+            Transform::from_xyz(0.0, BOARD_TOP_Y, 2.0),
+            LeaderboardRow,
+            LeaderboardScreen,
+        ));
+        return;
+    }
+    for (index, entry) in entries.iter().enumerate() {
+        let color = if latest == Some(index) { OWN_RUN_COLOR } else { Color::WHITE };
+        commands.spawn((
+            Text2d(row_label(index + 1, entry)),
+            TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+            TextColor(color),
+            Transform::from_xyz(0.0, BOARD_TOP_Y - index as f32 * BOARD_ROW_SPACING, 2.0),
+            LeaderboardRow,
+            LeaderboardScreen,
+        ));
+    }
+    if let Some(rank) = latest {
+        commands.spawn((
+            Text2d(format!("Your latest run: #{} of {}", rank + 1, entries.len())),
+            TextColor(OWN_RUN_COLOR),
+            Transform::from_xyz(0.0, BOARD_TOP_Y - (entries.len() as f32 + 1.0) * BOARD_ROW_SPACING, 2.0),
+            LeaderboardRow,
+            LeaderboardScreen,
+        ));
+    }
+}
+
+fn browse_leaderboard(
+    input: Res<ButtonInput<Key>>,
+    high_scores: Res<HighScores>,
+    mut filter: ResMut<LeaderboardFilter>,
+    camera_query: Query<Entity, With<GameCamera>>,
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if input.just_pressed(Key::Escape) {
+        // The splash screen brings its own camera.
+        for entity in &camera_query {
+            commands.entity(entity).despawn();
+        }
+        next_state.set(GameState::Splash);
+        return;
+    }
+    if input.just_pressed(Key::ArrowRight) {
+        let difficulty = match filter.difficulty {
+            None => Some(Difficulty::Easy),
+            Some(Difficulty::Hard) => None,
+            Some(difficulty) => Some(difficulty.next()),
+        };
+        filter.difficulty = difficulty;
+    }
+    if input.just_pressed(Key::ArrowLeft) {
+        let difficulty = match filter.difficulty {
+            None => Some(Difficulty::Hard),
+            Some(Difficulty::Easy) => None,
+            Some(difficulty) => Some(difficulty.previous()),
+        };
+        filter.difficulty = difficulty;
+    }
+    let top_cycle = high_scores.entries.iter().map(|entry| entry.new_game_plus).max().unwrap_or(0);
+    if input.just_pressed(Key::ArrowDown) && filter.new_game_plus < top_cycle {
+        filter.new_game_plus += 1;
+    }
+    if input.just_pressed(Key::ArrowUp) && filter.new_game_plus > 0 {
+        filter.new_game_plus -= 1;
+    }
+}
+
+fn despawn_leaderboard(mut commands: Commands, screen_query: Query<Entity, With<LeaderboardScreen>>) {
+    for entity in &screen_query {
+        commands.entity(entity).despawn();
+    }
+}
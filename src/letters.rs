@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+
+use crate::level::{CurrentLevel, Levels};
+use crate::scoring::GameScore;
+use crate::screenshot::{self, Toast};
+use crate::sfx::{PlaySfx, Sfx};
+use crate::{BlockDestroyed, GameEntity, GameState};
+
+/// Bonus for spelling the level's word, per letter in it.
+const WORD_BONUS_PER_LETTER: u32 = 250;
+const LETTER_COLOR: Color = Color::srgb(0.1, 0.08, 0.15);
+
+/// The letter shown on a block, placed with a letter in a level's rows.
+#[derive(Component, Clone, Copy)]
+pub struct Letter(pub char);
+
+/// How far the player has got spelling the current level's word, which only counts if
+/// its letters are broken in order.
+#[derive(Resource, Default)]
+pub struct WordProgress {
+    word: Vec<char>,
+    spelled: usize,
+    /// Set once a letter is broken out of turn, for the rest of the level.
+    missed: bool,
+}
+
+impl WordProgress {
+    fn label(&self) -> String {
+        let letters: String = self
+            .word
+            .iter()
+            .enumerate()
+            .map(|(i, letter)| if i < self.spelled { *letter } else { '_' })
+            .collect();
+        if self.missed {
+            format!("Word: {letters} (missed)")
+        } else {
+            format!("Word: {letters}")
+        }
+    }
+}
+
+#[derive(Component)]
+struct WordText;
+
+pub struct LettersPlugin;
+
+impl Plugin for LettersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WordProgress>()
+            .add_systems(OnEnter(GameState::Playing), (reset_word, spawn_word_text).chain())
+            .add_systems(
+                Update,
+                (spell_word, update_word_text.run_if(resource_changed::<WordProgress>))
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// Turns `block` into a letter block. Blocks are drawn by the shared block mesh, so it
+/// gets visibility of its own only to carry the label.
+pub fn spawn_letter(commands: &mut Commands, block: Entity, letter: char) {
+    let letter = letter.to_ascii_uppercase();
+    commands
+        .entity(block)
+        .insert((Letter(letter), Visibility::default()))
+        .with_child((
+            Text2d(letter.to_string()),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(LETTER_COLOR),
+            Transform::from_xyz(0.0, 0.0, 1.0),
+        ));
+}
+
+fn reset_word(mut progress: ResMut<WordProgress>, levels: Res<Levels>, current_level: Res<CurrentLevel>) {
+    let word = levels.get(current_level.0).word.as_deref().unwrap_or_default();
+    *progress = WordProgress {
+        word: word.chars().map(|letter| letter.to_ascii_uppercase()).collect(),
+        ..default()
+    };
+}
+
+fn spawn_word_text(mut commands: Commands, progress: Res<WordProgress>) {
+    if progress.word.is_empty() {
+        return;
+    }
+    commands.spawn((
+        Text::new(progress.label()),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(40.0),
+            top: Val::Px(225.0),
+            ..default()
+        },
+        WordText,
+        GameEntity,
+    ));
+}
+
+fn spell_word(
+    mut destroyed: MessageReader<BlockDestroyed>,
+    mut progress: ResMut<WordProgress>,
+    mut score: ResMut<GameScore>,
+    mut sfx: MessageWriter<PlaySfx>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    for letter in destroyed.read().filter_map(|block| block.letter) {
+        if progress.missed || progress.spelled == progress.word.len() {
+            continue;
+        }
+        if progress.word[progress.spelled] != letter {
+            progress.missed = true;
+            continue;
+        }
+        progress.spelled += 1;
+        if progress.spelled == progress.word.len() {
+            let bonus = WORD_BONUS_PER_LETTER * progress.word.len() as u32;
+            score.0 += bonus;
+            sfx.write(PlaySfx(Sfx::Fanfare));
+            let word: String = progress.word.iter().collect();
+            screenshot::show_toast(&mut commands, &toast_query, format!("{word} spelled! +{bonus}"));
+        }
+    }
+}
+
+fn update_word_text(progress: Res<WordProgress>, mut text_query: Query<&mut Text, With<WordText>>) {
+    for mut text in &mut text_query {
+        **text = progress.label();
+    }
+}
@@ -0,0 +1,327 @@
+use std::fs;
+use std::path::Path;
+
+use bevy::input::keyboard::Key;
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::display::GameCamera;
+use crate::force_zone::ForceZoneData;
+use crate::menu::MenuActivated;
+use crate::mods::ModRegistry;
+use crate::multiball::ExtraBallsData;
+use crate::obstacle::ObstacleData;
+use crate::portal::PortalPairData;
+use crate::pause;
+use crate::scoring::{Combo, GameScore};
+use crate::screenshot::{self, Toast};
+use crate::stars::StarRecord;
+use crate::{menu_button_tapped, BumpEnergy, GameEntity, GameState, WINDOW_HEIGHT, WINDOW_WIDTH};
+
+pub const LEVELS_FILE: &str = "levels.ron";
+pub const LEVELS_PATH: &str = "assets/levels.ron";
+
+/// Bonus points for every second a level is cleared under its target time.
+const TIME_BONUS_PER_SECOND: u32 = 10;
+
+/// Seconds R has to be held before the level restarts, so a stray tap doesn't cost a run.
+const QUICK_RESTART_HOLD: f32 = 0.75;
+
+/// Side walls that close in from both edges and back out on a loop.
+#[derive(Deserialize, Clone, Debug)]
+pub struct MovingWallsData {
+    /// Farthest each wall travels inward from its edge.
+    pub amplitude: f32,
+    /// Seconds for one full in-and-out cycle.
+    pub period: f32,
+}
+
+impl MovingWallsData {
+    /// How far each wall has moved in from its edge at `elapsed` seconds into the level.
+    pub fn inset_at(&self, elapsed: f32) -> f32 {
+        let phase = elapsed / self.period.max(f32::EPSILON) * std::f32::consts::TAU;
+        self.amplitude * (1.0 - phase.cos()) / 2.0
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct LevelData {
+    pub name: String,
+    /// Clear time, in seconds, that has to be beaten to earn a time bonus.
+    pub target_time: f32,
+    /// Block layout, top row first; `#` is a block, `!` a hazard block that sheds shards
+    /// when broken, `_`, `^`, `[` and `]` a block shielded on that face (see `Shield`),
+    /// a letter a letter block, and any other character is a gap.
+    pub rows: Vec<String>,
+    /// Word the level's letter blocks spell, for a bonus when they're broken in order.
+    #[serde(default)]
+    pub word: Option<String>,
+    /// Points for a block in each row, top row first; rows past the end of the list
+    /// take the last value, and a level without any scores every block as 1.
+    #[serde(default)]
+    pub row_points: Vec<u32>,
+    /// Bumpers placed in the open space between the blocks and the paddle.
+    #[serde(default)]
+    pub obstacles: Vec<ObstacleData>,
+    #[serde(default)]
+    pub portals: Vec<PortalPairData>,
+    #[serde(default)]
+    pub force_zones: Vec<ForceZoneData>,
+    #[serde(default)]
+    pub moving_walls: Option<MovingWallsData>,
+    /// Spin keeps bending the ball's path until its next bounce, rather than fading
+    /// out shortly after it leaves the paddle.
+    #[serde(default)]
+    pub curveball: bool,
+    /// Survival rules that add a ball at a time to the arena as the level goes on.
+    #[serde(default)]
+    pub extra_balls: Option<ExtraBallsData>,
+    /// Lua script under `assets/`, or a content pack, with hooks for the level's own
+    /// rules; see `scripting`.
+    #[serde(default)]
+    pub script: Option<String>,
+    /// Stars, summed over every level, needed to play this one; bonus levels that aren't
+    /// unlocked yet are skipped.
+    #[serde(default)]
+    pub stars_required: u32,
+}
+
+impl LevelData {
+    pub fn points_for_row(&self, row: usize) -> u32 {
+        self.row_points
+            .get(row)
+            .or(self.row_points.last())
+            .copied()
+            .unwrap_or(1)
+    }
+}
+
+impl Default for LevelData {
+    fn default() -> Self {
+        LevelData {
+            name: "The Wall".to_string(),
+            target_time: 90.0,
+            rows: vec!["################".to_string(); 4],
+            word: None,
+            row_points: Vec::new(),
+            obstacles: Vec::new(),
+            portals: Vec::new(),
+            force_zones: Vec::new(),
+            moving_walls: None,
+            curveball: false,
+            extra_balls: None,
+            script: None,
+            stars_required: 0,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Levels(pub Vec<LevelData>);
+
+fn read_levels(path: &Path) -> Result<Vec<LevelData>, String> {
+    fs::read_to_string(path)
+        .map_err(|err| err.to_string())
+        .and_then(|contents| ron::from_str::<Vec<LevelData>>(&contents).map_err(|err| err.to_string()))
+}
+
+impl Levels {
+    /// The built-in levels followed by those of every enabled content pack.
+    pub fn load(registry: &ModRegistry) -> Self {
+        let mut levels = match read_levels(Path::new(LEVELS_PATH)) {
+            Ok(levels) => levels,
+            Err(err) => {
+                error!("failed to load {LEVELS_PATH}: {err}");
+                Vec::new()
+            }
+        };
+        for (_, path) in registry.files(LEVELS_FILE) {
+            match read_levels(&path) {
+                Ok(pack_levels) => levels.extend(pack_levels),
+                Err(err) => error!("failed to load {}: {err}", path.display()),
+            }
+        }
+        if levels.is_empty() {
+            levels.push(LevelData::default());
+        }
+        Levels(levels)
+    }
+
+    pub fn get(&self, index: usize) -> &LevelData {
+        &self.0[index.min(self.0.len() - 1)]
+    }
+}
+
+/// Index into `Levels` of the level being played.
+#[derive(Resource, Default)]
+pub struct CurrentLevel(pub usize);
+
+/// Seconds spent on the current level.
+#[derive(Resource, Default)]
+pub struct LevelClock {
+    pub elapsed: f32,
+}
+
+/// Score when the current level started, which a quick restart goes back to.
+#[derive(Resource, Default)]
+struct LevelStartScore(u32);
+
+/// How long R has been held for a quick restart.
+#[derive(Resource, Default)]
+struct QuickRestartHold(f32);
+
+#[derive(Component)]
+pub struct LevelCompleteScreen;
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        let levels = Levels::load(app.world().resource::<ModRegistry>());
+        app.insert_resource(levels)
+            .init_resource::<CurrentLevel>()
+            .init_resource::<LevelClock>()
+            .init_resource::<LevelStartScore>()
+            .init_resource::<QuickRestartHold>()
+            .add_systems(OnEnter(GameState::Playing), (reset_level_clock, record_level_start_score))
+            .add_systems(
+                Update,
+                (
+                    tick_level_clock,
+                    quick_restart.run_if(pause::not_paused),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnEnter(GameState::LevelComplete), setup_level_complete)
+            .add_systems(Update, continue_button.run_if(in_state(GameState::LevelComplete)));
+    }
+}
+
+pub fn time_bonus(level: &LevelData, elapsed: f32) -> u32 {
+    (level.target_time - elapsed).max(0.0).ceil() as u32 * TIME_BONUS_PER_SECOND
+}
+
+fn reset_level_clock(mut clock: ResMut<LevelClock>) {
+    clock.elapsed = 0.0;
+}
+
+fn tick_level_clock(time: Res<Time>, mut clock: ResMut<LevelClock>) {
+    clock.elapsed += time.delta_secs();
+}
+
+fn record_level_start_score(score: Res<GameScore>, mut start_score: ResMut<LevelStartScore>) {
+    start_score.0 = score.0;
+}
+
+// Re-entering `Playing` runs the usual level setup again, so resetting here only has to
+// clear the arena and roll back the per-level state.
+fn quick_restart(
+    input: Res<ButtonInput<Key>>,
+    time: Res<Time<Real>>,
+    mut hold: ResMut<QuickRestartHold>,
+    start_score: Res<LevelStartScore>,
+    mut score: ResMut<GameScore>,
+    mut combo: ResMut<Combo>,
+    mut energy: ResMut<BumpEnergy>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+    game_query: Query<Entity, With<GameEntity>>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    let restart_key = Key::Character("r".into());
+    if !input.pressed(restart_key.clone()) || input.pressed(Key::Control) {
+        hold.0 = 0.0;
+        return;
+    }
+    if input.just_pressed(restart_key) {
+        screenshot::show_toast(&mut commands, &toast_query, "Hold R to restart the level".to_string());
+    }
+
+    hold.0 += time.delta_secs();
+    if hold.0 < QUICK_RESTART_HOLD {
+        return;
+    }
+    hold.0 = 0.0;
+    for entity in &game_query {
+        commands.entity(entity).despawn();
+    }
+    score.0 = start_score.0;
+    combo.count = 0;
+    *energy = BumpEnergy::default();
+    next_state.set(GameState::Playing);
+}
+
+fn setup_level_complete(
+    mut commands: Commands,
+    levels: Res<Levels>,
+    current: Res<CurrentLevel>,
+    clock: Res<LevelClock>,
+    mut score: ResMut<GameScore>,
+) {
+    let level = levels.get(current.0);
+    let bonus = time_bonus(level, clock.elapsed);
+    score.0 += bonus;
+
+    commands.spawn((
+        Sprite {
+            color: Color::srgba(0.0, 0.0, 0.0, 0.8),
+            custom_size: Some(Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 3.0),
+        LevelCompleteScreen,
+    ));
+
+    let lines = [
+        format!("{} cleared!", level.name),
+        format!("Time: {:.1}s (target {:.0}s)", clock.elapsed, level.target_time),
+        format!("Time bonus: +{bonus}"),
+        format!("Score: {}", score.0),
+    ];
+    for (i, line) in lines.into_iter().enumerate() {
+        commands.spawn((
+            Text2d(line),
+            Transform::from_xyz(0.0, 180.0 - i as f32 * 45.0, 4.0),
+            LevelCompleteScreen,
+        ));
+    }
+
+    crate::spawn_menu_button(&mut commands, "Press Spacebar or Tap to Continue", LevelCompleteScreen, 4.0);
+}
+
+fn continue_button(
+    input: Res<ButtonInput<Key>>,
+    touches: Res<Touches>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+    screen_query: Query<Entity, With<LevelCompleteScreen>>,
+    game_query: Query<Entity, With<GameEntity>>,
+    levels: Res<Levels>,
+    stars: Res<StarRecord>,
+    mut current: ResMut<CurrentLevel>,
+    mut activated: MessageReader<MenuActivated>,
+) {
+    let confirmed = activated
+        .read()
+        .any(|MenuActivated(entity)| screen_query.contains(*entity));
+    if !input.just_pressed(Key::Space) && !confirmed && !menu_button_tapped(&touches, &camera_query) {
+        return;
+    }
+    for entity in &screen_query {
+        commands.entity(entity).despawn();
+    }
+
+    // The last level leaves the cleared arena in place behind the win screen.
+    let next = (current.0 + 1..levels.0.len()).find(|index| levels.0[*index].stars_required <= stars.total());
+    if let Some(next) = next {
+        for entity in &game_query {
+            commands.entity(entity).despawn();
+        }
+        current.0 = next;
+        next_state.set(GameState::Playing);
+    } else {
+        next_state.set(GameState::GameWon);
+    }
+}
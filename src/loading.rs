@@ -0,0 +1,115 @@
+use bevy::asset::{LoadState, UntypedAssetId};
+use bevy::prelude::*;
+
+use crate::announcer::VoiceLines;
+use crate::mods::ModRegistry;
+use crate::sfx::SfxSounds;
+use crate::GameState;
+
+const IMAGES: [&str; 2] = ["splash.png", "ferris.png"];
+const FONTS: [&str; 1] = ["FiraSans-Bold.ttf"];
+
+const BAR_Y: f32 = -40.0;
+const BAR_SIZE: Vec2 = Vec2::new(400.0, 16.0);
+
+/// Assets loaded before the splash screen is shown, kept for the whole session so they
+/// never pop in on first use. Sounds are kept by their own plugins and only tracked
+/// here. Level data is read from disk at startup, so it's already in place by now.
+#[derive(Resource)]
+struct Preloaded(Vec<UntypedHandle>);
+
+#[derive(Component)]
+struct LoadingScreen;
+
+#[derive(Component)]
+struct LoadingBarFill;
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Loading), (start_loading, spawn_loading_screen))
+            .add_systems(Update, track_loading.run_if(in_state(GameState::Loading)))
+            .add_systems(OnExit(GameState::Loading), (despawn_loading_screen, crate::clear_game_camera));
+    }
+}
+
+fn start_loading(mut commands: Commands, asset_server: Res<AssetServer>, registry: Res<ModRegistry>) {
+    let images = IMAGES
+        .into_iter()
+        .map(|path| asset_server.load::<Image>(registry.asset_path(path)).untyped());
+    let fonts = FONTS
+        .into_iter()
+        .map(|path| asset_server.load::<Font>(registry.asset_path(path)).untyped());
+    commands.insert_resource(Preloaded(images.chain(fonts).collect()));
+}
+
+fn spawn_loading_screen(mut commands: Commands) {
+    crate::display::spawn_game_camera(&mut commands);
+    commands.spawn((
+        Text2d::new("Loading..."),
+        Transform::from_xyz(0.0, 0.0, 1.0),
+        LoadingScreen,
+    ));
+    commands.spawn((
+        Sprite {
+            color: Color::srgb(0.25, 0.25, 0.25),
+            custom_size: Some(BAR_SIZE),
+            ..default()
+        },
+        Transform::from_xyz(0.0, BAR_Y, 1.0),
+        LoadingScreen,
+    ));
+    commands.spawn((
+        Sprite {
+            color: Color::WHITE,
+            custom_size: Some(Vec2::new(0.0, BAR_SIZE.y)),
+            ..default()
+        },
+        Transform::from_xyz(-BAR_SIZE.x / 2.0, BAR_Y, 2.0),
+        LoadingScreen,
+        LoadingBarFill,
+    ));
+}
+
+// A failed load counts as done, so a missing file is logged rather than holding up the
+// game forever.
+fn track_loading(
+    asset_server: Res<AssetServer>,
+    preloaded: Res<Preloaded>,
+    voice_lines: Res<VoiceLines>,
+    sfx_sounds: Res<SfxSounds>,
+    mut fill_query: Query<(&mut Sprite, &mut Transform), With<LoadingBarFill>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let ids: Vec<UntypedAssetId> = preloaded
+        .0
+        .iter()
+        .map(|handle| handle.id())
+        .chain(voice_lines.ids())
+        .chain(sfx_sounds.ids())
+        .collect();
+    let done = ids
+        .iter()
+        .filter(|id| {
+            asset_server.is_loaded_with_dependencies(**id)
+                || matches!(asset_server.load_state(**id), LoadState::Failed(_))
+        })
+        .count();
+    let progress = if ids.is_empty() { 1.0 } else { done as f32 / ids.len() as f32 };
+
+    for (mut sprite, mut transform) in &mut fill_query {
+        let width = BAR_SIZE.x * progress;
+        sprite.custom_size = Some(Vec2::new(width, BAR_SIZE.y));
+        transform.translation.x = -BAR_SIZE.x / 2.0 + width / 2.0;
+    }
+    if done == ids.len() {
+        next_state.set(GameState::Splash);
+    }
+}
+
+fn despawn_loading_screen(mut commands: Commands, screen_query: Query<Entity, With<LoadingScreen>>) {
+    for entity in &screen_query {
+        commands.entity(entity).despawn();
+    }
+}
@@ -1,6 +1,141 @@
 use bevy::prelude::*;
+use bevy::app::RunFixedMainLoopSystems;
 use bevy::input::ButtonInput;
+use bevy::math::bounding::{Aabb2d, BoundingVolume, IntersectsVolume};
 use bevy::input::keyboard::Key;
+use bevy::input::touch::Touches;
+use clap::Parser;
+
+mod accessibility;
+mod announcer;
+mod block_mesh;
+mod bullet_time;
+mod cheats;
+mod cli;
+mod clip;
+mod collision;
+mod config;
+mod controls;
+mod credits;
+mod crumbling_walls;
+mod debris;
+mod debug;
+mod diagnostics;
+#[cfg(feature = "dev-tools")]
+mod dev_tools;
+mod display;
+mod dual_paddle;
+mod force_zone;
+mod game_over;
+mod ghost;
+mod hazard;
+mod highscores;
+mod hot_reload;
+mod hud;
+mod idle;
+mod interpolation;
+mod leaderboard;
+mod letters;
+mod level;
+mod loading;
+mod menu;
+mod mods;
+mod multiball;
+mod mutators;
+mod new_game_plus;
+mod obstacle;
+mod pause;
+mod photo_mode;
+#[cfg(feature = "rapier")]
+mod physics;
+mod pool;
+mod portal;
+mod progression;
+mod projectile;
+mod replay;
+mod result_card;
+mod rumble;
+mod scoring;
+mod screenshot;
+mod scripting;
+mod seed;
+mod serve;
+mod settings;
+mod sfx;
+mod shield;
+mod shrinking_paddle;
+mod skins;
+mod snapshot;
+mod speedrun;
+mod split_paddle;
+mod stars;
+mod stats;
+mod touch;
+mod trajectory;
+mod tutorial;
+mod visual_feedback;
+
+use accessibility::AccessibilityPlugin;
+use announcer::{Announce, AnnouncerPlugin, VoiceLine};
+use block_mesh::BlockMeshPlugin;
+use bullet_time::{BulletTime, BulletTimePlugin};
+use cheats::CheatsPlugin;
+use cli::LaunchOptions;
+use clip::ClipPlugin;
+use collision::{BallHitBlock, BallHitPaddle, BallHitWall, Side};
+use config::{ConfigPlugin, GameConfig};
+use controls::{ControlsPlugin, GameplayInput, PlayerId};
+use credits::CreditsPlugin;
+use crumbling_walls::{CrumblingWallsPlugin, WallHoles};
+use debris::DebrisPlugin;
+use debug::DebugPlugin;
+use diagnostics::GameDiagnosticsPlugin;
+use display::{DisplayPlugin, GameCamera, ARENA};
+use dual_paddle::{DualPaddlePlugin, TopPaddle};
+use force_zone::ForceZonePlugin;
+use game_over::GameOverPlugin;
+use ghost::GhostPlugin;
+use hazard::{Hazard, HazardPlugin};
+use highscores::HighScoresPlugin;
+use hot_reload::HotReloadPlugin;
+use hud::HudPlugin;
+use idle::IdlePlugin;
+use interpolation::{Interpolated, InterpolationPlugin};
+use leaderboard::LeaderboardPlugin;
+use letters::{Letter, LettersPlugin};
+use level::{CurrentLevel, LevelClock, LevelPlugin, Levels};
+use loading::LoadingPlugin;
+use menu::{MenuActivated, MenuItem, MenuPlugin};
+use mods::{ModRegistry, ModsPlugin};
+use multiball::MultiballPlugin;
+use mutators::Mutator;
+use new_game_plus::{NewGamePlus, NewGamePlusPlugin};
+use obstacle::ObstaclePlugin;
+use pause::PausePlugin;
+use photo_mode::PhotoModePlugin;
+use portal::PortalPlugin;
+use progression::ProgressionPlugin;
+use projectile::ProjectilePlugin;
+use replay::ReplayPlugin;
+use result_card::ResultCardPlugin;
+use rumble::RumblePlugin;
+use scoring::{BlockScored, Combo, ComboChanged, GameScore, ScoringPlugin};
+use screenshot::ScreenshotPlugin;
+use scripting::ScriptingPlugin;
+use seed::SeedPlugin;
+use serve::{ServeDeal, ServePlugin};
+use settings::Settings;
+use sfx::SfxPlugin;
+use shield::Shield;
+use shrinking_paddle::ShrinkingPaddlePlugin;
+use skins::SkinsPlugin;
+use snapshot::SnapshotPlugin;
+use speedrun::SpeedrunPlugin;
+use split_paddle::{SplitPaddle, SplitPaddlePlugin};
+use stars::StarsPlugin;
+use stats::{RunStats, StatsPlugin};
+use tutorial::TutorialPlugin;
+use visual_feedback::VisualFeedbackPlugin;
 
 const WINDOW_WIDTH: f32 = 1280.0;
 const WINDOW_HEIGHT: f32 = 720.0;
@@ -9,47 +144,169 @@ const PADDLE_HEIGHT: f32 = 20.0;
 const PADDLE_WIDTH: f32 = 100.0;
 const PADDLE_MARGIN: f32 = 30.0;
 
+const BLOCK_SIZE: Vec2 = Vec2::new(75.0, 20.0);
+
 const BALL_SIZE: f32 = 46.0;
 const BALL_COLLISION_MARGIN: f32 = 10.0;
 const BALL_START_SPEED: f32 = 200.0;
 const BALL_SPEED_MAX: f32 = 1000.0;
+/// Rate, in steps per second, of the fixed timestep the ball is simulated on.
+const SIMULATION_HZ: f64 = 120.0;
+/// Bounces resolved per frame before the ball gives up on the rest of its move.
+#[cfg(not(feature = "rapier"))]
+const MAX_SWEEP_STEPS: usize = 4;
+
+/// Spin picked up per unit of paddle speed at contact.
+const SPIN_PER_PADDLE_SPEED: f32 = 0.0014;
+const SPIN_MAX: f32 = 1.0;
+/// Fraction of spin lost per second.
+const SPIN_DECAY: f32 = 1.5;
+/// Seconds of spin applied as a one-off turn when the ball bounces off a wall.
+const SPIN_WALL_BIAS: f32 = 0.25;
+/// How much harder spin bends the ball's path on curveball levels.
+const CURVEBALL_STRENGTH: f32 = 2.5;
+
+/// Steepest angle, in radians from straight up, the ball leaves the paddle at.
+const PADDLE_BOUNCE_ANGLE_MAX: f32 = std::f32::consts::FRAC_PI_3;
+/// Fraction of the paddle's velocity added to the ball's when they meet.
+const PADDLE_MOMENTUM_TRANSFER: f32 = 0.3;
+
+// Serve aim, in radians from straight up.
+const SERVE_AIM_MAX: f32 = std::f32::consts::FRAC_PI_3;
+const SERVE_AIM_SPEED: f32 = 1.5;
+const SERVE_PREVIEW_LENGTH: f32 = 700.0;
+
+/// Paddle speed under the keys and D-pad, in units per second.
+const PADDLE_SPEED: f32 = 720.0;
+/// How far above its row the paddle can rise with advanced controls, and how fast.
+const PADDLE_LIFT_MAX: f32 = 60.0;
+const PADDLE_LIFT_SPEED: f32 = 240.0;
+/// Fastest the paddle can chase the cursor in mouse mode, in units per second.
+const MOUSE_PADDLE_MAX_SPEED: f32 = 1800.0;
+
+const SIDE_WALL_THICKNESS: f32 = 20.0;
+
+// Bump energy, as fractions of a full meter.
+const BUMP_ENERGY_COST: f32 = 0.35;
+const BUMP_ENERGY_REGEN: f32 = 0.12;
+const BUMP_ENERGY_PER_BLOCK: f32 = 0.05;
+/// How long a bump press is held waiting for the ball, in seconds.
+const BUMP_BUFFER_TIME: f32 = 0.1;
 
-const PADDLE_SPEED: f32 = 12.0;
+// Menu buttons are sized generously so they double as touch targets.
+const MENU_BUTTON_SIZE: Vec2 = Vec2::new(420.0, 120.0);
+const MENU_BUTTON_Y: f32 = -100.0;
 
-#[derive(States, Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+const DIFFICULTY_TEXT_Y: f32 = -200.0;
+const DIFFICULTY_TEXT_SIZE: Vec2 = Vec2::new(360.0, 60.0);
+
+#[derive(States, Reflect, Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
 enum GameState {
     #[default]
+    Loading,
     Splash,
     Playing,
+    LevelComplete,
     GameWon,
+    GameOver,
+    Replays,
+    Leaderboards,
+    Credits,
 }
 
 #[derive(Component)]
 struct SplashScreen;
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 struct StartButton;
 
 #[derive(Component)]
+struct DifficultyText;
+
+#[derive(Component, Clone)]
 struct WinScreen;
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 struct RestartButton;
 
+/// Everything spawned for a level, cleared out together when the next one starts.
+#[derive(Component)]
+struct GameEntity;
+
 #[derive(Component)]
 struct Paddle;
 
+/// Axis-aligned collision box centered on the entity's translation.
+#[derive(Component)]
+struct Collider {
+    size: Vec2,
+}
+
+impl Collider {
+    /// The box this collider covers when centered on `center`.
+    fn aabb(&self, center: Vec2) -> Aabb2d {
+        Aabb2d::new(center, self.size / 2.0)
+    }
+}
+
 #[derive(Component)]
 struct Ball;
 
 #[derive(Component)]
 struct Velocity(Vec2);
 
+/// How fast the paddle moved last frame, in world units per second.
+#[derive(Component, Default)]
+struct PaddleVelocity(f32);
+
+/// Turn rate of the ball's path in radians per second, put on it by a moving paddle.
+/// It fades over time and skews the next wall bounces.
+#[derive(Component, Default)]
+struct Spin(f32);
+
+/// Holds the ball on top of the paddle until the player launches it.
+#[derive(Component)]
+struct Serving {
+    /// Launch angle in radians from straight up; positive aims right.
+    aim: f32,
+}
+
 #[derive(Component)]
 struct Block;
 
+/// Hits left before a block breaks.
+#[derive(Component)]
+struct BlockHealth(u32);
+
+/// Points a block is worth before the speed bonus and combo; see `LevelData::row_points`.
 #[derive(Component)]
-struct Score;
+struct BlockPoints(u32);
+
+/// Vertical arena boundary; `side` is -1.0 for the left wall and 1.0 for the right.
+/// The ball and paddle are bounded by these entities' positions, so levels can move them.
+#[derive(Component)]
+struct SideWall {
+    side: f32,
+}
+
+/// Multipliers on the width the paddle gets from the config and settings, combined into
+/// its collider and sprite by `resize_paddle`.
+#[derive(Component, PartialEq)]
+struct PaddleWidth {
+    /// Narrowed over the course of a level by the shrinking-paddle mutator.
+    narrowing: f32,
+    /// Briefly cut by projectile hits.
+    shrink: f32,
+}
+
+impl Default for PaddleWidth {
+    fn default() -> Self {
+        PaddleWidth {
+            narrowing: 1.0,
+            shrink: 1.0,
+        }
+    }
+}
 
 #[derive(Component)]
 struct PaddleBounce {
@@ -58,46 +315,208 @@ struct PaddleBounce {
     is_bouncing: bool,
 }
 
-#[derive(Resource)]
-struct GameScore(u32);
+/// Systems that move the ball into whatever it hits and send the hit messages.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+struct BallCollision;
 
-#[derive(Component)]
-struct BallBlockCooldown(f32);
+/// Sent when the ball escapes the arena and goes back on the paddle to be served again.
+#[derive(Message)]
+struct BallLost;
+
+/// Sent when a bump press spends energy, whether or not it connects.
+#[derive(Message)]
+struct Bumped;
+
+/// Sent when a block breaks, with where it stood.
+#[derive(Message)]
+struct BlockDestroyed {
+    position: Vec2,
+    hazard: bool,
+    letter: Option<char>,
+}
+
+/// Meter the bump draws from, between 0.0 (empty) and 1.0 (full).
+#[derive(Resource, PartialEq)]
+struct BumpEnergy(f32);
+
+impl Default for BumpEnergy {
+    fn default() -> Self {
+        BumpEnergy(1.0)
+    }
+}
+
+/// Finger drag, in world units, not yet applied to the paddle. Touches update once a
+/// frame, so their drag is gathered here for however many fixed steps follow.
+#[derive(Resource, Default)]
+struct PendingDrag(f32);
+
+/// The latest bump press, which keeps trying to connect for a short while so bumping
+/// slightly before the ball reaches the paddle still counts.
+#[derive(Resource, Default)]
+struct BumpBuffer {
+    /// Seconds left before the press stops trying to connect.
+    time: f32,
+    /// Pressed with a full meter, so it splits the ball instead of speeding it up.
+    charged: bool,
+}
 
 fn main() {
     std::env::set_var("RUST_LOG", "error");
-    
-    App::new()
-        .insert_resource(ClearColor(Color::srgb(0.13, 0.1, 0.2)))
-        .insert_resource(GameScore(0))
-        .add_plugins(DefaultPlugins)
-        .insert_state(GameState::Splash)
+
+    let options = LaunchOptions::parse();
+    let mut settings = Settings::load();
+    options.apply_to(&mut settings);
+    let window = display::primary_window(&settings);
+
+    let mut app = App::new();
+    app.insert_resource(ClearColor(accessibility::STANDARD_BACKGROUND))
+        .insert_resource(settings)
+        .insert_resource(options.starting_level())
+        .insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+        .add_plugins(ModsPlugin)
+        // Closing the window mid-run asks first; see `pause`.
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(window),
+            close_when_requested: false,
+            ..default()
+        }))
+        .add_plugins(ConfigPlugin)
+        .add_plugins(DisplayPlugin)
+        .add_plugins(ScoringPlugin)
+        .add_plugins(LevelPlugin)
+        .add_plugins(LeaderboardPlugin)
+        .add_plugins(LoadingPlugin)
+        .add_plugins(BlockMeshPlugin)
+        .add_plugins(ObstaclePlugin)
+        .add_plugins(PortalPlugin)
+        .add_plugins(ForceZonePlugin)
+        .add_plugins(ScriptingPlugin)
+        .add_plugins(SfxPlugin)
+        .add_plugins(ServePlugin)
+        .add_plugins(SeedPlugin)
+        .add_plugins(SpeedrunPlugin)
+        .add_plugins(HudPlugin)
+        .add_plugins(IdlePlugin)
+        .add_plugins(BulletTimePlugin)
+        .add_plugins(HighScoresPlugin)
+        .add_plugins(HotReloadPlugin)
+        .add_plugins(DebugPlugin)
+        .add_plugins(CheatsPlugin)
+        .add_plugins(ScreenshotPlugin)
+        .add_plugins(SnapshotPlugin)
+        .add_plugins(ClipPlugin)
+        .add_plugins(AccessibilityPlugin)
+        .add_plugins(MenuPlugin)
+        .add_plugins(ControlsPlugin)
+        .add_plugins(ReplayPlugin)
+        .add_plugins(ResultCardPlugin)
+        .add_plugins(GhostPlugin)
+        .add_plugins(AnnouncerPlugin)
+        .add_plugins(SkinsPlugin)
+        .add_plugins(StatsPlugin)
+        .add_plugins(GameOverPlugin)
+        .add_plugins(NewGamePlusPlugin)
+        .add_plugins(StarsPlugin)
+        .add_plugins(ProgressionPlugin)
+        .add_plugins(TutorialPlugin)
+        .add_plugins(CreditsPlugin)
+        .add_plugins(ProjectilePlugin)
+        .add_plugins(HazardPlugin)
+        .add_plugins(LettersPlugin)
+        .add_plugins(DebrisPlugin)
+        .add_plugins(VisualFeedbackPlugin)
+        .add_plugins(MultiballPlugin)
+        .add_plugins(SplitPaddlePlugin)
+        .add_plugins(ShrinkingPaddlePlugin)
+        .add_plugins(DualPaddlePlugin)
+        .add_plugins(CrumblingWallsPlugin)
+        .add_plugins(PausePlugin)
+        .add_plugins(PhotoModePlugin)
+        .add_plugins(RumblePlugin)
+        .add_plugins(InterpolationPlugin)
+        .add_plugins(GameDiagnosticsPlugin)
+        .init_resource::<BumpEnergy>()
+        .init_resource::<BumpBuffer>()
+        .init_resource::<PendingDrag>()
+        .add_message::<BallHitPaddle>()
+        .add_message::<BallHitWall>()
+        .add_message::<BallHitBlock>()
+        .add_message::<BallLost>()
+        .add_message::<Bumped>()
+        .add_message::<BlockDestroyed>()
+        .insert_resource(options)
+        .insert_state(GameState::Loading)
         .add_systems(OnEnter(GameState::Splash), setup_splash)
-        .add_systems(Update, start_button.run_if(in_state(GameState::Splash)))
+        .add_systems(
+            Update,
+            (select_difficulty, start_button, skip_splash)
+                .run_if(in_state(GameState::Splash).and(seed::not_entering_seed)),
+        )
         .add_systems(OnEnter(GameState::Playing), setup_game)
         .add_systems(
             Update,
             (
-                paddle_movement_system,
-                ball_movement,
-                ball_collision_system,
+                move_side_walls,
+                resize_paddle,
+                serve_aim_system,
+                draw_aim_preview.after(serve_aim_system),
                 check_win_condition,
                 ball_bump_system,
-                ball_bounds_check,
-            ).run_if(in_state(GameState::Playing)),
+                regenerate_bump_energy,
+                launch_serve.after(ball_bump_system),
+            )
+                .run_if(in_state(GameState::Playing).and(debug::simulation_running).and(pause::not_paused)),
+        )
+        .add_systems(
+            RunFixedMainLoop,
+            gather_touch_drag
+                .in_set(RunFixedMainLoopSystems::BeforeFixedMainLoop)
+                .run_if(in_state(GameState::Playing).and(debug::simulation_running).and(pause::not_paused)),
+        )
+        // The ball and paddle are simulated on a fixed timestep so they behave the same at
+        // any frame rate.
+        .add_systems(
+            FixedUpdate,
+            (
+                paddle_movement_system.before(BallCollision),
+                damage_blocks.after(BallCollision),
+                apply_hit_speed.after(BallCollision),
+                bump_connect.after(apply_hit_speed),
+                ball_bounds_check.after(bump_connect),
+            )
+                .run_if(in_state(GameState::Playing).and(debug::simulation_running).and(pause::not_paused)),
         )
         .add_systems(OnEnter(GameState::GameWon), (clear_game_camera, setup_win_screen))
-        .add_systems(Update, restart_button.run_if(in_state(GameState::GameWon)))
-        .run();
+        .add_systems(Update, restart_button.run_if(in_state(GameState::GameWon)));
+
+    #[cfg(not(feature = "rapier"))]
+    app.init_resource::<collision::BlockGrid>().add_systems(
+        FixedUpdate,
+        (index_blocks, ball_collision_system.in_set(BallCollision))
+            .chain()
+            .run_if(in_state(GameState::Playing).and(debug::simulation_running).and(pause::not_paused)),
+    );
+    #[cfg(feature = "rapier")]
+    app.add_plugins(physics::RapierBackendPlugin);
+
+    #[cfg(feature = "dev-tools")]
+    app.add_plugins(dev_tools::DevToolsPlugin);
+
+    app.run();
 }
 
 // Barney
-fn setup_splash(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn((Camera2d, IsDefaultUiCamera));
+fn setup_splash(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    registry: Res<ModRegistry>,
+    settings: Res<Settings>,
+) {
+    display::spawn_game_camera(&mut commands);
 
     commands.spawn((
         Sprite {
-            image: asset_server.load("splash.png"),
+            image: asset_server.load(registry.asset_path("splash.png")),
             custom_size: Some(Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT)),
             ..default()
         },
@@ -105,290 +524,799 @@ fn setup_splash(mut commands: Commands, asset_server: Res<AssetServer>) {
         SplashScreen,
     ));
 
+    spawn_menu_button(&mut commands, "Press Spacebar or Tap to Start", StartButton, 1.0);
+
+    commands.spawn((
+        Text2d(difficulty_label(&settings)),
+        Transform::from_xyz(0.0, DIFFICULTY_TEXT_Y, 2.0),
+        MenuItem {
+            order: 1,
+            size: DIFFICULTY_TEXT_SIZE,
+        },
+        DifficultyText,
+        StartButton,
+    ));
+}
+
+fn difficulty_label(settings: &Settings) -> String {
+    format!("< Difficulty: {} >", settings.difficulty.name())
+}
+
+fn select_difficulty(
+    input: Res<ButtonInput<Key>>,
+    touches: Res<Touches>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    gamepads: Query<&Gamepad>,
+    mut activated: MessageReader<MenuActivated>,
+    mut settings: ResMut<Settings>,
+    mut text_query: Query<&mut Text2d, With<DifficultyText>>,
+) {
+    let confirmed = activated
+        .read()
+        .any(|MenuActivated(entity)| text_query.contains(*entity));
+    let tapped = camera_query.single().is_ok_and(|(camera, camera_transform)| {
+        touch::tapped_within(
+            &touches,
+            camera,
+            camera_transform,
+            Vec2::new(0.0, DIFFICULTY_TEXT_Y),
+            DIFFICULTY_TEXT_SIZE,
+        )
+    });
+    let direction = if input.just_pressed(Key::ArrowLeft)
+        || input.just_pressed(Key::Character("a".into()))
+        || menu::gamepad_just_pressed(&gamepads, GamepadButton::DPadLeft)
+    {
+        Some(settings.difficulty.previous())
+    } else if tapped
+        || confirmed
+        || menu::gamepad_just_pressed(&gamepads, GamepadButton::DPadRight)
+        || input.just_pressed(Key::ArrowRight)
+        || input.just_pressed(Key::Character("d".into()))
+    {
+        Some(settings.difficulty.next())
+    } else {
+        None
+    };
+
+    if let Some(difficulty) = direction {
+        settings.difficulty = difficulty;
+        settings.save();
+        for mut text in &mut text_query {
+            text.0 = difficulty_label(&settings);
+        }
+    }
+}
+
+/// Spawns the standard menu button (a sprite with a label on top) at `z`.
+fn spawn_menu_button<M: Component + Clone>(commands: &mut Commands, label: &str, marker: M, z: f32) {
     commands.spawn((
         Sprite {
             color: Color::srgb(0.25, 0.25, 0.85),
-            custom_size: Some(Vec2::new(300.0, 100.0)),
+            custom_size: Some(MENU_BUTTON_SIZE),
             ..default()
         },
-        Transform::from_xyz(0.0, -100.0, 1.0),
-        StartButton,
+        Transform::from_xyz(0.0, MENU_BUTTON_Y, z),
+        MenuItem {
+            order: 0,
+            size: MENU_BUTTON_SIZE,
+        },
+        marker.clone(),
     ));
 
     commands.spawn((
-        Text2d("Press Spacebar to Start".to_string()),
-        Transform::from_xyz(0.0, -100.0, 2.0),
-        StartButton,
+        Text2d(label.to_string()),
+        Transform::from_xyz(0.0, MENU_BUTTON_Y, z + 1.0),
+        marker,
     ));
 }
 
+fn menu_button_tapped(
+    touches: &Touches,
+    camera_query: &Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+) -> bool {
+    camera_query.single().is_ok_and(|(camera, camera_transform)| {
+        touch::tapped_within(
+            touches,
+            camera,
+            camera_transform,
+            Vec2::new(0.0, MENU_BUTTON_Y),
+            MENU_BUTTON_SIZE,
+        )
+    })
+}
+
 fn start_button(
     input: Res<ButtonInput<Key>>,
+    touches: Res<Touches>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
     mut next_state: ResMut<NextState<GameState>>,
     mut commands: Commands,
-    splash_query: Query<Entity, With<SplashScreen>>,
-    button_query: Query<Entity, With<StartButton>>,
+    splash_query: Query<Entity, Or<(With<SplashScreen>, With<StartButton>)>>,
+    mut activated: MessageReader<MenuActivated>,
+    start_item_query: Query<(), (With<StartButton>, With<MenuItem>, Without<DifficultyText>)>,
 ) {
-    if input.just_pressed(Key::Space) {
-        for entity in &splash_query {
-            commands.entity(entity).despawn();
-        }
-        for entity in &button_query {
-            commands.entity(entity).despawn();
+    let confirmed = activated
+        .read()
+        .any(|MenuActivated(entity)| start_item_query.contains(*entity));
+    if input.just_pressed(Key::Space) || confirmed || menu_button_tapped(&touches, &camera_query) {
+        leave_splash(&mut commands, &splash_query, &mut next_state);
+    }
+}
+
+// Launching with `--level` starts the game straight away, once; coming back to the
+// splash screen later shows it as usual.
+fn skip_splash(
+    mut options: ResMut<LaunchOptions>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+    splash_query: Query<Entity, Or<(With<SplashScreen>, With<StartButton>)>>,
+) {
+    if options.level.take().is_some() {
+        leave_splash(&mut commands, &splash_query, &mut next_state);
+    }
+}
+
+fn leave_splash(
+    commands: &mut Commands,
+    splash_query: &Query<Entity, Or<(With<SplashScreen>, With<StartButton>)>>,
+    next_state: &mut NextState<GameState>,
+) {
+    for entity in splash_query {
+        commands.entity(entity).despawn();
+    }
+    next_state.set(GameState::Playing);
+}
+
+// Drawn by the shared block mesh rather than a sprite each.
+fn spawn_block(commands: &mut Commands, position: Vec2, health: u32, points: u32) -> Entity {
+    commands
+        .spawn((
+            Transform::from_translation(position.extend(0.0)),
+            Block,
+            Collider { size: BLOCK_SIZE },
+            BlockHealth(health),
+            BlockPoints(points),
+            GameEntity,
+        ))
+        .id()
+}
+
+// Only writes when the size actually changes, since the rapier backend rebuilds the
+// paddle's body whenever its collider does.
+fn resize_paddle(
+    config: Res<GameConfig>,
+    settings: Res<Settings>,
+    mut paddle_query: Query<(&PaddleWidth, &mut Sprite, &mut Collider, Has<SplitPaddle>), With<Paddle>>,
+) {
+    for (width, mut sprite, mut collider, split) in &mut paddle_query {
+        let full_width = config.paddle_width * settings.paddle_scale * width.narrowing * width.shrink;
+        let size = Vec2::new(split_paddle::span(full_width, split), PADDLE_HEIGHT);
+        if collider.size != size {
+            collider.size = size;
+            sprite.custom_size = Some(size);
         }
-        next_state.set(GameState::Playing);
     }
 }
 
-fn setup_game(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn((
+fn paddle_row_y() -> f32 {
+    -ARENA.half_height() + PADDLE_MARGIN + PADDLE_HEIGHT / 2.0 + 100.0
+}
+
+fn setup_game(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    registry: Res<ModRegistry>,
+    config: Res<GameConfig>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+    score: Res<GameScore>,
+    settings: Res<Settings>,
+    mut serve_deal: ResMut<ServeDeal>,
+) {
+    let paddle_y = paddle_row_y();
+    let split = settings.mutator(Mutator::SplitPaddle);
+    let paddle_size = Vec2::new(
+        split_paddle::span(config.paddle_width * settings.paddle_scale, split),
+        PADDLE_HEIGHT,
+    );
+    let paddle = commands.spawn((
         Sprite {
-            color: Color::WHITE,
-            custom_size: Some(Vec2::new(PADDLE_WIDTH, PADDLE_HEIGHT)),
+            color: if split { Color::NONE } else { Color::WHITE },
+            custom_size: Some(paddle_size),
             ..Default::default()
         },
-        Transform::from_xyz(0.0, -WINDOW_HEIGHT / 2.0 + PADDLE_MARGIN + PADDLE_HEIGHT / 2.0 + 100.0, 0.0),
+        Transform::from_xyz(0.0, paddle_y, 0.0),
         Paddle,
+        PlayerId(0),
+        PaddleVelocity::default(),
+        PaddleWidth::default(),
+        Collider { size: paddle_size },
         PaddleBounce {
-            original_y: -WINDOW_HEIGHT / 2.0 + PADDLE_MARGIN + PADDLE_HEIGHT / 2.0 + 100.0,
+            original_y: paddle_y,
             bounce_timer: 0.0,
             is_bouncing: false,
         },
-    ));
+        Interpolated::default(),
+        GameEntity,
+    )).id();
+    if split {
+        split_paddle::split(&mut commands, paddle);
+    }
 
+    let ball_size = BALL_SIZE * settings.ball_scale;
     commands.spawn((
-        Sprite {
-            image: asset_server.load("ferris.png"),
-            custom_size: Some(Vec2::splat(BALL_SIZE)),
-            ..default()
-        },
-        Transform::from_xyz(0.0, 0.0, 1.0),
+        settings.ball_skin.sprite(&asset_server, &registry, Vec2::splat(ball_size)),
+        Transform::from_xyz(0.0, paddle_y + PADDLE_HEIGHT / 2.0 + ball_size / 2.0, 1.0),
         Ball,
-        Velocity(Vec2::new(BALL_START_SPEED, BALL_START_SPEED)),
-        BallBlockCooldown(0.0),
+        Collider {
+            size: Vec2::splat(ball_size),
+        },
+        Velocity(Vec2::ZERO),
+        Spin::default(),
+        Interpolated::default(),
+        serve_deal.next(),
+        GameEntity,
     ));
 
-    let block_width = 80.0;
-    let block_height = 20.0;
-    let blocks_per_row = (WINDOW_WIDTH / block_width) as i32;
-    let start_x = -(blocks_per_row as f32 * block_width) / 2.0 + block_width / 2.0;
+    let block_width = BLOCK_SIZE.x + 5.0;
+    let block_height = BLOCK_SIZE.y;
+    let level = levels.get(current_level.0);
+    let dual = settings.mutator(Mutator::DualPaddle);
+    // With a paddle at each end the blocks sit in the middle instead of at the top.
+    let top_y = if dual {
+        level.rows.len().saturating_sub(1) as f32 * (block_height + 10.0) / 2.0
+    } else {
+        WINDOW_HEIGHT / 2.0 - 50.0
+    };
     
-    for layer in 0..4 {
-        let y_pos = WINDOW_HEIGHT / 2.0 - 50.0 - (layer as f32 * (block_height + 10.0));
-        for i in 0..blocks_per_row {
+    for (layer, row) in level.rows.iter().enumerate() {
+        let blocks_per_row = row.chars().count();
+        let start_x = -(blocks_per_row as f32 * block_width) / 2.0 + block_width / 2.0;
+        let y_pos = top_y - (layer as f32 * (block_height + 10.0));
+        let points = level.points_for_row(layer);
+        for (i, cell) in row.chars().enumerate() {
+            let shield = Shield::from_cell(cell);
+            let letter = cell.is_ascii_alphabetic();
+            if cell != '#' && cell != '!' && shield.is_none() && !letter {
+                continue;
+            }
             let x_pos = start_x + (i as f32 * block_width);
-            commands.spawn((
-                Sprite {
-                    color: Color::srgb(0.8, 0.2, 0.2),
-                    custom_size: Some(Vec2::new(block_width - 5.0, block_height)),
-                    ..default()
-                },
-                Transform::from_xyz(x_pos, y_pos, 0.0),
-                Block,
-            ));
+            let block = spawn_block(&mut commands, Vec2::new(x_pos, y_pos), config.block_hp, points);
+            if cell == '!' {
+                commands.entity(block).insert(Hazard);
+            }
+            if let Some(shield) = shield {
+                commands.entity(block).insert(shield);
+            }
+            if letter {
+                letters::spawn_letter(&mut commands, block, cell);
+            }
         }
     }
 
-    commands.spawn((
-        Text2d("Score: 0".to_string()),
-        Transform::from_xyz(-WINDOW_WIDTH / 2.0 + 100.0, WINDOW_HEIGHT / 2.0 - 50.0, 2.0),
-        Score,
-    ));
+    scoring::spawn_score_hud(&mut commands, &score);
+    hud::spawn_speed_gauge(&mut commands);
+    hud::spawn_energy_gauge(&mut commands);
+    hud::spawn_focus_gauge(&mut commands);
 
-    // Walls
-    for (y_pos, z) in [(-WINDOW_HEIGHT / 2.0 + 10.0, 0.0), (WINDOW_HEIGHT / 2.0 - 10.0, 0.0)] {
+    // Walls. The top one is left out when the ceiling is a lose zone.
+    for side in [-1.0, 1.0] {
+        if dual && side > 0.0 {
+            continue;
+        }
         commands.spawn((
             Sprite {
                 color: Color::WHITE,
-                custom_size: Some(Vec2::new(WINDOW_WIDTH, 20.0)),
+                custom_size: Some(Vec2::new(ARENA.width, 20.0)),
                 ..default()
             },
-            Transform::from_xyz(0.0, y_pos, z),
+            Transform::from_xyz(0.0, side * (ARENA.half_height() - 10.0), 0.0),
+            Collider {
+                size: Vec2::new(ARENA.width, 20.0),
+            },
+            GameEntity,
         ));
     }
+
+    for side in [-1.0, 1.0] {
+        commands.spawn((
+            Sprite {
+                color: Color::WHITE,
+                custom_size: Some(Vec2::new(SIDE_WALL_THICKNESS, ARENA.height)),
+                ..default()
+            },
+            Transform::from_xyz(side * (ARENA.half_width() + SIDE_WALL_THICKNESS / 2.0), 0.0, 0.0),
+            SideWall { side },
+            Collider {
+                size: Vec2::new(SIDE_WALL_THICKNESS, ARENA.height),
+            },
+            GameEntity,
+        ));
+    }
+}
+
+/// Inner edges (left, right) of the side walls, or the arena bounds if there are none.
+fn side_bounds<'a>(walls: impl Iterator<Item = (&'a Transform, &'a SideWall)>) -> (f32, f32) {
+    let (mut left, mut right) = (-ARENA.half_width(), ARENA.half_width());
+    for (transform, wall) in walls {
+        let inner_edge = transform.translation.x - wall.side * SIDE_WALL_THICKNESS / 2.0;
+        if wall.side < 0.0 {
+            left = inner_edge;
+        } else {
+            right = inner_edge;
+        }
+    }
+    (left, right)
+}
+
+// Side walls sit just outside the arena; levels with moving walls slide them inward
+// and back out again over each cycle.
+fn move_side_walls(
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+    clock: Res<LevelClock>,
+    mut wall_query: Query<(&mut Transform, &SideWall)>,
+) {
+    let inset = levels
+        .get(current_level.0)
+        .moving_walls
+        .as_ref()
+        .map_or(0.0, |walls| walls.inset_at(clock.elapsed));
+    for (mut transform, wall) in &mut wall_query {
+        transform.translation.x = wall.side * (ARENA.half_width() - inset + SIDE_WALL_THICKNESS / 2.0);
+    }
+}
+
+fn gather_touch_drag(
+    touches: Res<Touches>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    mut drag: ResMut<PendingDrag>,
+) {
+    drag.0 += camera_query
+        .single()
+        .map_or(0.0, |(camera, camera_transform)| touch::drag_x(&touches, camera, camera_transform));
 }
 
 fn paddle_movement_system(
-    input: Res<ButtonInput<Key>>,
-    mut query: Query<&mut Transform, With<Paddle>>,
+    controls: GameplayInput,
+    time: Res<Time>,
+    mut drag: ResMut<PendingDrag>,
+    settings: Res<Settings>,
+    wall_query: Query<(&Transform, &SideWall), Without<Paddle>>,
+    mut query: Query<
+        (&mut Transform, &mut PaddleVelocity, &mut PaddleBounce, &Collider, &PlayerId),
+        With<Paddle>,
+    >,
 ) {
-    for mut transform in query.iter_mut() {
-        let mut direction = 0.0;
-        if input.pressed(Key::Character("a".into())) || input.pressed(Key::ArrowLeft) {
-            direction -= 1.0;
-        }
-        if input.pressed(Key::Character("d".into())) || input.pressed(Key::ArrowRight) {
-            direction += 1.0;
+    let _span = info_span!("paddle_movement").entered();
+    let (left, right) = side_bounds(wall_query.iter());
+    let row_y = paddle_row_y();
+    let lift_max = if settings.advanced_controls { PADDLE_LIFT_MAX } else { 0.0 };
+    let touch_drag = std::mem::take(&mut drag.0);
+
+    for (mut transform, mut paddle_velocity, mut bounce, paddle_collider, player) in query.iter_mut() {
+        // The resting height moves rather than the paddle itself, so a bump in progress
+        // rises from and settles back to wherever the paddle is.
+        let lift = controls.vertical(*player) * PADDLE_LIFT_SPEED * time.delta_secs();
+        let rest_y = (bounce.original_y + lift).clamp(row_y, row_y + lift_max);
+        transform.translation.y += rest_y - bounce.original_y;
+        bounce.original_y = rest_y;
+
+        let previous_x = transform.translation.x;
+        let direction = controls.direction(*player);
+        transform.translation.x += direction * PADDLE_SPEED * time.delta_secs();
+        // In mouse mode the paddle chases the cursor, but no faster than a hand could
+        // plausibly swing it, so flicking the mouse can't teleport it under the ball.
+        if let Some(target_x) = controls.mouse_target_x() {
+            let max_step = MOUSE_PADDLE_MAX_SPEED * time.delta_secs();
+            transform.translation.x += (target_x - transform.translation.x).clamp(-max_step, max_step);
         }
-        transform.translation.x += direction * PADDLE_SPEED;
+        // Dragging a finger moves the paddle one-to-one with the touch.
+        transform.translation.x += touch_drag;
         transform.translation.x = transform
             .translation
             .x
             .clamp(
-                -WINDOW_WIDTH / 2.0 + PADDLE_WIDTH / 2.0,
-                WINDOW_WIDTH / 2.0 - PADDLE_WIDTH / 2.0,
+                left + paddle_collider.size.x / 2.0,
+                (right - paddle_collider.size.x / 2.0).max(left + paddle_collider.size.x / 2.0),
             );
+        if time.delta_secs() > 0.0 {
+            paddle_velocity.0 = (transform.translation.x - previous_x) / time.delta_secs();
+        }
     }
 }
 
-fn ball_movement(
+// While serving, the ball rides on the paddle and movement keys sweep the launch angle.
+fn serve_aim_system(
+    controls: GameplayInput,
     time: Res<Time>,
-    mut query: Query<(&mut Transform, &Velocity), With<Ball>>,
+    paddle_query: Query<(&Transform, &PlayerId), With<Paddle>>,
+    mut ball_query: Query<(&mut Transform, &mut Serving, &Collider), (With<Ball>, Without<Paddle>)>,
 ) {
-    for (mut transform, velocity) in &mut query {
-        transform.translation.x += velocity.0.x * time.delta().as_secs_f32();
-        transform.translation.y += velocity.0.y * time.delta().as_secs_f32();
+    let Ok((paddle_transform, player)) = paddle_query.single() else {
+        return;
+    };
+    let direction = controls.direction(*player);
+    for (mut transform, mut serving, collider) in &mut ball_query {
+        serving.aim = (serving.aim + direction * SERVE_AIM_SPEED * time.delta_secs())
+            .clamp(-SERVE_AIM_MAX, SERVE_AIM_MAX);
+        transform.translation.x = paddle_transform.translation.x;
+        transform.translation.y =
+            paddle_transform.translation.y + PADDLE_HEIGHT / 2.0 + collider.size.y / 2.0;
     }
 }
 
-fn ball_collision_system(
-    mut ball_query: Query<(&mut Velocity, &mut Transform, &mut BallBlockCooldown), With<Ball>>,
-    paddle_query: Query<&Transform, (With<Paddle>, Without<Ball>)>,
-    block_query: Query<(Entity, &Transform), (With<Block>, Without<Ball>)>,
-    mut commands: Commands,
-    mut score: ResMut<GameScore>,
-    mut score_text: Query<&mut Text2d, With<Score>>,
-    time: Res<Time>,
+fn serve_direction(serving: &Serving) -> Vec2 {
+    Vec2::new(serving.aim.sin(), serving.aim.cos())
+}
+
+fn draw_aim_preview(
+    mut gizmos: Gizmos,
+    wall_query: Query<(&Transform, &SideWall)>,
+    ball_query: Query<(&Transform, &Serving, &Collider), With<Ball>>,
 ) {
-    let (mut velocity, mut transform, mut cooldown) = match ball_query.single_mut() {
-        Ok(res) => res,
-        Err(_) => return,
-    };
+    // Moving walls close in symmetrically, so the right edge gives the half width.
+    let (_, right) = side_bounds(wall_query.iter());
+    for (transform, serving, collider) in &ball_query {
+        let ball_radius = collider.size.x / 2.0 + BALL_COLLISION_MARGIN;
+        let half_extents = Vec2::new(right, ARENA.half_height()) - ball_radius;
+        let path = trajectory::predict_path(
+            transform.translation.truncate(),
+            serve_direction(serving),
+            half_extents,
+            SERVE_PREVIEW_LENGTH,
+            1,
+        );
+        trajectory::draw_dotted(&mut gizmos, &path, Color::srgba(1.0, 1.0, 1.0, 0.6));
+    }
+}
 
-    let effective_ball_size = BALL_SIZE + BALL_COLLISION_MARGIN * 2.0;
-    
-    // Wall collisions
-    if transform.translation.x + effective_ball_size / 2.0 > WINDOW_WIDTH / 2.0 {
-        velocity.0.x = -velocity.0.x.abs();
-        transform.translation.x = WINDOW_WIDTH / 2.0 - effective_ball_size / 2.0;
-    } else if transform.translation.x - effective_ball_size / 2.0 < -WINDOW_WIDTH / 2.0 {
-        velocity.0.x = velocity.0.x.abs();
-        transform.translation.x = -WINDOW_WIDTH / 2.0 + effective_ball_size / 2.0;
+fn launch_serve(
+    controls: GameplayInput,
+    touches: Res<Touches>,
+    config: Res<GameConfig>,
+    mut commands: Commands,
+    paddle_query: Query<&PlayerId, With<Paddle>>,
+    mut ball_query: Query<(Entity, &mut Velocity, &Serving), With<Ball>>,
+) {
+    let serve_pressed = paddle_query.iter().any(|player| controls.bump_just_pressed(*player));
+    if !serve_pressed && !touch::tapped(&touches) {
+        return;
     }
+    for (entity, mut velocity, serving) in &mut ball_query {
+        velocity.0 = serve_direction(serving) * config.ball_start_speed * std::f32::consts::SQRT_2;
+        commands.entity(entity).remove::<Serving>();
+    }
+}
 
-    if transform.translation.y - effective_ball_size / 2.0 < -WINDOW_HEIGHT / 2.0 {
-        velocity.0.y = velocity.0.y.abs();
-        velocity.0 *= 0.9;
+// Blocks never move on their own, so the grid is only rebuilt when one is placed, moved
+// or removed.
+#[cfg(not(feature = "rapier"))]
+fn index_blocks(
+    mut grid: ResMut<collision::BlockGrid>,
+    changed_query: Query<(), (With<Block>, Changed<Transform>)>,
+    mut removed: RemovedComponents<Block>,
+    block_query: Query<(Entity, &Transform, &Collider), With<Block>>,
+) {
+    let removed_any = removed.read().count() > 0;
+    if changed_query.is_empty() && !removed_any {
+        return;
     }
+    grid.clear();
+    for (entity, transform, collider) in &block_query {
+        grid.insert(entity, collider.aabb(transform.translation.truncate()));
+    }
+}
 
-    if transform.translation.y + effective_ball_size / 2.0 > WINDOW_HEIGHT / 2.0 {
-        velocity.0.y = -velocity.0.y.abs();
-        velocity.0 *= 0.9;
+/// Velocity and spin the ball leaves the paddle with after bouncing off the top
+/// (`normal_y` 1.0) or underside (-1.0) of `paddle_box`, the part of the paddle it hit.
+/// `velocity` has already been reflected as if off a flat surface.
+fn paddle_bounce(
+    velocity: Vec2,
+    ball_x: f32,
+    paddle_box: Aabb2d,
+    paddle_velocity: &PaddleVelocity,
+    normal_y: f32,
+    curvature: f32,
+) -> (Vec2, f32) {
+    let paddle_half_width = paddle_box.half_size().x;
+    let hit_offset = ((ball_x - paddle_box.center().x) / paddle_half_width).clamp(-1.0, 1.0);
+    let angle = if curvature > 0.0 {
+        // Reflecting off a surface tilted by `tilt` is the flat reflection turned by
+        // twice that, away from the middle. Clamped to the aimed bounce's range, so a steep
+        // tilt can't send the ball back down.
+        let tilt = hit_offset * curvature;
+        let reflected = Vec2::from_angle(-2.0 * tilt * normal_y).rotate(velocity);
+        reflected.x.atan2(reflected.y * normal_y).clamp(-PADDLE_BOUNCE_ANGLE_MAX, PADDLE_BOUNCE_ANGLE_MAX)
+    } else {
+        // The further from the middle the ball lands, the steeper it leaves, up to
+        // the maximum angle at the paddle's ends.
+        hit_offset * PADDLE_BOUNCE_ANGLE_MAX
+    };
+    let mut velocity = Vec2::new(angle.sin(), angle.cos() * normal_y) * velocity.length();
+    // A moving paddle whips the ball along with it.
+    velocity.x += paddle_velocity.0 * PADDLE_MOMENTUM_TRANSFER;
+    // Moving right curves the ball clockwise, so it bends the way the paddle swept.
+    let spin = (-paddle_velocity.0 * SPIN_PER_PADDLE_SPEED).clamp(-SPIN_MAX, SPIN_MAX);
+    (velocity, spin)
+}
+
+/// Curves the ball's path by its spin over `dt` seconds, and lets the spin fade.
+///
+/// With `curveball` the spin bends the path harder and holds until a bounce takes it
+/// off, so the ball swerves all the way between hits.
+fn curve_with_spin(velocity: &mut Velocity, spin: &mut Spin, dt: f32, curveball: bool) {
+    if curveball {
+        velocity.0 = Vec2::from_angle(spin.0 * CURVEBALL_STRENGTH * dt).rotate(velocity.0);
+        return;
     }
+    velocity.0 = Vec2::from_angle(spin.0 * dt).rotate(velocity.0);
+    spin.0 *= (-SPIN_DECAY * dt).exp();
+}
 
-    // Paddle collisions
-    for paddle_transform in paddle_query.iter() {
-        let paddle_pos = paddle_transform.translation;
-        
-        let ball_left = transform.translation.x - effective_ball_size / 2.0;
-        let ball_right = transform.translation.x + effective_ball_size / 2.0;
-        let paddle_left = paddle_pos.x - PADDLE_WIDTH / 2.0;
-        let paddle_right = paddle_pos.x + PADDLE_WIDTH / 2.0;
-        let paddle_top = paddle_pos.y + PADDLE_HEIGHT / 2.0;
-        let paddle_bottom = paddle_pos.y - PADDLE_HEIGHT / 2.0;
-        
-        if velocity.0.y < 0.0
-            && transform.translation.y - effective_ball_size / 2.0 <= paddle_pos.y + PADDLE_HEIGHT / 2.0
-            && transform.translation.y - effective_ball_size / 2.0 >= paddle_pos.y - PADDLE_HEIGHT / 2.0
-            && transform.translation.x + effective_ball_size / 2.0 > paddle_pos.x - PADDLE_WIDTH / 2.0
-            && transform.translation.x - effective_ball_size / 2.0 < paddle_pos.x + PADDLE_WIDTH / 2.0
-        {
-            velocity.0.y = velocity.0.y.abs();
-            
-            let ball_relative_x = transform.translation.x - paddle_pos.x;
-            let paddle_half_width = PADDLE_WIDTH / 2.0;
-            
-            if ball_relative_x > paddle_half_width * 0.1 {
-                velocity.0.x = BALL_START_SPEED * 0.8;
-            } else if ball_relative_x < -paddle_half_width * 0.1 {
-                velocity.0.x = -BALL_START_SPEED * 0.8;
-            } else {
-                velocity.0.x = 0.0;
-            }
-            
-            let ball_relative_y = transform.translation.y - paddle_pos.y;
-            if ball_relative_y < 0.0 {
-                velocity.0 *= 1.3;
-            } else {
-                velocity.0 *= 1.15;
+/// Spin grips the side and top walls, skewing the bounce and using up half of it.
+fn skew_wall_bounce(velocity: &mut Velocity, spin: &mut Spin) {
+    velocity.0 = Vec2::from_angle(spin.0 * SPIN_WALL_BIAS).rotate(velocity.0);
+    spin.0 *= 0.5;
+}
+
+// Moves the ball and bounces it off whatever it hits along the way. The move is swept
+// against the paddle and blocks, so a fast ball can't skip over them between frames.
+// Everything else a hit causes is left to the systems reading the hit messages.
+#[cfg(not(feature = "rapier"))]
+fn ball_collision_system(
+    mut ball_query: Query<
+        (Entity, &mut Velocity, &mut Spin, &mut Transform, &Collider),
+        (With<Ball>, Without<Serving>),
+    >,
+    paddle_query: Query<
+        (Entity, &Transform, &Collider, &PaddleVelocity, Has<SplitPaddle>),
+        (Or<(With<Paddle>, With<TopPaddle>)>, Without<Ball>),
+    >,
+    block_query: Query<(Entity, &Transform, &Collider, &BlockHealth), (With<Block>, Without<Ball>)>,
+    grid: Res<collision::BlockGrid>,
+    config: Res<GameConfig>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+    bullet_time: Res<BulletTime>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+    wall_query: Query<(&Transform, &SideWall), Without<Ball>>,
+    wall_holes: Res<WallHoles>,
+    mut paddle_hits: MessageWriter<BallHitPaddle>,
+    mut wall_hits: MessageWriter<BallHitWall>,
+    mut block_hits: MessageWriter<BallHitBlock>,
+) {
+    let _span = info_span!("ball_collision").entered();
+    let curveball = levels.get(current_level.0).curveball;
+    let open_ceiling = settings.mutator(Mutator::DualPaddle);
+    for (ball, mut velocity, mut spin, mut transform, ball_collider) in &mut ball_query {
+        let mut position = transform.translation.truncate();
+        let dt = time.delta_secs() * bullet_time.ball_time_scale();
+        let mut remaining = dt;
+        // Damage is applied later, so blocks already hit enough times this frame to break
+        // are skipped by hand.
+        let mut hit_blocks = Vec::new();
+
+        curve_with_spin(&mut velocity, &mut spin, dt, curveball);
+
+        for step in 0..MAX_SWEEP_STEPS {
+            let _step_span = info_span!("sweep_step", step).entered();
+            let displacement = velocity.0 * remaining;
+            let ball_box = ball_collider.aabb(position);
+            let mut nearest: Option<(collision::SweepHit, Entity)> = None;
+            let mut consider = |hit: Option<collision::SweepHit>, entity: Entity| {
+                let Some(hit) = hit else {
+                    return;
+                };
+                if nearest.is_none_or(|(nearest_hit, _)| hit.time < nearest_hit.time) {
+                    nearest = Some((hit, entity));
+                }
+            };
+
+            // The paddle is a little more forgiving than blocks.
+            for (entity, paddle_transform, paddle_collider, _, split) in &paddle_query {
+                let center = paddle_transform.translation.truncate();
+                for part in split_paddle::parts(center, paddle_collider, split) {
+                    let hit = collision::sweep_aabb(
+                        ball_box.grow(Vec2::splat(BALL_COLLISION_MARGIN)),
+                        displacement,
+                        part,
+                    );
+                    consider(hit, entity);
+                }
             }
-        }
-        
-        if velocity.0.y > 0.0
-            && transform.translation.y + effective_ball_size / 2.0 >= paddle_pos.y - PADDLE_HEIGHT / 2.0
-            && transform.translation.y + effective_ball_size / 2.0 <= paddle_pos.y + PADDLE_HEIGHT / 2.0
-            && transform.translation.x + effective_ball_size / 2.0 > paddle_pos.x - PADDLE_WIDTH / 2.0
-            && transform.translation.x - effective_ball_size / 2.0 < paddle_pos.x + PADDLE_WIDTH / 2.0
-        {
-            velocity.0.y = -velocity.0.y.abs();
-            
-            let ball_relative_x = transform.translation.x - paddle_pos.x;
-            let paddle_half_width = PADDLE_WIDTH / 2.0;
-            
-            if ball_relative_x > paddle_half_width * 0.1 {
-                velocity.0.x = BALL_START_SPEED * 0.8;
-            } else if ball_relative_x < -paddle_half_width * 0.1 {
-                velocity.0.x = -BALL_START_SPEED * 0.8;
-            } else {
-                velocity.0.x = 0.0;
+            let swept_area = ball_box.merge(&Aabb2d::new(position + displacement, ball_box.half_size()));
+            let nearby_blocks = grid.nearby(swept_area);
+            for (entity, block_transform, block_collider, health) in block_query.iter_many(nearby_blocks) {
+                let hits = hit_blocks.iter().filter(|&&hit| hit == entity).count();
+                if hits >= health.0 as usize {
+                    continue;
+                }
+                let hit = collision::sweep_aabb(
+                    ball_box,
+                    displacement,
+                    block_collider.aabb(block_transform.translation.truncate()),
+                );
+                consider(hit, entity);
             }
-            
-            let ball_relative_y = transform.translation.y - paddle_pos.y;
-            if ball_relative_y < 0.0 {
-                velocity.0 *= 1.3;
+
+            let Some((hit, entity)) = nearest else {
+                position += displacement;
+                break;
+            };
+            // Reflect off the face that was actually struck, and never leave the ball inside
+            // a box it started the frame overlapping.
+            position += displacement * hit.time + hit.normal * hit.penetration;
+            remaining *= 1.0 - hit.time;
+            let side = Side::from_normal(hit.normal);
+            let speed = velocity.0.length();
+            velocity.0 = collision::bounce(velocity.0, hit.normal);
+
+            if let Ok((_, paddle_transform, paddle_collider, paddle_velocity, split)) = paddle_query.get(entity)
+            {
+                paddle_hits.write(BallHitPaddle { ball, side, speed });
+                if hit.normal.y == 0.0 {
+                    continue;
+                }
+
+                // Of a split paddle's halves, the one the ball came down on.
+                let center = paddle_transform.translation.truncate();
+                let distance = |part: &Aabb2d| (part.center().x - position.x).abs();
+                let Some(part) = split_paddle::parts(center, paddle_collider, split)
+                    .into_iter()
+                    .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+                else {
+                    continue;
+                };
+                (velocity.0, spin.0) = paddle_bounce(
+                    velocity.0,
+                    position.x,
+                    part,
+                    paddle_velocity,
+                    hit.normal.y,
+                    config.paddle_curvature,
+                );
             } else {
-                velocity.0 *= 1.15;
+                hit_blocks.push(entity);
+                block_hits.write(BallHitBlock {
+                    ball,
+                    entity,
+                    side,
+                    speed,
+                });
             }
         }
-        
-        if ball_right >= paddle_left && ball_left <= paddle_left
-            && transform.translation.y + effective_ball_size / 2.0 > paddle_bottom
-            && transform.translation.y - effective_ball_size / 2.0 < paddle_top
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+
+        let effective_ball_size = ball_collider.size.x + BALL_COLLISION_MARGIN * 2.0;
+
+        // Wall collisions. A ball still overlapping a wall it already bounced off doesn't
+        // count as hitting it again. One lined up with a hole in a crumbling wall, or
+        // already halfway through it, carries on out of the arena.
+        let (left, right) = side_bounds(wall_query.iter());
+        let ball_y = transform.translation.y;
+        let escaping = |side, past_wall| {
+            wall_holes.breached(side)
+                && (past_wall || wall_holes.lets_through(side, ball_y, effective_ball_size / 2.0))
+        };
+        let mut walls_hit = Vec::new();
+        if transform.translation.x + effective_ball_size / 2.0 > right
+            && !escaping(Side::Right, transform.translation.x > right)
         {
+            if velocity.0.x > 0.0 {
+                walls_hit.push(Side::Right);
+            }
             velocity.0.x = -velocity.0.x.abs();
-        }
-        
-        if ball_left <= paddle_right && ball_right >= paddle_right
-            && transform.translation.y + effective_ball_size / 2.0 > paddle_bottom
-            && transform.translation.y - effective_ball_size / 2.0 < paddle_top
+            transform.translation.x = right - effective_ball_size / 2.0;
+        } else if transform.translation.x - effective_ball_size / 2.0 < left
+            && !escaping(Side::Left, transform.translation.x < left)
         {
+            if velocity.0.x < 0.0 {
+                walls_hit.push(Side::Left);
+            }
             velocity.0.x = velocity.0.x.abs();
+            transform.translation.x = left + effective_ball_size / 2.0;
         }
-    }
 
-    // Block collisions
-    for (block_entity, block_transform) in block_query.iter() {
-        let block_pos = block_transform.translation;
-        let block_width = 75.0;
-        let block_height = 20.0;
-        
-        if transform.translation.x + BALL_SIZE / 2.0 > block_pos.x - block_width / 2.0
-            && transform.translation.x - BALL_SIZE / 2.0 < block_pos.x + block_width / 2.0
-            && transform.translation.y + BALL_SIZE / 2.0 > block_pos.y - block_height / 2.0
-            && transform.translation.y - BALL_SIZE / 2.0 < block_pos.y + block_height / 2.0
+        if transform.translation.y - effective_ball_size / 2.0 < -ARENA.half_height() && velocity.0.y < 0.0 {
+            walls_hit.push(Side::Bottom);
+            velocity.0.y = velocity.0.y.abs();
+        }
+
+        if !open_ceiling
+            && transform.translation.y + effective_ball_size / 2.0 > ARENA.half_height()
+            && velocity.0.y > 0.0
         {
-            if cooldown.0 <= 0.0 {
-                commands.entity(block_entity).despawn();
-                score.0 += 1;
-                
-                for mut text in score_text.iter_mut() {
-                    *text = Text2d(format!("Score: {}", score.0));
-                }
-                
-                velocity.0.y = -velocity.0.y;
-                velocity.0 *= 1.1;
-                cooldown.0 = 0.1;
+            walls_hit.push(Side::Top);
+            velocity.0.y = -velocity.0.y.abs();
+        }
+
+        for side in walls_hit {
+            if side != Side::Bottom {
+                skew_wall_bounce(&mut velocity, &mut spin);
             }
+            wall_hits.write(BallHitWall { ball, side });
         }
     }
-    
-    cooldown.0 -= time.delta_secs();
-    if cooldown.0 < 0.0 {
-        cooldown.0 = 0.0;
+}
+
+fn damage_blocks(
+    mut block_hits: MessageReader<BallHitBlock>,
+    mut block_query: Query<
+        (&mut BlockHealth, &BlockPoints, &Transform, Has<Hazard>, Option<&Shield>, Option<&Letter>),
+        With<Block>,
+    >,
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut combo: ResMut<Combo>,
+    mut score_events: MessageWriter<BlockScored>,
+    mut combo_events: MessageWriter<ComboChanged>,
+    mut destroyed_events: MessageWriter<BlockDestroyed>,
+) {
+    for hit in block_hits.read() {
+        let Ok((mut health, points, transform, hazard, shield, letter)) = block_query.get_mut(hit.entity) else {
+            continue;
+        };
+        if health.0 == 0 || shield.is_some_and(|shield| shield.blocks(hit.side)) {
+            continue;
+        }
+        health.0 -= 1;
+        if health.0 == 0 {
+            commands.entity(hit.entity).despawn();
+            destroyed_events.write(BlockDestroyed {
+                position: transform.translation.truncate(),
+                hazard,
+                letter: letter.map(|letter| letter.0),
+            });
+            combo.count += 1;
+            score_events.write(BlockScored {
+                points: points.0 * scoring::speed_points(hit.speed, &config),
+                multiplier: combo.multiplier(),
+                position: transform.translation.truncate(),
+            });
+            combo_events.write(ComboChanged {
+                multiplier: combo.multiplier(),
+            });
+        }
+    }
+}
+
+// Each hit scales the ball's speed by its factor in the configured speed ramp, which
+// also keeps it within the configured speed range.
+fn apply_hit_speed(
+    config: Res<GameConfig>,
+    mut paddle_hits: MessageReader<BallHitPaddle>,
+    mut wall_hits: MessageReader<BallHitWall>,
+    mut block_hits: MessageReader<BallHitBlock>,
+    mut ball_query: Query<(Entity, &mut Velocity), (With<Ball>, Without<Serving>)>,
+) {
+    let ramp = config.speed_ramp;
+    let mut factors = Vec::new();
+    for hit in paddle_hits.read() {
+        match hit.side {
+            Side::Top => factors.push((hit.ball, ramp.paddle)),
+            Side::Bottom => factors.push((hit.ball, ramp.paddle_underside)),
+            Side::Left | Side::Right => {}
+        }
     }
+    for hit in wall_hits.read() {
+        if matches!(hit.side, Side::Top | Side::Bottom) {
+            factors.push((hit.ball, ramp.wall));
+        }
+    }
+    factors.extend(block_hits.read().map(|hit| (hit.ball, ramp.block)));
 
-    let speed = velocity.0.length().clamp(BALL_START_SPEED, BALL_SPEED_MAX);
-    velocity.0 = velocity.0.normalize() * speed;
+    for (ball, mut velocity) in &mut ball_query {
+        let multiplier: f32 = factors
+            .iter()
+            .filter(|(hit_ball, _)| *hit_ball == ball)
+            .map(|(_, factor)| factor)
+            .product();
+        velocity.0 = config.ramp_speed(velocity.0, multiplier);
+    }
 }
 
 fn check_win_condition(
@@ -396,51 +1324,50 @@ fn check_win_condition(
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     if block_query.is_empty() {
-        next_state.set(GameState::GameWon);
+        next_state.set(GameState::LevelComplete);
     }
 }
 
-fn clear_game_camera(mut commands: Commands, camera_query: Query<Entity, With<Camera>>) {
+fn clear_game_camera(mut commands: Commands, camera_query: Query<Entity, With<GameCamera>>) {
     for camera_entity in camera_query.iter() {
         commands.entity(camera_entity).despawn();
     }
 }
 
+// Presses are read every frame so none are missed; whether the bump connects is
+// checked on the fixed timestep by `bump_connect`.
 fn ball_bump_system(
-    input: Res<ButtonInput<Key>>,
-    mut paddle_query: Query<(&mut Transform, &mut PaddleBounce), With<Paddle>>,
-    mut ball_query: Query<(&mut Velocity, &Transform), (With<Ball>, Without<Paddle>)>,
+    controls: GameplayInput,
+    touches: Res<Touches>,
+    mut paddle_query: Query<(&mut Transform, &mut PaddleBounce, &PlayerId), With<Paddle>>,
+    ball_query: Query<(), (With<Ball>, Without<Serving>)>,
+    mut energy: ResMut<BumpEnergy>,
+    mut buffer: ResMut<BumpBuffer>,
+    mut bumped: MessageWriter<Bumped>,
     time: Res<Time>,
 ) {
-    if input.just_pressed(Key::Space) {
-        if let Ok((mut paddle_transform, mut paddle_bounce)) = paddle_query.single_mut() {
-            if let Ok((mut ball_velocity, ball_transform)) = ball_query.single_mut() {
-                let paddle_pos = paddle_transform.translation;
-                let ball_pos = ball_transform.translation;
-                
-                let effective_ball_size = BALL_SIZE + BALL_COLLISION_MARGIN * 2.0;
-                let collision = ball_pos.x + effective_ball_size / 2.0 > paddle_pos.x - PADDLE_WIDTH / 2.0
-                    && ball_pos.x - effective_ball_size / 2.0 < paddle_pos.x + PADDLE_WIDTH / 2.0
-                    && ball_pos.y + effective_ball_size / 2.0 > paddle_pos.y - PADDLE_HEIGHT / 2.0
-                    && ball_pos.y - effective_ball_size / 2.0 < paddle_pos.y + PADDLE_HEIGHT / 2.0;
-                
+    if let Ok((mut paddle_transform, mut paddle_bounce, player)) = paddle_query.single_mut() {
+        if !ball_query.is_empty() {
+            let bump_pressed = controls.bump_just_pressed(*player) || touch::tapped(&touches);
+            if bump_pressed && energy.0 >= BUMP_ENERGY_COST {
+                *buffer = BumpBuffer {
+                    time: BUMP_BUFFER_TIME,
+                    charged: energy.0 >= 1.0,
+                };
+                energy.0 -= BUMP_ENERGY_COST;
+                bumped.write(Bumped);
+
                 if !paddle_bounce.is_bouncing {
                     paddle_bounce.original_y = paddle_transform.translation.y;
                     paddle_bounce.is_bouncing = true;
                     paddle_bounce.bounce_timer = 0.2;
                     paddle_transform.translation.y += 15.0;
                 }
-                
-                if collision {
-                    ball_velocity.0 *= 1.5;
-                    let speed = ball_velocity.0.length().clamp(BALL_START_SPEED, BALL_SPEED_MAX);
-                    ball_velocity.0 = ball_velocity.0.normalize() * speed;
-                }
             }
         }
     }
-    
-    for (mut paddle_transform, mut paddle_bounce) in paddle_query.iter_mut() {
+
+    for (mut paddle_transform, mut paddle_bounce, _) in paddle_query.iter_mut() {
         if paddle_bounce.is_bouncing {
             paddle_bounce.bounce_timer -= time.delta_secs();
             if paddle_bounce.bounce_timer <= 0.0 {
@@ -451,26 +1378,118 @@ fn ball_bump_system(
     }
 }
 
+fn bump_connect(
+    mut commands: Commands,
+    paddle_query: Query<(&Transform, &Collider, Has<SplitPaddle>), With<Paddle>>,
+    mut ball_query: Query<
+        (&mut Velocity, &Transform, &Collider, &Sprite, &Spin),
+        (With<Ball>, Without<Paddle>, Without<Serving>),
+    >,
+    mut buffer: ResMut<BumpBuffer>,
+    mut announcements: MessageWriter<Announce>,
+    config: Res<GameConfig>,
+    time: Res<Time>,
+) {
+    if buffer.time <= 0.0 {
+        return;
+    }
+    let Ok((paddle_transform, paddle_collider, split)) = paddle_query.single() else {
+        return;
+    };
+    let paddle_parts = split_paddle::parts(paddle_transform.translation.truncate(), paddle_collider, split);
+
+    // One bump only ever boosts one ball, even with several against the paddle.
+    let hit = ball_query.iter_mut().find(|(_, ball_transform, ball_collider, _, _)| {
+        let ball_box = ball_collider
+            .aabb(ball_transform.translation.truncate())
+            .grow(Vec2::splat(BALL_COLLISION_MARGIN));
+        paddle_parts.iter().any(|part| ball_box.intersects(part))
+    });
+    let Some((mut ball_velocity, ball_transform, ball_collider, sprite, spin)) = hit else {
+        buffer.time = (buffer.time - time.delta_secs()).max(0.0);
+        return;
+    };
+
+    if buffer.charged {
+        // The twin leaves mirrored across the vertical, spinning the other way.
+        commands.spawn((
+            sprite.clone(),
+            *ball_transform,
+            Ball,
+            Collider {
+                size: ball_collider.size,
+            },
+            Velocity(Vec2::new(-ball_velocity.0.x, ball_velocity.0.y)),
+            Spin(-spin.0),
+            Interpolated::default(),
+            GameEntity,
+        ));
+        announcements.write(Announce(VoiceLine::MultiBall));
+    } else {
+        ball_velocity.0 = config.ramp_speed(ball_velocity.0, config.speed_ramp.bump);
+    }
+    *buffer = BumpBuffer::default();
+}
+
+fn regenerate_bump_energy(
+    time: Res<Time>,
+    mut score_events: MessageReader<BlockScored>,
+    mut energy: ResMut<BumpEnergy>,
+) {
+    let blocks = score_events.read().count() as f32;
+    // A full meter is left untouched, so the gauge isn't redrawn every frame.
+    energy.set_if_neq(BumpEnergy(
+        (energy.0 + BUMP_ENERGY_REGEN * time.delta_secs() + BUMP_ENERGY_PER_BLOCK * blocks).min(1.0),
+    ));
+}
+
 fn ball_bounds_check(
-    mut ball_query: Query<(&mut Transform, &mut Velocity), With<Ball>>,
+    config: Res<GameConfig>,
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut serve_deal: ResMut<ServeDeal>,
+    mut ball_query: Query<(Entity, &Transform, &mut Velocity), (With<Ball>, Without<Serving>)>,
+    mut lost_events: MessageWriter<BallLost>,
 ) {
-    if let Ok((mut transform, mut velocity)) = ball_query.single_mut() {
-        let max_allowed_distance = WINDOW_WIDTH / 2.0 + 100.0;
-        
-        if transform.translation.x.abs() > max_allowed_distance 
-            || transform.translation.y.abs() > max_allowed_distance {
-            transform.translation = Vec3::new(0.0, 0.0, 1.0);
-            velocity.0 = Vec2::new(BALL_START_SPEED, BALL_START_SPEED);
+    let max_allowed_distance = ARENA.half_width().max(ARENA.half_height()) + 100.0;
+    // Past the top edge, with the ceiling open, is as good as gone.
+    let ceiling = if settings.mutator(Mutator::DualPaddle) {
+        ARENA.half_height() + BALL_SIZE
+    } else {
+        max_allowed_distance
+    };
+    let in_play = ball_query.iter().len();
+    let mut escaped = Vec::new();
+    for (entity, transform, mut velocity) in &mut ball_query {
+        if transform.translation.x.abs() > max_allowed_distance
+            || transform.translation.y.abs() > max_allowed_distance
+            || transform.translation.y > ceiling {
+            escaped.push(entity);
+            continue;
         }
-        
-        if velocity.0.length() < BALL_START_SPEED * 0.5 {
-            velocity.0 = velocity.0.normalize() * BALL_START_SPEED;
+
+        if velocity.0.length() < config.ball_start_speed * 0.5 {
+            velocity.0 = velocity.0.normalize() * config.ball_start_speed;
         }
     }
+
+    // Balls that escape while others are still in play are simply gone. The last one
+    // goes back onto the paddle for a fresh serve, and only that costs a life.
+    let keep = (escaped.len() == in_play).then(|| escaped.pop()).flatten();
+    for entity in escaped {
+        commands.entity(entity).despawn();
+    }
+    if let Some(entity) = keep {
+        if let Ok((_, _, mut velocity)) = ball_query.get_mut(entity) {
+            velocity.0 = Vec2::ZERO;
+        }
+        commands.entity(entity).insert((serve_deal.next(), Spin::default()));
+        lost_events.write(BallLost);
+    }
 }
 
-fn setup_win_screen(mut commands: Commands, _asset_server: Res<AssetServer>) {
-    commands.spawn((Camera2d, IsDefaultUiCamera));
+fn setup_win_screen(mut commands: Commands, _asset_server: Res<AssetServer>, stats: Res<RunStats>) {
+    display::spawn_game_camera(&mut commands);
 
     commands.spawn((
         Sprite {
@@ -484,60 +1503,49 @@ fn setup_win_screen(mut commands: Commands, _asset_server: Res<AssetServer>) {
 
     commands.spawn((
         Text2d("You won!".to_string()),
-        Transform::from_xyz(0.0, 50.0, 2.0),
+        Transform::from_xyz(0.0, 260.0, 2.0),
         WinScreen,
     ));
+    stats::spawn_summary(&mut commands, &stats, WinScreen, 2.0);
 
-    commands.spawn((
-        Sprite {
-            color: Color::srgb(0.25, 0.25, 0.85),
-            custom_size: Some(Vec2::new(300.0, 100.0)),
-            ..default()
-        },
-        Transform::from_xyz(0.0, -100.0, 1.0),
-        RestartButton,
-    ));
-
-    commands.spawn((
-        Text2d("Press Spacebar to Restart".to_string()),
-        Transform::from_xyz(0.0, -100.0, 2.0),
-        RestartButton,
-    ));
+    spawn_menu_button(&mut commands, "Press Spacebar or Tap to Restart", RestartButton, 1.0);
 }
 
 fn restart_button(
     input: Res<ButtonInput<Key>>,
+    touches: Res<Touches>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
     mut next_state: ResMut<NextState<GameState>>,
     mut commands: Commands,
     win_screen_query: Query<Entity, With<WinScreen>>,
     button_query: Query<Entity, With<RestartButton>>,
-    paddle_query: Query<Entity, With<Paddle>>,
-    ball_query: Query<Entity, With<Ball>>,
-    block_query: Query<Entity, With<Block>>,
-    score_query: Query<Entity, With<Score>>,
+    game_query: Query<Entity, With<GameEntity>>,
     mut score: ResMut<GameScore>,
+    mut combo: ResMut<Combo>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut energy: ResMut<BumpEnergy>,
+    mut cycle: ResMut<NewGamePlus>,
+    mut activated: MessageReader<MenuActivated>,
 ) {
-    if input.just_pressed(Key::Space) {
+    let confirmed = activated
+        .read()
+        .any(|MenuActivated(entity)| button_query.contains(*entity));
+    if input.just_pressed(Key::Space) || confirmed || menu_button_tapped(&touches, &camera_query) {
         for entity in &win_screen_query {
             commands.entity(entity).despawn();
         }
         for entity in &button_query {
             commands.entity(entity).despawn();
         }
-        for entity in &paddle_query {
-            commands.entity(entity).despawn();
-        }
-        for entity in &ball_query {
-            commands.entity(entity).despawn();
-        }
-        for entity in &block_query {
-            commands.entity(entity).despawn();
-        }
-        for entity in &score_query {
+        for entity in &game_query {
             commands.entity(entity).despawn();
         }
         
         score.0 = 0;
+        combo.count = 0;
+        current_level.0 = 0;
+        energy.0 = 1.0;
+        cycle.0 = 0;
         next_state.set(GameState::Playing);
     }
 }
@@ -0,0 +1,114 @@
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+
+use crate::sfx::{PlaySfx, Sfx};
+
+const FOCUS_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+/// Gap between a focused item's edge and its outline.
+const FOCUS_PADDING: f32 = 8.0;
+
+/// Something on a menu screen that can be focused with the arrow keys or D-pad.
+/// Items are visited in ascending `order`.
+#[derive(Component)]
+pub struct MenuItem {
+    pub order: u32,
+    pub size: Vec2,
+}
+
+/// The menu item currently showing the focus outline.
+#[derive(Resource, Default)]
+struct MenuFocus(Option<Entity>);
+
+/// Sent when Enter or the gamepad's south button is pressed on the focused item.
+#[derive(Message)]
+pub struct MenuActivated(pub Entity);
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuFocus>()
+            .add_message::<MenuActivated>()
+            .add_systems(Update, ((navigate_menu, draw_menu_focus).chain(), play_back_sound));
+    }
+}
+
+/// Whether any connected gamepad pressed `button` this frame.
+pub fn gamepad_just_pressed(gamepads: &Query<&Gamepad>, button: GamepadButton) -> bool {
+    gamepads.iter().any(|gamepad| gamepad.just_pressed(button))
+}
+
+fn navigate_menu(
+    input: Res<ButtonInput<Key>>,
+    gamepads: Query<&Gamepad>,
+    item_query: Query<(Entity, &MenuItem)>,
+    mut focus: ResMut<MenuFocus>,
+    mut activated: MessageWriter<MenuActivated>,
+    mut sfx: MessageWriter<PlaySfx>,
+) {
+    let mut items: Vec<(Entity, u32)> = item_query.iter().map(|(entity, item)| (entity, item.order)).collect();
+    if items.is_empty() {
+        focus.0 = None;
+        return;
+    }
+    items.sort_by_key(|(_, order)| *order);
+
+    // A new screen starts out focused on its first item.
+    let current = focus
+        .0
+        .and_then(|focused| items.iter().position(|(entity, _)| *entity == focused))
+        .unwrap_or(0);
+    let up = input.just_pressed(Key::ArrowUp) || gamepad_just_pressed(&gamepads, GamepadButton::DPadUp);
+    let down =
+        input.just_pressed(Key::ArrowDown) || gamepad_just_pressed(&gamepads, GamepadButton::DPadDown);
+    let next = if up {
+        (current + items.len() - 1) % items.len()
+    } else if down {
+        (current + 1) % items.len()
+    } else {
+        current
+    };
+    let focused = items[next].0;
+    if next != current {
+        sfx.write(PlaySfx(Sfx::MenuMove));
+    }
+    focus.0 = Some(focused);
+
+    // Alt+Enter is the fullscreen shortcut, not a menu press.
+    let enter = input.just_pressed(Key::Enter) && !input.pressed(Key::Alt);
+    if enter || gamepad_just_pressed(&gamepads, GamepadButton::South) {
+        activated.write(MenuActivated(focused));
+        sfx.write(PlaySfx(Sfx::MenuConfirm));
+    }
+}
+
+// Escape and the east button back out of whichever menu is open, so the sound plays for
+// all of them without each screen asking for it.
+fn play_back_sound(
+    input: Res<ButtonInput<Key>>,
+    gamepads: Query<&Gamepad>,
+    item_query: Query<(), With<MenuItem>>,
+    mut sfx: MessageWriter<PlaySfx>,
+) {
+    if item_query.is_empty() {
+        return;
+    }
+    if input.just_pressed(Key::Escape) || gamepad_just_pressed(&gamepads, GamepadButton::East) {
+        sfx.write(PlaySfx(Sfx::MenuBack));
+    }
+}
+
+fn draw_menu_focus(
+    mut gizmos: Gizmos,
+    focus: Res<MenuFocus>,
+    item_query: Query<(&GlobalTransform, &MenuItem)>,
+) {
+    let Some((transform, item)) = focus.0.and_then(|focused| item_query.get(focused).ok()) else {
+        return;
+    };
+    gizmos.rect_2d(
+        transform.translation().truncate(),
+        item.size + FOCUS_PADDING * 2.0,
+        FOCUS_COLOR,
+    );
+}
@@ -0,0 +1,189 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bevy::asset::io::{AssetSourceBuilder, AssetSourceId};
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::display::GameCamera;
+use crate::level::Levels;
+use crate::menu::{MenuActivated, MenuItem};
+use crate::settings::Settings;
+use crate::{touch, GameState};
+
+const MODS_DIR: &str = "mods";
+/// Asset source that serves files out of `MODS_DIR`, as `mods://<pack>/<path>`.
+const MODS_SOURCE: &str = "mods";
+const PACK_INFO_FILE: &str = "mod.ron";
+
+const ROW_TOP_Y: f32 = -280.0;
+const ROW_SPACING: f32 = 26.0;
+const ROW_SIZE: Vec2 = Vec2::new(360.0, 22.0);
+
+/// Optional `mod.ron` at the root of a pack.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct PackInfo {
+    name: Option<String>,
+}
+
+/// A folder under `mods/` whose files add to or stand in for the game's own content.
+pub struct ContentPack {
+    /// Folder name, which is also how the pack is remembered in the settings.
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+}
+
+/// Content packs found at startup, in load order: sorted by folder name, with later
+/// packs winning when two provide the same file.
+///
+/// A pack can hold any of the files under `assets/` at the same relative path. Images
+/// and scripts replace the built-in ones, and a pack's `levels.ron` is played after the
+/// built-in levels.
+#[derive(Resource)]
+pub struct ModRegistry {
+    pub packs: Vec<ContentPack>,
+}
+
+impl ModRegistry {
+    fn scan(settings: &Settings) -> Self {
+        let mut packs: Vec<ContentPack> = fs::read_dir(MODS_DIR)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| {
+                let id = entry.file_name().to_string_lossy().into_owned();
+                let info: PackInfo = fs::read_to_string(entry.path().join(PACK_INFO_FILE))
+                    .ok()
+                    .and_then(|contents| ron::from_str(&contents).ok())
+                    .unwrap_or_default();
+                ContentPack {
+                    name: info.name.unwrap_or_else(|| id.clone()),
+                    enabled: !settings.disabled_mods.contains(&id),
+                    id,
+                }
+            })
+            .collect();
+        packs.sort_by(|a, b| a.id.cmp(&b.id));
+        ModRegistry { packs }
+    }
+
+    /// Enabled packs that contain `path`, each with the full path to its copy.
+    pub fn files<'a>(&'a self, path: &'a str) -> impl Iterator<Item = (&'a ContentPack, PathBuf)> + 'a {
+        self.packs
+            .iter()
+            .filter(|pack| pack.enabled)
+            .map(move |pack| (pack, Path::new(MODS_DIR).join(&pack.id).join(path)))
+            .filter(|(_, file)| file.is_file())
+    }
+
+    /// Where to read `path`, relative to `assets/`, from disk.
+    pub fn file_path(&self, path: &str) -> PathBuf {
+        self.files(path)
+            .last()
+            .map_or_else(|| Path::new("assets").join(path), |(_, file)| file)
+    }
+
+    /// Path to hand the `AssetServer` for `path`, relative to `assets/`.
+    pub fn asset_path(&self, path: &str) -> String {
+        self.files(path)
+            .last()
+            .map_or_else(|| path.to_string(), |(pack, _)| format!("{MODS_SOURCE}://{}/{path}", pack.id))
+    }
+}
+
+/// One pack's line in the mod list on the splash screen.
+#[derive(Component)]
+struct ModRow(usize);
+
+/// Scans `mods/` and serves pack files to the `AssetServer`, so it has to be added before
+/// `DefaultPlugins`.
+pub struct ModsPlugin;
+
+impl Plugin for ModsPlugin {
+    fn build(&self, app: &mut App) {
+        let registry = ModRegistry::scan(app.world().resource::<Settings>());
+        app.register_asset_source(
+            AssetSourceId::from(MODS_SOURCE),
+            AssetSourceBuilder::platform_default(MODS_DIR, None),
+        )
+        .insert_resource(registry)
+        .add_systems(OnEnter(GameState::Splash), spawn_mod_rows)
+        .add_systems(Update, toggle_pack.run_if(in_state(GameState::Splash)))
+        .add_systems(OnExit(GameState::Splash), despawn_mod_rows);
+    }
+}
+
+fn row_label(pack: &ContentPack) -> String {
+    format!("[{}] {}", if pack.enabled { "x" } else { " " }, pack.name)
+}
+
+fn row_y(index: usize) -> f32 {
+    ROW_TOP_Y - index as f32 * ROW_SPACING
+}
+
+// Listed under the difficulty and ball skin, after them in the menu order.
+fn spawn_mod_rows(mut commands: Commands, registry: Res<ModRegistry>) {
+    for (index, pack) in registry.packs.iter().enumerate() {
+        commands.spawn((
+            Text2d(row_label(pack)),
+            TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+            Transform::from_xyz(0.0, row_y(index), 2.0),
+            MenuItem {
+                order: 3 + index as u32,
+                size: ROW_SIZE,
+            },
+            ModRow(index),
+        ));
+    }
+}
+
+fn toggle_pack(
+    touches: Res<Touches>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    mut activated: MessageReader<MenuActivated>,
+    mut registry: ResMut<ModRegistry>,
+    mut levels: ResMut<Levels>,
+    mut settings: ResMut<Settings>,
+    mut row_query: Query<(Entity, &ModRow, &mut Text2d)>,
+) {
+    let activated: Vec<Entity> = activated.read().map(|MenuActivated(entity)| *entity).collect();
+    let tap = camera_query.single().ok();
+    let mut toggled = false;
+    for (entity, row, mut text) in &mut row_query {
+        let tapped = tap.is_some_and(|(camera, camera_transform)| {
+            touch::tapped_within(&touches, camera, camera_transform, Vec2::new(0.0, row_y(row.0)), ROW_SIZE)
+        });
+        if !tapped && !activated.contains(&entity) {
+            continue;
+        }
+        let pack = &mut registry.packs[row.0];
+        pack.enabled = !pack.enabled;
+        text.0 = row_label(pack);
+        toggled = true;
+    }
+    if !toggled {
+        return;
+    }
+
+    settings.disabled_mods = registry
+        .packs
+        .iter()
+        .filter(|pack| !pack.enabled)
+        .map(|pack| pack.id.clone())
+        .collect();
+    settings.save();
+    *levels = Levels::load(&registry);
+}
+
+fn despawn_mod_rows(mut commands: Commands, row_query: Query<Entity, With<ModRow>>) {
+    for entity in &row_query {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::announcer::{Announce, VoiceLine};
+use crate::config::GameConfig;
+use crate::interpolation::Interpolated;
+use crate::level::{CurrentLevel, LevelClock, Levels};
+use crate::mods::ModRegistry;
+use crate::settings::Settings;
+use crate::{
+    debug, pause, Ball, Collider, GameEntity, GameState, Paddle, Serving, Spin, Velocity, BALL_SIZE,
+    PADDLE_HEIGHT,
+};
+
+/// Widest angle, in radians either side of straight up, an extra ball is launched at.
+const LAUNCH_SPREAD: f32 = 0.6;
+
+/// Survival rules for a level: another ball joins every `interval` seconds, until
+/// `max` are in play at once.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ExtraBallsData {
+    #[serde(default = "default_interval")]
+    pub interval: f32,
+    pub max: u32,
+}
+
+fn default_interval() -> f32 {
+    60.0
+}
+
+/// Extra-ball intervals already handled this level, launched or skipped.
+#[derive(Resource, Default)]
+struct ExtraBallsLaunched(u32);
+
+#[derive(Component)]
+struct BallCountText;
+
+pub struct MultiballPlugin;
+
+impl Plugin for MultiballPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ExtraBallsLaunched>()
+            .add_systems(OnEnter(GameState::Playing), (reset_launched, spawn_ball_count_text))
+            .add_systems(
+                Update,
+                (
+                    launch_extra_balls
+                        .run_if(in_state(GameState::Playing).and(debug::simulation_running).and(pause::not_paused)),
+                    update_ball_count_text.run_if(in_state(GameState::Playing)),
+                ),
+            );
+    }
+}
+
+fn ball_count_label(count: usize) -> String {
+    format!("Balls: {count}")
+}
+
+fn reset_launched(mut launched: ResMut<ExtraBallsLaunched>) {
+    launched.0 = 0;
+}
+
+// Only levels with survival rules can have more than one ball, so only they get a count.
+fn spawn_ball_count_text(mut commands: Commands, levels: Res<Levels>, current_level: Res<CurrentLevel>) {
+    if levels.get(current_level.0).extra_balls.is_none() {
+        return;
+    }
+    commands.spawn((
+        Text::new(ball_count_label(1)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(40.0),
+            top: Val::Px(200.0),
+            ..default()
+        },
+        BallCountText,
+        GameEntity,
+    ));
+}
+
+// Held back while the ball is being served, so a new one never joins an empty arena.
+fn launch_extra_balls(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    registry: Res<ModRegistry>,
+    config: Res<GameConfig>,
+    settings: Res<Settings>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+    clock: Res<LevelClock>,
+    mut launched: ResMut<ExtraBallsLaunched>,
+    mut announcements: MessageWriter<Announce>,
+    paddle_query: Query<&Transform, With<Paddle>>,
+    ball_query: Query<Has<Serving>, With<Ball>>,
+) {
+    let Some(rules) = &levels.get(current_level.0).extra_balls else {
+        return;
+    };
+    let due = (clock.elapsed / rules.interval.max(f32::EPSILON)) as u32;
+    if launched.0 >= due || ball_query.iter().any(|serving| serving) {
+        return;
+    }
+    let Ok(paddle_transform) = paddle_query.single() else {
+        return;
+    };
+    // A ball due while the cap is reached is skipped rather than saved up.
+    launched.0 = due;
+    if ball_query.iter().len() as u32 >= rules.max {
+        return;
+    }
+
+    let ball_size = BALL_SIZE * settings.ball_scale;
+    let position = paddle_transform.translation.truncate() + Vec2::Y * (PADDLE_HEIGHT / 2.0 + ball_size);
+    let angle = rand::rng().random_range(-LAUNCH_SPREAD..LAUNCH_SPREAD);
+    commands.spawn((
+        settings.ball_skin.sprite(&asset_server, &registry, Vec2::splat(ball_size)),
+        Transform::from_translation(position.extend(1.0)),
+        Ball,
+        Collider {
+            size: Vec2::splat(ball_size),
+        },
+        Velocity(Vec2::new(angle.sin(), angle.cos()) * config.ball_start_speed * std::f32::consts::SQRT_2),
+        Spin::default(),
+        Interpolated::default(),
+        GameEntity,
+    ));
+    announcements.write(Announce(VoiceLine::MultiBall));
+}
+
+fn update_ball_count_text(
+    ball_query: Query<(), With<Ball>>,
+    mut text_query: Query<&mut Text, With<BallCountText>>,
+) {
+    let count = ball_query.iter().len();
+    for mut text in &mut text_query {
+        if **text != ball_count_label(count) {
+            **text = ball_count_label(count);
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Optional rule changes for a run, kept in the settings and turned on at launch with
+/// `--mutator`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Mutator {
+    /// Two half-width paddles with a gap between them; see `split_paddle`.
+    SplitPaddle,
+    /// The paddle narrows by 5% for every 10 blocks broken, back to full width on each
+    /// new level; see `shrinking_paddle`.
+    ShrinkingPaddle,
+    /// A mirrored second paddle along the top, with the blocks in the middle; see
+    /// `dual_paddle`.
+    DualPaddle,
+    /// Side walls built from segments that chip with every impact and leave a hole the
+    /// ball can escape through once broken; see `crumbling_walls`.
+    CrumblingWalls,
+}
+
+impl Mutator {
+    pub fn name(self) -> &'static str {
+        match self {
+            Mutator::SplitPaddle => "Split paddle",
+            Mutator::ShrinkingPaddle => "Shrinking paddle",
+            Mutator::DualPaddle => "Dual paddle",
+            Mutator::CrumblingWalls => "Crumbling walls",
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+
+use crate::display::GameCamera;
+use crate::level::CurrentLevel;
+use crate::menu::{MenuActivated, MenuItem};
+use crate::scoring::{Combo, GameScore};
+use crate::{touch, BumpEnergy, GameEntity, GameState, RestartButton, WinScreen};
+
+/// How much faster the ball starts, and can go, with each New Game+ cycle.
+pub const SPEED_STEP: f32 = 0.15;
+/// Extra hits every block takes with each cycle.
+pub const EXTRA_BLOCK_HP: u32 = 1;
+
+const OPTION_Y: f32 = -200.0;
+const OPTION_SIZE: Vec2 = Vec2::new(360.0, 44.0);
+
+/// How many times the campaign has been beaten in a row to get to the current run; 0 for
+/// an ordinary run. Each cycle replays the levels with a faster ball and tougher blocks.
+#[derive(Resource, Default, PartialEq)]
+pub struct NewGamePlus(pub u32);
+
+#[derive(Component)]
+struct NewGamePlusOption;
+
+pub struct NewGamePlusPlugin;
+
+impl Plugin for NewGamePlusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NewGamePlus>()
+            .add_systems(OnEnter(GameState::Splash), reset_cycle)
+            .add_systems(OnEnter(GameState::GameWon), spawn_option)
+            .add_systems(Update, start_next_cycle.run_if(in_state(GameState::GameWon)));
+    }
+}
+
+fn reset_cycle(mut cycle: ResMut<NewGamePlus>) {
+    cycle.set_if_neq(NewGamePlus(0));
+}
+
+// Listed under the restart button, after it in the menu order.
+fn spawn_option(mut commands: Commands, cycle: Res<NewGamePlus>) {
+    commands.spawn((
+        Text2d(format!("Play New Game+ {}", cycle.0 + 1)),
+        Transform::from_xyz(0.0, OPTION_Y, 2.0),
+        MenuItem {
+            order: 1,
+            size: OPTION_SIZE,
+        },
+        NewGamePlusOption,
+        WinScreen,
+    ));
+}
+
+fn start_next_cycle(
+    touches: Res<Touches>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    mut activated: MessageReader<MenuActivated>,
+    option_query: Query<(), With<NewGamePlusOption>>,
+    mut cycle: ResMut<NewGamePlus>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+    screen_query: Query<Entity, Or<(With<WinScreen>, With<RestartButton>)>>,
+    game_query: Query<Entity, With<GameEntity>>,
+    mut score: ResMut<GameScore>,
+    mut combo: ResMut<Combo>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut energy: ResMut<BumpEnergy>,
+) {
+    let confirmed = activated
+        .read()
+        .any(|MenuActivated(entity)| option_query.contains(*entity));
+    let tapped = camera_query.single().is_ok_and(|(camera, camera_transform)| {
+        touch::tapped_within(&touches, camera, camera_transform, Vec2::new(0.0, OPTION_Y), OPTION_SIZE)
+    });
+    if !confirmed && !tapped {
+        return;
+    }
+
+    for entity in screen_query.iter().chain(&game_query) {
+        commands.entity(entity).despawn();
+    }
+    cycle.0 += 1;
+    score.0 = 0;
+    combo.count = 0;
+    current_level.0 = 0;
+    energy.0 = 1.0;
+    next_state.set(GameState::Playing);
+}
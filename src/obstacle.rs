@@ -0,0 +1,118 @@
+use std::f32::consts::SQRT_2;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::level::{CurrentLevel, Levels};
+use crate::{debug, pause, Ball, BallCollision, Collider, GameEntity, GameState, Serving, Velocity};
+
+const OBSTACLE_COLOR: Color = Color::srgb(0.35, 0.8, 0.75);
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObstacleShape {
+    Circle,
+    /// A square standing on one corner.
+    Diamond,
+}
+
+/// A bumper placed by a level file.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ObstacleData {
+    pub shape: ObstacleShape,
+    pub position: (f32, f32),
+    /// Radius for circles, center-to-corner distance for diamonds.
+    pub size: f32,
+}
+
+#[derive(Component)]
+pub struct Obstacle {
+    pub shape: ObstacleShape,
+    pub size: f32,
+}
+
+pub struct ObstaclePlugin;
+
+impl Plugin for ObstaclePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), spawn_obstacles)
+            .add_systems(
+                FixedUpdate,
+                ball_obstacle_collision
+                    .after(BallCollision)
+                    .run_if(in_state(GameState::Playing).and(debug::simulation_running).and(pause::not_paused)),
+            );
+    }
+}
+
+fn spawn_obstacles(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+) {
+    let material = materials.add(OBSTACLE_COLOR);
+    for data in &levels.get(current_level.0).obstacles {
+        let mesh = match data.shape {
+            ObstacleShape::Circle => meshes.add(Circle::new(data.size)),
+            ObstacleShape::Diamond => meshes.add(Rhombus::new(data.size * 2.0, data.size * 2.0)),
+        };
+        commands.spawn((
+            Mesh2d(mesh),
+            MeshMaterial2d(material.clone()),
+            Transform::from_xyz(data.position.0, data.position.1, 0.0),
+            Obstacle {
+                shape: data.shape,
+                size: data.size,
+            },
+            GameEntity,
+        ));
+    }
+}
+
+/// Outward surface normal and penetration depth of a ball of `radius` at `offset` from
+/// the obstacle's center, or `None` if they don't touch.
+fn contact(obstacle: &Obstacle, offset: Vec2, radius: f32) -> Option<(Vec2, f32)> {
+    match obstacle.shape {
+        ObstacleShape::Circle => {
+            let distance = offset.length();
+            let depth = obstacle.size + radius - distance;
+            (depth > 0.0).then(|| (offset.try_normalize().unwrap_or(Vec2::Y), depth))
+        }
+        ObstacleShape::Diamond => {
+            // Distance to the nearest face of |x| + |y| = size, with the face normal
+            // pointing into whichever quadrant the ball is in.
+            let distance = (offset.x.abs() + offset.y.abs() - obstacle.size) / SQRT_2;
+            let depth = radius - distance;
+            let normal = Vec2::new(
+                if offset.x < 0.0 { -1.0 } else { 1.0 },
+                if offset.y < 0.0 { -1.0 } else { 1.0 },
+            ) / SQRT_2;
+            (depth > 0.0).then_some((normal, depth))
+        }
+    }
+}
+
+fn ball_obstacle_collision(
+    obstacle_query: Query<(&Transform, &Obstacle), Without<Ball>>,
+    mut ball_query: Query<(&mut Transform, &mut Velocity, &Collider), (With<Ball>, Without<Serving>)>,
+) {
+    for (mut ball_transform, mut velocity, collider) in &mut ball_query {
+        let radius = collider.size.x / 2.0;
+        for (obstacle_transform, obstacle) in &obstacle_query {
+            let offset =
+                ball_transform.translation.truncate() - obstacle_transform.translation.truncate();
+            let Some((normal, depth)) = contact(obstacle, offset, radius) else {
+                continue;
+            };
+
+            ball_transform.translation += (normal * depth).extend(0.0);
+            // Only reflect if the ball is still heading into the surface, so a ball that
+            // was just pushed out doesn't get flipped back in.
+            let approach = velocity.0.dot(normal);
+            if approach < 0.0 {
+                velocity.0 -= 2.0 * approach * normal;
+            }
+        }
+    }
+}
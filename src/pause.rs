@@ -0,0 +1,399 @@
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+use bevy::window::{WindowCloseRequested, WindowFocused};
+
+use crate::controls::{self, ActiveGamepad, ControlScheme, GameplayInput};
+use crate::debug::FrameStep;
+use crate::display::ARENA;
+use crate::menu;
+use crate::photo_mode::PhotoMode;
+use crate::settings::Settings;
+use crate::trajectory;
+use crate::{Ball, Collider, GameState, SideWall, Velocity, BALL_COLLISION_MARGIN};
+
+const PAUSED_LABEL: &str = "Paused\nQ: Quit   Ctrl+O: Photo mode";
+const QUIT_LABEL: &str = "Quit the game?\nThis run will be lost.\nY: Quit   N: Back";
+const CONTROLLER_LOST_LABEL: &str = "Controller disconnected\nReconnect it, or press Ctrl+I to switch controls";
+/// Seconds between leaving the pause screen and the ball moving again.
+const RESUME_COUNTDOWN: f32 = 3.0;
+/// How far ahead the ball's path is previewed during the countdown, in seconds of travel.
+const RESUME_PREVIEW_SECONDS: f32 = 1.0;
+const RESUME_PREVIEW_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.25);
+
+/// Whether the player has paused the game with Escape or the gamepad's Start button.
+#[derive(Resource, Default)]
+pub struct Paused(pub bool);
+
+/// Set while the game is paused because the gamepad in use was unplugged. The pause
+/// can't be dismissed by hand until a pad is back or the controls are switched.
+#[derive(Resource, Default)]
+struct ControllerLost(bool);
+
+/// Set while the pause screen is asking whether to quit, either picked from it or because
+/// the window was closed mid-run.
+#[derive(Resource, Default)]
+struct QuitConfirm(bool);
+
+/// Real-time seconds left before play resumes, while counting down out of a pause.
+#[derive(Resource, Default)]
+struct ResumeCountdown(Option<f32>);
+
+#[derive(Component)]
+pub struct PauseScreen;
+
+#[derive(Component)]
+struct CountdownText;
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Paused>()
+            .init_resource::<ControllerLost>()
+            .init_resource::<ResumeCountdown>()
+            .init_resource::<QuitConfirm>()
+            .add_systems(
+                Update,
+                (
+                    (
+                        toggle_pause,
+                        pause_on_focus_loss,
+                        pause_on_controller_loss.after(controls::bind_gamepad),
+                    ),
+                    (request_quit, confirm_quit).chain(),
+                    tick_resume_countdown,
+                    draw_resume_preview.run_if(|countdown: Res<ResumeCountdown>| countdown.0.is_some()),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(Update, quit_on_close.run_if(not(in_state(GameState::Playing))))
+            .add_systems(OnExit(GameState::Playing), unpause);
+    }
+}
+
+/// Run condition for gameplay systems that stop while the game is paused.
+pub fn not_paused(paused: Res<Paused>) -> bool {
+    !paused.0
+}
+
+/// Virtual time stays paused while either the player or the debug frame stepper wants it.
+pub fn sync_virtual_time(paused: &Paused, frame_step: &FrameStep, virtual_time: &mut Time<Virtual>) {
+    if paused.0 || frame_step.paused {
+        virtual_time.pause();
+    } else {
+        virtual_time.unpause();
+    }
+}
+
+// Shared by every way of pausing, so the overlay and virtual time never disagree with
+// the `Paused` flag.
+fn set_paused(
+    pause: bool,
+    label: &str,
+    paused: &mut Paused,
+    countdown: &mut ResumeCountdown,
+    frame_step: &FrameStep,
+    virtual_time: &mut Time<Virtual>,
+    commands: &mut Commands,
+    screen_query: &Query<Entity, With<PauseScreen>>,
+) {
+    countdown.0 = None;
+    paused.0 = pause;
+    sync_virtual_time(paused, frame_step, virtual_time);
+
+    for entity in screen_query {
+        commands.entity(entity).despawn();
+    }
+    if pause {
+        commands.spawn((
+            Text2d(label.to_string()),
+            TextFont {
+                font_size: 48.0,
+                ..default()
+            },
+            Transform::from_xyz(0.0, 0.0, 5.0),
+            PauseScreen,
+        ));
+    }
+}
+
+// The game stays paused through the countdown; only the overlay changes.
+fn start_countdown(
+    countdown: &mut ResumeCountdown,
+    commands: &mut Commands,
+    screen_query: &Query<Entity, With<PauseScreen>>,
+) {
+    countdown.0 = Some(RESUME_COUNTDOWN);
+    for entity in screen_query {
+        commands.entity(entity).despawn();
+    }
+    commands.spawn((
+        Text2d(countdown_label(RESUME_COUNTDOWN)),
+        TextFont {
+            font_size: 72.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 5.0),
+        CountdownText,
+        PauseScreen,
+    ));
+}
+
+fn countdown_label(remaining: f32) -> String {
+    (remaining.ceil() as u32).max(1).to_string()
+}
+
+fn toggle_pause(
+    controls: GameplayInput,
+    controller_lost: Res<ControllerLost>,
+    quit_confirm: Res<QuitConfirm>,
+    photo_mode: Res<PhotoMode>,
+    mut paused: ResMut<Paused>,
+    mut countdown: ResMut<ResumeCountdown>,
+    frame_step: Res<FrameStep>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut commands: Commands,
+    screen_query: Query<Entity, With<PauseScreen>>,
+) {
+    // Backing out of the quit prompt is handled by `confirm_quit`, and photo mode has to
+    // be left before the game can resume.
+    if !controls.pause_just_pressed() || controller_lost.0 || quit_confirm.0 || photo_mode.active {
+        return;
+    }
+    // Pressing pause during the countdown goes straight back to the pause screen.
+    if paused.0 && countdown.0.is_none() {
+        start_countdown(&mut countdown, &mut commands, &screen_query);
+    } else {
+        set_paused(
+            true,
+            PAUSED_LABEL,
+            &mut paused,
+            &mut countdown,
+            &frame_step,
+            &mut virtual_time,
+            &mut commands,
+            &screen_query,
+        );
+    }
+}
+
+// Alt-tabbing away shouldn't cost a ball. Regaining focus leaves the game paused so the
+// player can pick it back up when they're ready.
+fn pause_on_focus_loss(
+    mut focus_events: MessageReader<WindowFocused>,
+    mut paused: ResMut<Paused>,
+    mut countdown: ResMut<ResumeCountdown>,
+    frame_step: Res<FrameStep>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut commands: Commands,
+    screen_query: Query<Entity, With<PauseScreen>>,
+) {
+    let lost_focus = focus_events.read().any(|event| !event.focused);
+    if lost_focus && (!paused.0 || countdown.0.is_some()) {
+        set_paused(
+            true,
+            PAUSED_LABEL,
+            &mut paused,
+            &mut countdown,
+            &frame_step,
+            &mut virtual_time,
+            &mut commands,
+            &screen_query,
+        );
+    }
+}
+
+// Runs after gamepad binding, so another connected pad taking over doesn't count as
+// losing the controller.
+fn pause_on_controller_loss(
+    mut connection_events: MessageReader<GamepadConnectionEvent>,
+    settings: Res<Settings>,
+    active_gamepad: Res<ActiveGamepad>,
+    mut controller_lost: ResMut<ControllerLost>,
+    mut paused: ResMut<Paused>,
+    mut countdown: ResMut<ResumeCountdown>,
+    frame_step: Res<FrameStep>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut commands: Commands,
+    screen_query: Query<Entity, With<PauseScreen>>,
+) {
+    let disconnected = connection_events
+        .read()
+        .any(|event| matches!(event.connection, GamepadConnection::Disconnected));
+    let without_pad = settings.control_scheme == ControlScheme::Gamepad && active_gamepad.0.is_none();
+
+    if !controller_lost.0 && disconnected && without_pad {
+        controller_lost.0 = true;
+        set_paused(
+            true,
+            CONTROLLER_LOST_LABEL,
+            &mut paused,
+            &mut countdown,
+            &frame_step,
+            &mut virtual_time,
+            &mut commands,
+            &screen_query,
+        );
+    } else if controller_lost.0 && !without_pad {
+        controller_lost.0 = false;
+        start_countdown(&mut countdown, &mut commands, &screen_query);
+    }
+}
+
+// Closing the window from a menu quits straight away; there's no run to lose.
+fn quit_on_close(mut close_events: MessageReader<WindowCloseRequested>, mut exit: MessageWriter<AppExit>) {
+    if close_events.read().count() > 0 {
+        exit.write(AppExit::Success);
+    }
+}
+
+fn request_quit(
+    input: Res<ButtonInput<Key>>,
+    mut close_events: MessageReader<WindowCloseRequested>,
+    controller_lost: Res<ControllerLost>,
+    mut quit_confirm: ResMut<QuitConfirm>,
+    mut paused: ResMut<Paused>,
+    mut countdown: ResMut<ResumeCountdown>,
+    frame_step: Res<FrameStep>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut commands: Commands,
+    screen_query: Query<Entity, With<PauseScreen>>,
+) {
+    let close_requested = close_events.read().count() > 0;
+    let on_pause_screen = paused.0 && countdown.0.is_none() && !controller_lost.0;
+    let quit_picked = on_pause_screen && input.just_pressed(Key::Character("q".into()));
+    if quit_confirm.0 || !(close_requested || quit_picked) {
+        return;
+    }
+    quit_confirm.0 = true;
+    set_paused(
+        true,
+        QUIT_LABEL,
+        &mut paused,
+        &mut countdown,
+        &frame_step,
+        &mut virtual_time,
+        &mut commands,
+        &screen_query,
+    );
+}
+
+// Backing out returns to the pause screen rather than straight into play.
+fn confirm_quit(
+    input: Res<ButtonInput<Key>>,
+    controls: GameplayInput,
+    gamepads: Query<&Gamepad>,
+    mut quit_confirm: ResMut<QuitConfirm>,
+    mut exit: MessageWriter<AppExit>,
+    mut paused: ResMut<Paused>,
+    mut countdown: ResMut<ResumeCountdown>,
+    frame_step: Res<FrameStep>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut commands: Commands,
+    screen_query: Query<Entity, With<PauseScreen>>,
+) {
+    if !quit_confirm.0 {
+        return;
+    }
+    if input.just_pressed(Key::Character("y".into()))
+        || menu::gamepad_just_pressed(&gamepads, GamepadButton::South)
+    {
+        exit.write(AppExit::Success);
+    } else if input.just_pressed(Key::Character("n".into()))
+        || controls.pause_just_pressed()
+        || menu::gamepad_just_pressed(&gamepads, GamepadButton::East)
+    {
+        quit_confirm.0 = false;
+        set_paused(
+            true,
+            PAUSED_LABEL,
+            &mut paused,
+            &mut countdown,
+            &frame_step,
+            &mut virtual_time,
+            &mut commands,
+            &screen_query,
+        );
+    }
+}
+
+fn unpause(
+    mut paused: ResMut<Paused>,
+    mut countdown: ResMut<ResumeCountdown>,
+    mut controller_lost: ResMut<ControllerLost>,
+    mut quit_confirm: ResMut<QuitConfirm>,
+    frame_step: Res<FrameStep>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut commands: Commands,
+    screen_query: Query<Entity, With<PauseScreen>>,
+) {
+    controller_lost.0 = false;
+    quit_confirm.0 = false;
+    set_paused(
+        false,
+        PAUSED_LABEL,
+        &mut paused,
+        &mut countdown,
+        &frame_step,
+        &mut virtual_time,
+        &mut commands,
+        &screen_query,
+    );
+}
+
+// Counts in real time, since virtual time is paused until the countdown finishes.
+fn tick_resume_countdown(
+    time: Res<Time<Real>>,
+    mut paused: ResMut<Paused>,
+    mut countdown: ResMut<ResumeCountdown>,
+    frame_step: Res<FrameStep>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut commands: Commands,
+    screen_query: Query<Entity, With<PauseScreen>>,
+    mut text_query: Query<&mut Text2d, With<CountdownText>>,
+) {
+    let Some(remaining) = countdown.0 else {
+        return;
+    };
+    let remaining = remaining - time.delta_secs();
+    if remaining > 0.0 {
+        countdown.0 = Some(remaining);
+        for mut text in &mut text_query {
+            text.0 = countdown_label(remaining);
+        }
+    } else {
+        set_paused(
+            false,
+            PAUSED_LABEL,
+            &mut paused,
+            &mut countdown,
+            &frame_step,
+            &mut virtual_time,
+            &mut commands,
+            &screen_query,
+        );
+    }
+}
+
+// A faint reminder of where the ball is headed, so play doesn't resume blind.
+fn draw_resume_preview(
+    mut gizmos: Gizmos,
+    wall_query: Query<(&Transform, &SideWall)>,
+    ball_query: Query<(&Transform, &Velocity, &Collider), With<Ball>>,
+) {
+    let (_, right) = crate::side_bounds(wall_query.iter());
+    for (transform, velocity, collider) in &ball_query {
+        let ball_radius = collider.size.x / 2.0 + BALL_COLLISION_MARGIN;
+        let half_extents = Vec2::new(right, ARENA.half_height()) - ball_radius;
+        let path = trajectory::predict_path(
+            transform.translation.truncate(),
+            velocity.0,
+            half_extents,
+            velocity.0.length() * RESUME_PREVIEW_SECONDS,
+            2,
+        );
+        trajectory::draw_dotted(&mut gizmos, &path, RESUME_PREVIEW_COLOR);
+    }
+}
@@ -0,0 +1,283 @@
+use bevy::camera::RenderTarget;
+use bevy::image::BevyDefault;
+use bevy::input::keyboard::Key;
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy::render::view::screenshot::{save_to_disk, Screenshot, ScreenshotCaptured};
+
+use crate::display::GameCamera;
+use crate::force_zone::Streak;
+use crate::pause::{PauseScreen, Paused};
+use crate::screenshot::{self, Toast, SCREENSHOT_DIR};
+use crate::{GameState, WINDOW_HEIGHT, WINDOW_WIDTH};
+
+/// World units per second the camera pans at, at the default zoom.
+const PAN_SPEED: f32 = 600.0;
+/// Zoom change per key press or wheel notch.
+const ZOOM_STEP: f32 = 1.1;
+/// Projection scale limits; below 1.0 is zoomed in.
+const ZOOM_MIN: f32 = 0.25;
+const ZOOM_MAX: f32 = 1.5;
+/// Exported photos are this many times the arena's size in pixels.
+const PHOTO_SCALE: u32 = 2;
+const PHOTO_HINT: &str = "Photo mode\nArrows: Pan   Wheel/+/-: Zoom   E: Effects   Space: Save   Ctrl+O: Exit";
+
+/// A free camera over the paused game, for taking screenshots. Holds what it changed so
+/// leaving puts everything back.
+#[derive(Resource, Default)]
+pub struct PhotoMode {
+    pub active: bool,
+    camera: Option<(Transform, f32)>,
+    hidden: Vec<Entity>,
+    effects_hidden: bool,
+}
+
+#[derive(Component)]
+struct PhotoHint;
+
+pub struct PhotoModePlugin;
+
+impl Plugin for PhotoModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhotoMode>()
+            .add_systems(
+                Update,
+                (
+                    toggle_photo_mode,
+                    (move_camera, toggle_effects, export_photo).run_if(in_photo_mode),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), leave_photo_mode);
+    }
+}
+
+pub fn in_photo_mode(photo_mode: Res<PhotoMode>) -> bool {
+    photo_mode.active
+}
+
+fn projection_scale(projection: &Projection) -> f32 {
+    match projection {
+        Projection::Orthographic(ortho) => ortho.scale,
+        _ => 1.0,
+    }
+}
+
+// Only while paused, so the scene holds still; everything on screen apart from the game
+// itself is hidden for the duration.
+fn toggle_photo_mode(
+    input: Res<ButtonInput<Key>>,
+    paused: Res<Paused>,
+    mut photo_mode: ResMut<PhotoMode>,
+    mut commands: Commands,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<GameCamera>>,
+    mut overlay_query: Query<
+        (Entity, &mut Visibility),
+        (
+            Or<((With<Node>, Without<ChildOf>, Without<Toast>), With<PauseScreen>)>,
+            Without<Streak>,
+        ),
+    >,
+    hint_query: Query<Entity, With<PhotoHint>>,
+    mut config_store: ResMut<GizmoConfigStore>,
+    mut streak_query: Query<&mut Visibility, With<Streak>>,
+) {
+    if !input.pressed(Key::Control) || !input.just_pressed(Key::Character("o".into())) {
+        return;
+    }
+    if photo_mode.active {
+        set_effects_hidden(false, &mut photo_mode, &mut config_store, &mut streak_query);
+        restore(&mut photo_mode, &mut commands, &mut camera_query, &hint_query);
+        return;
+    }
+    if !paused.0 {
+        return;
+    }
+    let Ok((transform, projection)) = camera_query.single() else {
+        return;
+    };
+    photo_mode.active = true;
+    photo_mode.camera = Some((*transform, projection_scale(&projection)));
+    photo_mode.hidden.clear();
+    for (entity, mut visibility) in &mut overlay_query {
+        if *visibility != Visibility::Hidden {
+            *visibility = Visibility::Hidden;
+            photo_mode.hidden.push(entity);
+        }
+    }
+    commands.spawn((
+        Text::new(PHOTO_HINT),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(40.0),
+            top: Val::Px(20.0),
+            ..default()
+        },
+        PhotoHint,
+    ));
+}
+
+fn restore(
+    photo_mode: &mut PhotoMode,
+    commands: &mut Commands,
+    camera_query: &mut Query<(&mut Transform, &mut Projection), With<GameCamera>>,
+    hint_query: &Query<Entity, With<PhotoHint>>,
+) {
+    if let (Some((saved_transform, saved_scale)), Ok((mut transform, mut projection))) =
+        (photo_mode.camera.take(), camera_query.single_mut())
+    {
+        *transform = saved_transform;
+        if let Projection::Orthographic(ortho) = &mut *projection {
+            ortho.scale = saved_scale;
+        }
+    }
+    // Entities despawned in the meantime are simply skipped.
+    for entity in photo_mode.hidden.drain(..) {
+        if let Ok(mut entity) = commands.get_entity(entity) {
+            entity.insert(Visibility::Inherited);
+        }
+    }
+    for entity in hint_query {
+        commands.entity(entity).despawn();
+    }
+    photo_mode.active = false;
+}
+
+fn leave_photo_mode(
+    mut photo_mode: ResMut<PhotoMode>,
+    mut commands: Commands,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<GameCamera>>,
+    hint_query: Query<Entity, With<PhotoHint>>,
+    mut config_store: ResMut<GizmoConfigStore>,
+    mut streak_query: Query<&mut Visibility, With<Streak>>,
+) {
+    if photo_mode.active {
+        set_effects_hidden(false, &mut photo_mode, &mut config_store, &mut streak_query);
+        restore(&mut photo_mode, &mut commands, &mut camera_query, &hint_query);
+    }
+}
+
+// Real time, since virtual time is stopped while paused.
+fn move_camera(
+    time: Res<Time<Real>>,
+    input: Res<ButtonInput<Key>>,
+    mut wheel: MessageReader<MouseWheel>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<GameCamera>>,
+) {
+    let Ok((mut transform, mut projection)) = camera_query.single_mut() else {
+        return;
+    };
+    let Projection::Orthographic(ortho) = &mut *projection else {
+        return;
+    };
+
+    let mut zoom_steps: f32 = wheel.read().map(|event| event.y.signum()).sum();
+    if input.just_pressed(Key::Character("=".into())) || input.just_pressed(Key::Character("+".into())) {
+        zoom_steps += 1.0;
+    }
+    if input.just_pressed(Key::Character("-".into())) {
+        zoom_steps -= 1.0;
+    }
+    ortho.scale = (ortho.scale / ZOOM_STEP.powf(zoom_steps)).clamp(ZOOM_MIN, ZOOM_MAX);
+
+    let mut direction = Vec2::ZERO;
+    if input.pressed(Key::ArrowLeft) {
+        direction.x -= 1.0;
+    }
+    if input.pressed(Key::ArrowRight) {
+        direction.x += 1.0;
+    }
+    if input.pressed(Key::ArrowDown) {
+        direction.y -= 1.0;
+    }
+    if input.pressed(Key::ArrowUp) {
+        direction.y += 1.0;
+    }
+    let step = direction.normalize_or_zero() * PAN_SPEED * ortho.scale * time.delta_secs();
+    let limit = Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT) / 2.0;
+    let position = (transform.translation.truncate() + step).clamp(-limit, limit);
+    transform.translation = position.extend(transform.translation.z);
+}
+
+// Decorative streaks and every gizmo overlay, such as the aim preview, can be left out
+// of the shot.
+fn toggle_effects(
+    input: Res<ButtonInput<Key>>,
+    mut photo_mode: ResMut<PhotoMode>,
+    mut config_store: ResMut<GizmoConfigStore>,
+    mut streak_query: Query<&mut Visibility, With<Streak>>,
+) {
+    if !input.just_pressed(Key::Character("e".into())) || input.pressed(Key::Control) {
+        return;
+    }
+    let hidden = !photo_mode.effects_hidden;
+    set_effects_hidden(hidden, &mut photo_mode, &mut config_store, &mut streak_query);
+}
+
+fn set_effects_hidden(
+    hidden: bool,
+    photo_mode: &mut PhotoMode,
+    config_store: &mut GizmoConfigStore,
+    streak_query: &mut Query<&mut Visibility, With<Streak>>,
+) {
+    if photo_mode.effects_hidden == hidden {
+        return;
+    }
+    photo_mode.effects_hidden = hidden;
+    let visibility = if hidden { Visibility::Hidden } else { Visibility::Inherited };
+    for mut streak_visibility in streak_query.iter_mut() {
+        *streak_visibility = visibility;
+    }
+    for (_, config, _) in config_store.iter_mut() {
+        config.enabled = !hidden;
+    }
+}
+
+// Rendered by a second camera into an image larger than the window, matching the photo
+// camera's framing, then saved like an ordinary screenshot.
+fn export_photo(
+    input: Res<ButtonInput<Key>>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+    camera_query: Query<(&Transform, &Projection), With<GameCamera>>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !input.just_pressed(Key::Space) {
+        return;
+    }
+    let Ok((transform, projection)) = camera_query.single() else {
+        return;
+    };
+    if let Err(err) = std::fs::create_dir_all(SCREENSHOT_DIR) {
+        error!("failed to create {SCREENSHOT_DIR}: {err}");
+        return;
+    }
+
+    let size = UVec2::new(WINDOW_WIDTH as u32, WINDOW_HEIGHT as u32) * PHOTO_SCALE;
+    let target = images.add(Image::new_target_texture(size.x, size.y, TextureFormat::bevy_default()));
+    let camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                target: RenderTarget::Image(target.clone().into()),
+                ..default()
+            },
+            projection.clone(),
+            *transform,
+        ))
+        .id();
+    let path = format!("{SCREENSHOT_DIR}/pong-photo-{}.png", screenshot::timestamp());
+    commands
+        .spawn(Screenshot::image(target))
+        .observe(save_to_disk(path.clone()))
+        .observe(move |_: On<ScreenshotCaptured>, mut commands: Commands| {
+            commands.entity(camera).despawn();
+        });
+    screenshot::show_toast(&mut commands, &toast_query, format!("Saved {path}"));
+}
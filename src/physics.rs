@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude as rapier;
+use bevy_rapier2d::prelude::{NoUserData, RapierPhysicsPlugin};
+
+use crate::bullet_time::BulletTime;
+use crate::collision::{BallHitBlock, BallHitPaddle, BallHitWall, Side};
+use crate::config::GameConfig;
+use crate::level::{CurrentLevel, Levels};
+use crate::{
+    curve_with_spin, paddle_bounce, skew_wall_bounce, Ball, BallCollision, Block, Collider, Paddle,
+    PaddleVelocity, Serving, Spin, Velocity,
+};
+
+const PIXELS_PER_METER: f32 = 100.0;
+
+/// Rapier rigid bodies in place of the built-in swept collisions, only built with the
+/// `rapier` feature.
+///
+/// Rapier resolves the contacts; the game's `Velocity` stays the source of truth and is
+/// copied in before each step and back out after it. Contacts are turned into the usual
+/// hit messages, so scoring, speed and rumble work the same with either backend.
+/// Bullet time doesn't slow the ball with this backend.
+pub struct RapierBackendPlugin;
+
+impl Plugin for RapierBackendPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(PIXELS_PER_METER))
+            .add_systems(
+                Update,
+                (
+                    attach_bodies,
+                    attach_ball_body,
+                    toggle_serving_body,
+                    send_hit_messages.in_set(BallCollision),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    push_velocity.before(rapier::PhysicsSet::SyncBackend),
+                    pull_velocity.after(rapier::PhysicsSet::Writeback),
+                ),
+            );
+    }
+}
+
+// The paddle is moved by hand, so it's kinematic; blocks and walls never move during a
+// contact. Resized colliders (moving walls, paddle width) get a fresh shape.
+fn attach_bodies(
+    mut commands: Commands,
+    collider_query: Query<(Entity, &Collider, Has<Paddle>), (Changed<Collider>, Without<Ball>)>,
+) {
+    for (entity, collider, is_paddle) in &collider_query {
+        let half = collider.size / 2.0;
+        let body = if is_paddle {
+            rapier::RigidBody::KinematicPositionBased
+        } else {
+            rapier::RigidBody::Fixed
+        };
+        commands
+            .entity(entity)
+            .insert((body, rapier::Collider::cuboid(half.x, half.y)));
+    }
+}
+
+fn attach_ball_body(
+    mut commands: Commands,
+    ball_query: Query<(Entity, &Collider), (With<Ball>, Changed<Collider>)>,
+) {
+    for (entity, collider) in &ball_query {
+        commands.entity(entity).insert((
+            rapier::RigidBody::Dynamic,
+            rapier::Collider::ball(collider.size.x / 2.0),
+            rapier::Restitution {
+                coefficient: 1.0,
+                combine_rule: rapier::CoefficientCombineRule::Max,
+            },
+            rapier::Friction::coefficient(0.0),
+            rapier::GravityScale(0.0),
+            rapier::LockedAxes::ROTATION_LOCKED,
+            rapier::Ccd::enabled(),
+            rapier::ActiveEvents::COLLISION_EVENTS,
+            rapier::Velocity::zero(),
+        ));
+    }
+}
+
+// A ball waiting to be served rides on the paddle, so it's taken out of the simulation
+// until it's launched.
+fn toggle_serving_body(
+    mut commands: Commands,
+    served_query: Query<Entity, (With<Ball>, Added<Serving>)>,
+    mut launched: RemovedComponents<Serving>,
+) {
+    for entity in &served_query {
+        commands.entity(entity).insert(rapier::RigidBodyDisabled);
+    }
+    for entity in launched.read() {
+        if let Ok(mut entity) = commands.get_entity(entity) {
+            entity.remove::<rapier::RigidBodyDisabled>();
+        }
+    }
+}
+
+fn push_velocity(
+    time: Res<Time>,
+    bullet_time: Res<BulletTime>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+    mut ball_query: Query<
+        (&mut Velocity, &mut Spin, &mut rapier::Velocity),
+        (With<Ball>, Without<Serving>),
+    >,
+) {
+    let dt = time.delta_secs() * bullet_time.ball_time_scale();
+    let curveball = levels.get(current_level.0).curveball;
+    for (mut velocity, mut spin, mut body_velocity) in &mut ball_query {
+        curve_with_spin(&mut velocity, &mut spin, dt, curveball);
+        body_velocity.linvel = velocity.0;
+    }
+}
+
+fn pull_velocity(
+    mut ball_query: Query<(&mut Velocity, &rapier::Velocity), (With<Ball>, Without<Serving>)>,
+) {
+    for (mut velocity, body_velocity) in &mut ball_query {
+        velocity.0 = body_velocity.linvel;
+    }
+}
+
+// Rapier doesn't report which face was struck, so it's worked out from where the ball
+// sits relative to the box, scaled by the box's size.
+fn side_struck(ball: Vec2, target: &Transform, collider: &Collider) -> Side {
+    let offset = (ball - target.translation.truncate()) / (collider.size / 2.0);
+    Side::from_normal(offset)
+}
+
+fn send_hit_messages(
+    mut collision_events: MessageReader<rapier::CollisionEvent>,
+    mut ball_query: Query<(&Transform, &mut Velocity, &mut Spin), With<Ball>>,
+    target_query: Query<
+        (&Transform, &Collider, Option<&PaddleVelocity>, Has<Block>),
+        Without<Ball>,
+    >,
+    config: Res<GameConfig>,
+    mut paddle_hits: MessageWriter<BallHitPaddle>,
+    mut wall_hits: MessageWriter<BallHitWall>,
+    mut block_hits: MessageWriter<BallHitBlock>,
+) {
+    let _span = info_span!("send_hit_messages").entered();
+    for event in collision_events.read() {
+        let rapier::CollisionEvent::Started(first, second, _) = *event else {
+            continue;
+        };
+        let (ball, target) = if ball_query.contains(first) {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        let Ok((ball_transform, mut velocity, mut spin)) = ball_query.get_mut(ball) else {
+            continue;
+        };
+        let Ok((target_transform, collider, paddle_velocity, is_block)) = target_query.get(target) else {
+            continue;
+        };
+
+        let position = ball_transform.translation.truncate();
+        let side = side_struck(position, target_transform, collider);
+        let speed = velocity.0.length();
+        if let Some(paddle_velocity) = paddle_velocity {
+            paddle_hits.write(BallHitPaddle { ball, side, speed });
+            let normal_y = match side {
+                Side::Top => 1.0,
+                Side::Bottom => -1.0,
+                Side::Left | Side::Right => continue,
+            };
+            (velocity.0, spin.0) = paddle_bounce(
+                velocity.0,
+                position.x,
+                collider.aabb(target_transform.translation.truncate()),
+                paddle_velocity,
+                normal_y,
+                config.paddle_curvature,
+            );
+        } else if is_block {
+            block_hits.write(BallHitBlock {
+                ball,
+                entity: target,
+                side,
+                speed,
+            });
+        } else {
+            // Walls are named by where they are, which is the opposite of the face struck.
+            let wall = match side {
+                Side::Top => Side::Bottom,
+                Side::Bottom => Side::Top,
+                Side::Left => Side::Right,
+                Side::Right => Side::Left,
+            };
+            if wall != Side::Bottom {
+                skew_wall_bounce(&mut velocity, &mut spin);
+            }
+            wall_hits.write(BallHitWall { ball, side: wall });
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+/// Hidden entities kept around for reuse by short-lived effects marked with `T`, so
+/// busy effects don't spawn and despawn entities every frame.
+///
+/// Released entities lose their `T` and are hidden, so effect systems querying for `T`
+/// skip them until they're handed out again.
+#[derive(Resource)]
+pub struct EntityPool<T: Component> {
+    free: Vec<Entity>,
+    marker: PhantomData<T>,
+}
+
+impl<T: Component> Default for EntityPool<T> {
+    fn default() -> Self {
+        EntityPool {
+            free: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Component> EntityPool<T> {
+    /// Reuses a released entity for `bundle`, or spawns a new one if none are free.
+    pub fn spawn(&mut self, commands: &mut Commands, bundle: impl Bundle) -> Entity {
+        match self.free.pop() {
+            Some(entity) => {
+                commands.entity(entity).insert((bundle, Visibility::Inherited));
+                entity
+            }
+            None => commands.spawn(bundle).id(),
+        }
+    }
+
+    pub fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        commands.entity(entity).remove::<T>().insert(Visibility::Hidden);
+        self.free.push(entity);
+    }
+}
+
+/// Forgets every pooled entity; add it wherever the pooled entities get despawned, such
+/// as entering a new level.
+pub fn clear_pool<T: Component>(mut pool: ResMut<EntityPool<T>>) {
+    pool.free.clear();
+}
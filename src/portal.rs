@@ -0,0 +1,136 @@
+use bevy::math::bounding::{BoundingCircle, IntersectsVolume};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::accessibility;
+use crate::interpolation::Interpolated;
+use crate::level::{CurrentLevel, Levels};
+use crate::{debug, pause, Ball, BallCollision, Collider, GameEntity, GameState, Serving};
+
+const PORTAL_RADIUS: f32 = 32.0;
+const PORTAL_RING_WIDTH: f32 = 6.0;
+/// Seconds after teleporting during which the ball ignores portals.
+const PORTAL_COOLDOWN: f32 = 0.5;
+/// Radians per second the portal's swirl rotates.
+const SWIRL_SPEED: f32 = 3.0;
+const SWIRL_DOTS: usize = 3;
+
+const ENTRY_COLOR: Color = Color::srgb(0.3, 0.6, 1.0);
+const EXIT_COLOR: Color = Color::srgb(1.0, 0.55, 0.2);
+
+/// Two linked portals from a level file. Portals work in both directions.
+#[derive(Deserialize, Clone, Debug)]
+pub struct PortalPairData {
+    pub a: (f32, f32),
+    pub b: (f32, f32),
+}
+
+#[derive(Component)]
+struct Portal {
+    exit: Vec2,
+}
+
+/// Keeps a ball that just came out of a portal from going straight back in.
+#[derive(Component)]
+struct PortalCooldown(f32);
+
+pub struct PortalPlugin;
+
+impl Plugin for PortalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), spawn_portals)
+            .add_systems(
+                Update,
+                swirl_portals.run_if(in_state(GameState::Playing).and(accessibility::decorative_effects)),
+            )
+            .add_systems(
+                FixedUpdate,
+                (tick_portal_cooldown, teleport_balls)
+                    .chain()
+                    .after(BallCollision)
+                    .run_if(in_state(GameState::Playing).and(debug::simulation_running).and(pause::not_paused)),
+            );
+    }
+}
+
+fn spawn_portals(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+) {
+    let ring = meshes.add(Annulus::new(PORTAL_RADIUS - PORTAL_RING_WIDTH, PORTAL_RADIUS));
+    let dot = meshes.add(Circle::new(PORTAL_RING_WIDTH * 0.75));
+
+    for pair in &levels.get(current_level.0).portals {
+        let a = Vec2::new(pair.a.0, pair.a.1);
+        let b = Vec2::new(pair.b.0, pair.b.1);
+        for (position, exit, color) in [(a, b, ENTRY_COLOR), (b, a, EXIT_COLOR)] {
+            let material = materials.add(color);
+            commands
+                .spawn((
+                    Mesh2d(ring.clone()),
+                    MeshMaterial2d(material.clone()),
+                    Transform::from_translation(position.extend(0.5)),
+                    Portal { exit },
+                    GameEntity,
+                ))
+                .with_children(|parent| {
+                    for i in 0..SWIRL_DOTS {
+                        let angle = i as f32 / SWIRL_DOTS as f32 * std::f32::consts::TAU;
+                        let offset = Vec2::from_angle(angle) * (PORTAL_RADIUS * 0.5);
+                        parent.spawn((
+                            Mesh2d(dot.clone()),
+                            MeshMaterial2d(material.clone()),
+                            Transform::from_translation(offset.extend(0.1)),
+                        ));
+                    }
+                });
+        }
+    }
+}
+
+fn teleport_balls(
+    mut commands: Commands,
+    portal_query: Query<(&Transform, &Portal), Without<Ball>>,
+    mut ball_query: Query<
+        (Entity, &mut Transform, &mut Interpolated, &Collider),
+        (With<Ball>, Without<Serving>, Without<PortalCooldown>),
+    >,
+) {
+    for (ball, mut ball_transform, mut interpolated, collider) in &mut ball_query {
+        // The ball has to be well inside the ring before it's pulled through.
+        let ball_core = BoundingCircle::new(ball_transform.translation.truncate(), collider.size.x / 4.0);
+        let entered = portal_query.iter().find(|(portal_transform, _)| {
+            ball_core.intersects(&BoundingCircle::new(portal_transform.translation.truncate(), PORTAL_RADIUS))
+        });
+        if let Some((_, portal)) = entered {
+            // Velocity is untouched, so the ball leaves the exit with the same speed and
+            // heading it went in with.
+            ball_transform.translation.x = portal.exit.x;
+            ball_transform.translation.y = portal.exit.y;
+            interpolated.teleport(portal.exit);
+            commands.entity(ball).insert(PortalCooldown(PORTAL_COOLDOWN));
+        }
+    }
+}
+
+fn tick_portal_cooldown(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut ball_query: Query<(Entity, &mut PortalCooldown)>,
+) {
+    for (entity, mut cooldown) in &mut ball_query {
+        cooldown.0 -= time.delta_secs();
+        if cooldown.0 <= 0.0 {
+            commands.entity(entity).remove::<PortalCooldown>();
+        }
+    }
+}
+
+fn swirl_portals(time: Res<Time>, mut portal_query: Query<&mut Transform, With<Portal>>) {
+    for mut transform in &mut portal_query {
+        transform.rotate_z(SWIRL_SPEED * time.delta_secs());
+    }
+}
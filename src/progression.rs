@@ -0,0 +1,234 @@
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::cheats::Cheats;
+use crate::scoring::GameScore;
+use crate::stats::RunStats;
+use crate::GameState;
+
+const PROGRESS_PATH: &str = "progress.ron";
+/// XP to go from level 1 to 2; each level after needs this much more than the one before.
+const XP_LEVEL_STEP: u32 = 100;
+/// Points of score worth one XP.
+const SCORE_PER_XP: u32 = 10;
+/// Seconds the results bar takes to fill up to the new total.
+const XP_BAR_FILL_TIME: f32 = 1.5;
+
+const XP_BAR_Y: f32 = -300.0;
+const XP_BAR_SIZE: Vec2 = Vec2::new(400.0, 14.0);
+const XP_BAR_BACKGROUND: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+const XP_BAR_FILL: Color = Color::srgb(0.4, 0.85, 0.45);
+
+/// Experience earned over every run played, which levels up the player and unlocks
+/// rewards along the way.
+#[derive(Resource, Serialize, Deserialize, Default, Debug, PartialEq)]
+#[serde(default)]
+pub struct PlayerProgress {
+    pub xp: u32,
+}
+
+impl PlayerProgress {
+    fn load() -> Self {
+        fs::read_to_string(PROGRESS_PATH)
+            .ok()
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        let contents = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(err) => {
+                error!("failed to serialize progress: {err}");
+                return;
+            }
+        };
+        if let Err(err) = fs::write(PROGRESS_PATH, contents) {
+            error!("failed to write {PROGRESS_PATH}: {err}");
+        }
+    }
+
+    pub fn level(&self) -> u32 {
+        level_at(self.xp as f32).0
+    }
+}
+
+/// Player level at `xp`, and how far into that level it is from 0.0 to 1.0.
+fn level_at(xp: f32) -> (u32, f32) {
+    let mut level = 1;
+    let mut floor = 0.0;
+    loop {
+        let needed = (XP_LEVEL_STEP * level) as f32;
+        if xp < floor + needed {
+            return (level, (xp - floor) / needed);
+        }
+        floor += needed;
+        level += 1;
+    }
+}
+
+/// XP total before and after the run that just ended, for the results bar to fill between.
+#[derive(Resource, Default)]
+struct XpAward {
+    from: u32,
+    to: u32,
+    elapsed: f32,
+}
+
+#[derive(Component)]
+struct XpBar;
+
+#[derive(Component)]
+struct XpBarFill;
+
+#[derive(Component)]
+struct XpBarText;
+
+pub struct ProgressionPlugin;
+
+impl Plugin for ProgressionPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PlayerProgress::load())
+            .init_resource::<XpAward>()
+            .add_systems(OnEnter(GameState::GameWon), (award_xp, spawn_xp_bar).chain())
+            .add_systems(OnEnter(GameState::GameOver), (award_xp, spawn_xp_bar).chain())
+            .add_systems(
+                Update,
+                fill_xp_bar.run_if(in_state(GameState::GameWon).or(in_state(GameState::GameOver))),
+            )
+            .add_systems(OnExit(GameState::GameWon), despawn_xp_bar)
+            .add_systems(OnExit(GameState::GameOver), despawn_xp_bar);
+    }
+}
+
+fn award_xp(
+    score: Res<GameScore>,
+    stats: Res<RunStats>,
+    cheats: Res<Cheats>,
+    mut progress: ResMut<PlayerProgress>,
+    mut award: ResMut<XpAward>,
+) {
+    // Cheated runs still show the bar, just without anything added to it.
+    let earned = if cheats.any() {
+        0
+    } else {
+        score.0 / SCORE_PER_XP + stats.blocks_broken
+    };
+    *award = XpAward {
+        from: progress.xp,
+        to: progress.xp + earned,
+        elapsed: 0.0,
+    };
+    progress.xp = award.to;
+    progress.save();
+}
+
+fn xp_bar_label(xp: f32, award: &XpAward) -> String {
+    format!("Level {}   +{} XP", level_at(xp).0, award.to - award.from)
+}
+
+/// The bar's fill as `fraction` of the way full, grown from its left end.
+fn xp_bar_fill(fraction: f32) -> (Vec2, f32) {
+    let width = XP_BAR_SIZE.x * fraction;
+    (Vec2::new(width, XP_BAR_SIZE.y), (width - XP_BAR_SIZE.x) / 2.0)
+}
+
+fn spawn_xp_bar(mut commands: Commands, award: Res<XpAward>) {
+    let (size, x) = xp_bar_fill(level_at(award.from as f32).1);
+    commands.spawn((
+        Text2d(xp_bar_label(award.from as f32, &award)),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, XP_BAR_Y + 25.0, 2.0),
+        XpBarText,
+        XpBar,
+    ));
+    commands.spawn((
+        Sprite {
+            color: XP_BAR_BACKGROUND,
+            custom_size: Some(XP_BAR_SIZE),
+            ..default()
+        },
+        Transform::from_xyz(0.0, XP_BAR_Y, 2.0),
+        XpBar,
+    ));
+    commands.spawn((
+        Sprite {
+            color: XP_BAR_FILL,
+            custom_size: Some(size),
+            ..default()
+        },
+        Transform::from_xyz(x, XP_BAR_Y, 2.5),
+        XpBarFill,
+        XpBar,
+    ));
+}
+
+// Counts up through the XP earned, wrapping the bar back to empty on each level up.
+fn fill_xp_bar(
+    time: Res<Time>,
+    mut award: ResMut<XpAward>,
+    mut fill_query: Query<(&mut Sprite, &mut Transform), With<XpBarFill>>,
+    mut text_query: Query<&mut Text2d, With<XpBarText>>,
+) {
+    if award.elapsed >= XP_BAR_FILL_TIME {
+        return;
+    }
+    award.elapsed = (award.elapsed + time.delta_secs()).min(XP_BAR_FILL_TIME);
+    let t = award.elapsed / XP_BAR_FILL_TIME;
+    let xp = award.from as f32 + (award.to - award.from) as f32 * t;
+    let (size, x) = xp_bar_fill(level_at(xp).1);
+    for (mut sprite, mut transform) in &mut fill_query {
+        sprite.custom_size = Some(size);
+        transform.translation.x = x;
+    }
+    for mut text in &mut text_query {
+        text.0 = xp_bar_label(xp, &award);
+    }
+}
+
+fn despawn_xp_bar(mut commands: Commands, bar_query: Query<Entity, With<XpBar>>) {
+    for entity in &bar_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_at_level_one() {
+        assert_eq!(level_at(0.0), (1, 0.0));
+    }
+
+    #[test]
+    fn levels_up_exactly_on_each_threshold() {
+        assert_eq!(level_at(100.0), (2, 0.0));
+        assert_eq!(level_at(300.0), (3, 0.0));
+        assert_eq!(level_at(600.0), (4, 0.0));
+    }
+
+    #[test]
+    fn one_below_a_threshold_stays_on_the_level() {
+        let (level, progress) = level_at(99.0);
+        assert_eq!(level, 1);
+        assert!((progress - 0.99).abs() < 1e-6);
+        let (level, progress) = level_at(299.0);
+        assert_eq!(level, 2);
+        assert!((progress - 0.995).abs() < 1e-6);
+    }
+
+    #[test]
+    fn most_xp_a_save_can_hold() {
+        let (level, progress) = level_at(u32::MAX as f32);
+        let threshold = |level: u64| XP_LEVEL_STEP as u64 * (level - 1) * level / 2;
+        assert!(threshold(level as u64) <= u32::MAX as u64);
+        assert!(threshold(level as u64 + 1) > u32::MAX as u64);
+        assert!((0.0..1.0).contains(&progress));
+    }
+}
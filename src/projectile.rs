@@ -0,0 +1,130 @@
+use bevy::math::bounding::IntersectsVolume;
+use bevy::prelude::*;
+
+use crate::display::ARENA;
+use crate::scoring::GameScore;
+use crate::split_paddle::{self, SplitPaddle};
+use crate::{debug, pause, Collider, GameEntity, GameState, Paddle, PaddleWidth};
+
+pub const PROJECTILE_SIZE: Vec2 = Vec2::new(10.0, 18.0);
+pub const PROJECTILE_COLOR: Color = Color::srgb(1.0, 0.3, 0.25);
+/// Falling speed for projectiles fired without one, slow enough to see coming.
+pub const DEFAULT_PROJECTILE_SPEED: f32 = 220.0;
+
+/// Share of its width the paddle keeps while shrunk by a hit.
+const SHRINK_SCALE: f32 = 0.6;
+const SHRINK_DURATION: f32 = 4.0;
+
+/// What a projectile does to the paddle it hits.
+#[derive(Clone, Copy, Debug)]
+pub enum ProjectileEffect {
+    /// Shrinks the paddle for a few seconds.
+    Shrink,
+    /// Takes points off the score.
+    CostScore(u32),
+}
+
+/// Something hostile moving through the arena that the paddle has to dodge. Unlike the
+/// ball, it passes straight through blocks and walls.
+#[derive(Component)]
+pub struct Projectile {
+    velocity: Vec2,
+    effect: ProjectileEffect,
+}
+
+/// Seconds left before a paddle shrunk by a projectile goes back to its full width.
+#[derive(Component)]
+struct Shrunk(f32);
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (move_projectiles, hit_paddle, shrink_paddle)
+                .chain()
+                .run_if(in_state(GameState::Playing).and(debug::simulation_running).and(pause::not_paused)),
+        );
+    }
+}
+
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    position: Vec2,
+    velocity: Vec2,
+    size: Vec2,
+    color: Color,
+    effect: ProjectileEffect,
+) {
+    commands.spawn((
+        Sprite {
+            color,
+            custom_size: Some(size),
+            ..default()
+        },
+        Transform::from_translation(position.extend(1.0)),
+        Projectile { velocity, effect },
+        Collider { size },
+        GameEntity,
+    ));
+}
+
+fn move_projectiles(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut projectile_query: Query<(Entity, &mut Transform, &Projectile, &Collider)>,
+) {
+    for (entity, mut transform, projectile, collider) in &mut projectile_query {
+        transform.translation += (projectile.velocity * time.delta_secs()).extend(0.0);
+        let position = transform.translation.truncate();
+        if position.y < -ARENA.half_height() - collider.size.y || position.x.abs() > ARENA.half_width() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn hit_paddle(
+    mut commands: Commands,
+    mut score: ResMut<GameScore>,
+    projectile_query: Query<(Entity, &Transform, &Collider, &Projectile)>,
+    paddle_query: Query<(Entity, &Transform, &Collider, Has<SplitPaddle>), (With<Paddle>, Without<Projectile>)>,
+) {
+    for (entity, projectile_transform, projectile_collider, projectile) in &projectile_query {
+        let projectile_box = projectile_collider.aabb(projectile_transform.translation.truncate());
+        let hit = paddle_query.iter().find(|(_, transform, collider, split)| {
+            split_paddle::parts(transform.translation.truncate(), collider, *split)
+                .iter()
+                .any(|part| part.intersects(&projectile_box))
+        });
+        let Some((paddle, _, _, _)) = hit else {
+            continue;
+        };
+        commands.entity(entity).despawn();
+        match projectile.effect {
+            ProjectileEffect::Shrink => {
+                commands.entity(paddle).insert(Shrunk(SHRINK_DURATION));
+            }
+            ProjectileEffect::CostScore(points) => score.0 = score.0.saturating_sub(points),
+        }
+    }
+}
+
+fn shrink_paddle(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut paddle_query: Query<(Entity, &mut Shrunk, &mut PaddleWidth), With<Paddle>>,
+) {
+    for (entity, mut shrunk, mut width) in &mut paddle_query {
+        shrunk.0 -= time.delta_secs();
+        let shrink = if shrunk.0 > 0.0 {
+            SHRINK_SCALE
+        } else {
+            commands.entity(entity).remove::<Shrunk>();
+            1.0
+        };
+        if width.shrink != shrink {
+            width.shrink = shrink;
+        }
+    }
+}
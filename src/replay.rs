@@ -0,0 +1,486 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use serde::{Deserialize, Serialize};
+
+use crate::cheats;
+use crate::config::Difficulty;
+use crate::controls::{GameplayInput, PlayerId};
+use crate::display::GameCamera;
+use crate::game_over::Lives;
+use crate::level::{CurrentLevel, LevelClock, Levels};
+use crate::menu::{MenuActivated, MenuItem};
+use crate::scoring::{Combo, GameScore};
+use crate::screenshot::{self, Toast};
+use crate::seed;
+use crate::serve::{self, ServeDeal};
+use crate::settings::Settings;
+use crate::tutorial::Tutorial;
+use crate::{Ball, BumpEnergy, GameState, Paddle, SplashScreen, StartButton};
+
+const REPLAY_DIR: &str = "replays";
+/// Bumped whenever `Replay` changes shape; older files are listed but can't be played.
+const REPLAY_VERSION: u32 = 2;
+/// Most recent replays shown in the browser.
+const BROWSER_ROWS: usize = 8;
+
+const BROWSER_TOP_Y: f32 = 220.0;
+const BROWSER_ROW_SPACING: f32 = 45.0;
+const BROWSER_ROW_SIZE: Vec2 = Vec2::new(620.0, 36.0);
+
+/// Everything about a run that has to match for its inputs to play back the same way.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReplayHeader {
+    pub version: u32,
+    /// Version of the game that recorded it, for reference when a replay stops matching.
+    pub game_version: String,
+    pub level: usize,
+    pub level_name: String,
+    pub difficulty: Difficulty,
+    pub game_speed: f32,
+    pub ball_scale: f32,
+    pub paddle_scale: f32,
+    pub start_score: u32,
+    /// Score when the level was cleared, checked against a playback to verify it.
+    pub final_score: u32,
+    /// Seconds on the level clock it took to clear.
+    #[serde(default)]
+    pub clear_time: f32,
+    /// Seed the level's serve angles were dealt from.
+    #[serde(default)]
+    pub serve_seed: u64,
+}
+
+/// Gameplay input for one player on one frame.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct PlayerFrame {
+    pub direction: f32,
+    pub bump: bool,
+    #[serde(default)]
+    pub vertical: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReplayFrame {
+    /// Real seconds the frame lasted; playback feeds the same deltas to the clock.
+    pub dt: f32,
+    pub players: Vec<PlayerFrame>,
+    /// Cursor position steering the paddle under the mouse control scheme.
+    pub target_x: Option<f32>,
+}
+
+/// Where the paddle and ball were drawn at a point on the level clock.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct GhostFrame {
+    pub time: f32,
+    pub paddle: (f32, f32),
+    pub ball: Option<(f32, f32)>,
+}
+
+/// One level's worth of input, saved to `replays/` whenever a level is cleared.
+///
+/// Paused frames aren't recorded, and touch input isn't captured.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Replay {
+    pub header: ReplayHeader,
+    pub frames: Vec<ReplayFrame>,
+    /// What the run looked like, for racing against it as a ghost.
+    #[serde(default)]
+    pub ghost: Vec<GhostFrame>,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|contents| ron::from_str(&contents).map_err(|err| err.to_string()))
+    }
+
+    fn save(&self) {
+        let contents = match ron::ser::to_string(self) {
+            Ok(contents) => contents,
+            Err(err) => {
+                error!("failed to serialize replay: {err}");
+                return;
+            }
+        };
+        if let Err(err) = fs::create_dir_all(REPLAY_DIR) {
+            error!("failed to create {REPLAY_DIR}: {err}");
+            return;
+        }
+        let path = format!(
+            "{REPLAY_DIR}/level-{}-{}.ron",
+            self.header.level + 1,
+            screenshot::timestamp()
+        );
+        if let Err(err) = fs::write(&path, contents) {
+            error!("failed to write {path}: {err}");
+        }
+    }
+}
+
+/// Saved replays, newest first.
+pub fn saved_replays() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(REPLAY_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "ron"))
+        .collect();
+    paths.sort_by(|a, b| b.cmp(a));
+    paths
+}
+
+/// The level being recorded, if any; nothing is recorded while a replay plays.
+#[derive(Resource, Default)]
+struct ReplayRecorder(Option<Replay>);
+
+/// A replay being played back in place of the player's input.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    frame: usize,
+}
+
+impl ReplayPlayback {
+    pub fn header(&self) -> &ReplayHeader {
+        &self.replay.header
+    }
+
+    pub fn current(&self) -> Option<&ReplayFrame> {
+        self.replay.frames.get(self.frame)
+    }
+}
+
+#[derive(Component)]
+struct ReplayBrowser;
+
+#[derive(Component)]
+struct ReplayRow(PathBuf);
+
+#[derive(Component)]
+struct ReplayHint;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayRecorder>()
+            .add_systems(OnEnter(GameState::Playing), start_recording.after(serve::reseed_serves))
+            .add_systems(Update, record_frame.run_if(in_state(GameState::Playing)))
+            .add_systems(PostUpdate, record_ghost_frame.run_if(in_state(GameState::Playing)))
+            .add_systems(OnEnter(GameState::LevelComplete), (save_recording, finish_playback))
+            .add_systems(OnEnter(GameState::GameOver), finish_playback)
+            .add_systems(Last, advance_playback.run_if(resource_exists::<ReplayPlayback>))
+            .add_systems(OnEnter(GameState::Splash), spawn_replay_hint)
+            .add_systems(OnExit(GameState::Splash), despawn_replay_hint)
+            .add_systems(
+                Update,
+                open_browser
+                    .after(cheats::detect_cheat_codes)
+                    .run_if(in_state(GameState::Splash).and(seed::not_entering_seed).and(cheats::not_typing_code)),
+            )
+            .add_systems(OnEnter(GameState::Replays), spawn_browser)
+            .add_systems(Update, browse_replays.run_if(in_state(GameState::Replays)))
+            .add_systems(OnExit(GameState::Replays), despawn_browser);
+    }
+}
+
+fn start_recording(
+    mut recorder: ResMut<ReplayRecorder>,
+    playback: Option<Res<ReplayPlayback>>,
+    tutorial: Res<Tutorial>,
+    current_level: Res<CurrentLevel>,
+    levels: Res<Levels>,
+    settings: Res<Settings>,
+    score: Res<GameScore>,
+    serve_deal: Res<ServeDeal>,
+) {
+    // The tutorial stops the clock mid-level, which a replay has no way to reproduce.
+    let record = playback.is_none() && !tutorial.guides(current_level.0);
+    recorder.0 = record.then(|| Replay {
+        header: ReplayHeader {
+            version: REPLAY_VERSION,
+            game_version: env!("CARGO_PKG_VERSION").to_string(),
+            level: current_level.0,
+            level_name: levels.get(current_level.0).name.clone(),
+            difficulty: settings.difficulty,
+            game_speed: settings.game_speed,
+            ball_scale: settings.ball_scale,
+            paddle_scale: settings.paddle_scale,
+            start_score: score.0,
+            final_score: score.0,
+            clear_time: 0.0,
+            serve_seed: serve_deal.seed,
+        },
+        frames: Vec::new(),
+        ghost: Vec::new(),
+    });
+}
+
+fn record_frame(
+    real_time: Res<Time<Real>>,
+    virtual_time: Res<Time<Virtual>>,
+    controls: GameplayInput,
+    paddle_query: Query<&PlayerId, With<Paddle>>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    if virtual_time.is_paused() {
+        return;
+    }
+    let Some(replay) = recorder.0.as_mut() else {
+        return;
+    };
+    let player_count = paddle_query.iter().map(|player| player.0 + 1).max().unwrap_or(0);
+    replay.frames.push(ReplayFrame {
+        dt: real_time.delta_secs(),
+        players: (0..player_count)
+            .map(|player| PlayerFrame {
+                direction: controls.direction(PlayerId(player)),
+                bump: controls.bump_just_pressed(PlayerId(player)),
+                vertical: controls.vertical(PlayerId(player)),
+            })
+            .collect(),
+        target_x: controls.mouse_target_x(),
+    });
+}
+
+// Taken after movement and interpolation, so the ghost is drawn where the real thing was.
+fn record_ghost_frame(
+    clock: Res<LevelClock>,
+    paddle_query: Query<(&Transform, &PlayerId), With<Paddle>>,
+    ball_query: Query<&Transform, With<Ball>>,
+    mut recorder: ResMut<ReplayRecorder>,
+) {
+    let Some(replay) = recorder.0.as_mut() else {
+        return;
+    };
+    if replay.ghost.last().is_some_and(|frame| frame.time >= clock.elapsed) {
+        return;
+    }
+    let Some((paddle, _)) = paddle_query.iter().find(|(_, player)| **player == PlayerId(0)) else {
+        return;
+    };
+    replay.ghost.push(GhostFrame {
+        time: clock.elapsed,
+        paddle: (paddle.translation.x, paddle.translation.y),
+        ball: ball_query
+            .iter()
+            .next()
+            .map(|ball| (ball.translation.x, ball.translation.y)),
+    });
+}
+
+fn save_recording(mut recorder: ResMut<ReplayRecorder>, score: Res<GameScore>, clock: Res<LevelClock>) {
+    if let Some(mut replay) = recorder.0.take() {
+        replay.header.final_score = score.0;
+        replay.header.clear_time = clock.elapsed;
+        replay.save();
+    }
+}
+
+fn end_playback(
+    commands: &mut Commands,
+    playback: &ReplayPlayback,
+    score: &GameScore,
+    toast_query: &Query<Entity, With<Toast>>,
+) {
+    commands.remove_resource::<ReplayPlayback>();
+    commands.insert_resource(TimeUpdateStrategy::Automatic);
+    let recorded = playback.replay.header.final_score;
+    let message = if score.0 == recorded {
+        format!("Replay verified: {recorded} points")
+    } else {
+        format!("Replay diverged: {} points, {recorded} recorded", score.0)
+    };
+    screenshot::show_toast(commands, toast_query, message);
+}
+
+fn finish_playback(
+    mut commands: Commands,
+    playback: Option<Res<ReplayPlayback>>,
+    score: Res<GameScore>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if let Some(playback) = playback {
+        end_playback(&mut commands, &playback, &score, &toast_query);
+    }
+}
+
+// Runs at the end of the frame so the next frame starts with the next recorded delta.
+fn advance_playback(
+    mut commands: Commands,
+    virtual_time: Res<Time<Virtual>>,
+    mut playback: ResMut<ReplayPlayback>,
+    score: Res<GameScore>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if virtual_time.is_paused() {
+        return;
+    }
+    playback.frame += 1;
+    match playback.current() {
+        Some(frame) => {
+            let dt = Duration::from_secs_f32(frame.dt);
+            commands.insert_resource(TimeUpdateStrategy::ManualDuration(dt));
+        }
+        None => end_playback(&mut commands, &playback, &score, &toast_query),
+    }
+}
+
+fn spawn_replay_hint(mut commands: Commands) {
+    commands.spawn((
+        Text2d("R: Replays".to_string()),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, 330.0, 2.0),
+        ReplayHint,
+    ));
+}
+
+fn open_browser(
+    input: Res<ButtonInput<Key>>,
+    mut commands: Commands,
+    splash_query: Query<Entity, Or<(With<SplashScreen>, With<StartButton>)>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if input.pressed(Key::Control) || !input.just_pressed(Key::Character("r".into())) {
+        return;
+    }
+    for entity in &splash_query {
+        commands.entity(entity).despawn();
+    }
+    next_state.set(GameState::Replays);
+}
+
+fn despawn_replay_hint(mut commands: Commands, hint_query: Query<Entity, With<ReplayHint>>) {
+    for entity in &hint_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn row_label(path: &Path, levels: &Levels) -> String {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    match Replay::load(path) {
+        Ok(replay) if replay.header.version != REPLAY_VERSION => {
+            format!("{file_name}  (made by version {}, can't play)", replay.header.game_version)
+        }
+        Ok(replay) => {
+            let header = &replay.header;
+            let status = if levels.0.get(header.level).is_some_and(|level| level.name == header.level_name) {
+                ""
+            } else {
+                "  (level missing)"
+            };
+            format!(
+                "{}  {}  {} points{status}",
+                header.level_name,
+                header.difficulty.name(),
+                header.final_score
+            )
+        }
+        Err(_) => format!("{file_name}  (unreadable)"),
+    }
+}
+
+fn spawn_browser(mut commands: Commands, levels: Res<Levels>) {
+    let replays = saved_replays();
+    let title = if replays.is_empty() {
+        "No replays yet. Clear a level to record one.\nEscape: Back"
+    } else {
+        "Replays\nEnter: Play   Escape: Back"
+    };
+    commands.spawn((
+        Text2d(title.to_string()),
+        Transform::from_xyz(0.0, BROWSER_TOP_Y + 70.0, 2.0),
+        ReplayBrowser,
+    ));
+    for (index, path) in replays.into_iter().take(BROWSER_ROWS).enumerate() {
+        commands.spawn((
+            Text2d(row_label(&path, &levels)),
+            TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+            Transform::from_xyz(0.0, BROWSER_TOP_Y - index as f32 * BROWSER_ROW_SPACING, 2.0),
+            MenuItem {
+                order: index as u32,
+                size: BROWSER_ROW_SIZE,
+            },
+            ReplayRow(path),
+            ReplayBrowser,
+        ));
+    }
+}
+
+fn browse_replays(
+    input: Res<ButtonInput<Key>>,
+    mut activated: MessageReader<MenuActivated>,
+    row_query: Query<&ReplayRow>,
+    camera_query: Query<Entity, With<GameCamera>>,
+    levels: Res<Levels>,
+    mut settings: ResMut<Settings>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut score: ResMut<GameScore>,
+    mut combo: ResMut<Combo>,
+    mut energy: ResMut<BumpEnergy>,
+    mut lives: ResMut<Lives>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if input.just_pressed(Key::Escape) {
+        // The splash screen brings its own camera.
+        for entity in &camera_query {
+            commands.entity(entity).despawn();
+        }
+        next_state.set(GameState::Splash);
+        return;
+    }
+    let Some(row) = activated.read().find_map(|MenuActivated(entity)| row_query.get(*entity).ok()) else {
+        return;
+    };
+    let replay = match Replay::load(&row.0) {
+        Ok(replay) if replay.header.version == REPLAY_VERSION => replay,
+        _ => {
+            screenshot::show_toast(&mut commands, &toast_query, "Can't play this replay".to_string());
+            return;
+        }
+    };
+    let header = &replay.header;
+    if levels.0.get(header.level).is_none_or(|level| level.name != header.level_name) {
+        let message = format!("Level \"{}\" isn't installed", header.level_name);
+        screenshot::show_toast(&mut commands, &toast_query, message);
+        return;
+    }
+
+    // Played with the recorded settings, which then stay until changed again.
+    settings.difficulty = header.difficulty;
+    settings.game_speed = header.game_speed;
+    settings.ball_scale = header.ball_scale;
+    settings.paddle_scale = header.paddle_scale;
+    current_level.0 = header.level;
+    score.0 = header.start_score;
+    combo.count = 0;
+    *energy = BumpEnergy::default();
+    *lives = Lives::default();
+
+    if let Some(frame) = replay.frames.first() {
+        commands.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(frame.dt)));
+    }
+    commands.insert_resource(ReplayPlayback { replay, frame: 0 });
+    next_state.set(GameState::Playing);
+}
+
+fn despawn_browser(mut commands: Commands, browser_query: Query<Entity, With<ReplayBrowser>>) {
+    for entity in &browser_query {
+        commands.entity(entity).despawn();
+    }
+}
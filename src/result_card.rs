@@ -0,0 +1,166 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::camera::visibility::RenderLayers;
+use bevy::camera::RenderTarget;
+use bevy::image::BevyDefault;
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy::render::view::screenshot::{save_to_disk, Screenshot, ScreenshotCaptured};
+
+use crate::new_game_plus::NewGamePlus;
+use crate::scoring::GameScore;
+use crate::screenshot::{self, Toast, SCREENSHOT_DIR};
+use crate::seed::{self, RunSeed};
+use crate::settings::Settings;
+use crate::stats::RunStats;
+use crate::GameState;
+
+/// Render layer only the card and its camera are on, so it never shows in the window.
+const CARD_LAYER: usize = 30;
+/// Pixel size of the saved card, the usual shape for link previews.
+const CARD_SIZE: UVec2 = UVec2::new(1200, 630);
+const CARD_BACKGROUND: Color = Color::srgb(0.06, 0.07, 0.12);
+/// Colors of the block-like stripes across the top and bottom of the card.
+const STRIPE_COLORS: [Color; 4] = [
+    Color::srgb(0.9, 0.3, 0.3),
+    Color::srgb(0.95, 0.7, 0.2),
+    Color::srgb(0.3, 0.8, 0.4),
+    Color::srgb(0.3, 0.5, 0.95),
+];
+const STRIPE_HEIGHT: f32 = 14.0;
+
+/// Everything drawn for the card, cleared away once it has been saved.
+#[derive(Component, Clone)]
+struct ResultCard;
+
+pub struct ResultCardPlugin;
+
+impl Plugin for ResultCardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::GameWon), save_result_card)
+            .add_systems(OnEnter(GameState::GameOver), save_result_card);
+    }
+}
+
+fn mode_label(settings: &Settings, cycle: &NewGamePlus) -> String {
+    let mut parts = vec![settings.difficulty.name().to_string()];
+    if cycle.0 > 0 {
+        parts.push(format!("New Game+ {}", cycle.0));
+    }
+    parts.extend(settings.mutators.iter().map(|mutator| mutator.name().to_string()));
+    parts.join(" + ")
+}
+
+/// Today's date in UTC as `YYYY-MM-DD`, worked out from the days since the Unix epoch.
+fn date_label() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since_epoch| since_epoch.as_secs());
+    // Howard Hinnant's days-to-civil conversion.
+    let z = (seconds / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+// Laid out on its own render layer and drawn by a camera into an image, then saved like
+// a screenshot; the camera and card go once the capture is on disk.
+fn save_result_card(
+    state: Res<State<GameState>>,
+    score: Res<GameScore>,
+    stats: Res<RunStats>,
+    settings: Res<Settings>,
+    cycle: Res<NewGamePlus>,
+    run_seed: Res<RunSeed>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if let Err(err) = std::fs::create_dir_all(SCREENSHOT_DIR) {
+        error!("failed to create {SCREENSHOT_DIR}: {err}");
+        return;
+    }
+    let layer = RenderLayers::layer(CARD_LAYER);
+    let size = CARD_SIZE.as_vec2();
+
+    commands.spawn((
+        Sprite {
+            color: CARD_BACKGROUND,
+            custom_size: Some(size),
+            ..default()
+        },
+        Transform::default(),
+        layer.clone(),
+        ResultCard,
+    ));
+    let stripe_width = size.x / STRIPE_COLORS.len() as f32;
+    for (i, color) in STRIPE_COLORS.into_iter().enumerate() {
+        let x = -size.x / 2.0 + stripe_width * (i as f32 + 0.5);
+        for y in [size.y / 2.0 - STRIPE_HEIGHT / 2.0, -size.y / 2.0 + STRIPE_HEIGHT / 2.0] {
+            commands.spawn((
+                Sprite {
+                    color,
+                    custom_size: Some(Vec2::new(stripe_width - 4.0, STRIPE_HEIGHT)),
+                    ..default()
+                },
+                Transform::from_xyz(x, y, 1.0),
+                layer.clone(),
+                ResultCard,
+            ));
+        }
+    }
+
+    let title = if *state.get() == GameState::GameWon {
+        "Rusty Pong - Cleared!"
+    } else {
+        "Rusty Pong - Game over"
+    };
+    let lines = [
+        (title.to_string(), 56.0, 200.0),
+        (format!("Score: {}", score.0), 72.0, 90.0),
+        (format!("Time: {:.1}s", stats.time), 32.0, -10.0),
+        (format!("Mode: {}", mode_label(&settings, &cycle)), 28.0, -70.0),
+        (format!("Seed: {}", seed::code(run_seed.0)), 24.0, -130.0),
+        (date_label(), 24.0, -190.0),
+    ];
+    for (text, font_size, y) in lines {
+        commands.spawn((
+            Text2d(text),
+            TextFont { font_size, ..default() },
+            Transform::from_xyz(0.0, y, 2.0),
+            layer.clone(),
+            ResultCard,
+        ));
+    }
+
+    let target = images.add(Image::new_target_texture(CARD_SIZE.x, CARD_SIZE.y, TextureFormat::bevy_default()));
+    let camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                target: RenderTarget::Image(target.clone().into()),
+                ..default()
+            },
+            layer,
+        ))
+        .id();
+    let path = format!("{SCREENSHOT_DIR}/pong-result-{}.png", screenshot::timestamp());
+    commands
+        .spawn(Screenshot::image(target))
+        .observe(save_to_disk(path.clone()))
+        .observe(
+            move |_: On<ScreenshotCaptured>, mut commands: Commands, card_query: Query<Entity, With<ResultCard>>| {
+                commands.entity(camera).despawn();
+                for entity in &card_query {
+                    commands.entity(entity).despawn();
+                }
+            },
+        );
+    screenshot::show_toast(&mut commands, &toast_query, format!("Result card saved to {path}"));
+}
@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use bevy::input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+
+use crate::collision::BallHitPaddle;
+use crate::config::GameConfig;
+use crate::controls::{ActiveGamepad, ControlScheme};
+use crate::scoring::BlockScored;
+use crate::screenshot::{self, Toast};
+use crate::settings::Settings;
+use crate::{BallLost, GameState};
+
+pub struct RumblePlugin;
+
+impl Plugin for RumblePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, toggle_rumble).add_systems(
+            Update,
+            rumble_on_impacts.run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+fn toggle_rumble(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if input.pressed(Key::Control) && input.just_pressed(Key::Character("r".into())) {
+        settings.rumble = !settings.rumble;
+        settings.save();
+        let state = if settings.rumble { "on" } else { "off" };
+        screenshot::show_toast(&mut commands, &toast_query, format!("Rumble {state}"));
+    }
+}
+
+// Paddle hits are a light tap that gets firmer the faster the ball is going, blocks
+// breaking are a sharper buzz, and losing the ball is the longest, heaviest pulse.
+fn rumble_on_impacts(
+    settings: Res<Settings>,
+    config: Res<GameConfig>,
+    active_gamepad: Res<ActiveGamepad>,
+    mut paddle_hits: MessageReader<BallHitPaddle>,
+    mut block_scores: MessageReader<BlockScored>,
+    mut lost_events: MessageReader<BallLost>,
+    mut rumble_requests: MessageWriter<GamepadRumbleRequest>,
+) {
+    let Some(gamepad) = active_gamepad
+        .0
+        .filter(|_| settings.rumble && settings.control_scheme == ControlScheme::Gamepad)
+    else {
+        paddle_hits.clear();
+        block_scores.clear();
+        lost_events.clear();
+        return;
+    };
+
+    let mut pulse = |weak: f32, strong: f32, millis: u64| {
+        rumble_requests.write(GamepadRumbleRequest::Add {
+            gamepad,
+            intensity: GamepadRumbleIntensity {
+                weak_motor: weak.clamp(0.0, 1.0),
+                strong_motor: strong.clamp(0.0, 1.0),
+            },
+            duration: Duration::from_millis(millis),
+        });
+    };
+
+    if let Some(hit) = paddle_hits.read().last() {
+        let speed = config.speed_fraction(hit.speed);
+        pulse(0.3 + 0.5 * speed, 0.2 * speed, 60);
+    }
+    if let Some(score) = block_scores.read().last() {
+        pulse(0.6, 0.3 + 0.1 * score.points as f32, 90);
+    }
+    if lost_events.read().count() > 0 {
+        pulse(0.4, 1.0, 300);
+    }
+}
@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+
+use crate::collision::BallHitPaddle;
+use crate::config::GameConfig;
+use crate::pool::{self, EntityPool};
+use crate::{GameEntity, GameState};
+
+/// Points for a block hit at the configured start speed, and the most a single hit can
+/// earn at max speed, before the combo multiplier.
+const BASE_BLOCK_POINTS: u32 = 1;
+const MAX_BLOCK_POINTS: u32 = 3;
+
+/// Seconds a score popup takes to rise and fade out.
+const POPUP_LIFETIME: f32 = 0.8;
+/// World units per second a popup drifts upward.
+const POPUP_RISE_SPEED: f32 = 60.0;
+
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct GameScore(pub u32);
+
+#[derive(Component)]
+pub struct Score;
+
+/// Blocks broken in a row since the ball last touched the paddle.
+#[derive(Resource, Default)]
+pub struct Combo {
+    pub count: u32,
+}
+
+impl Combo {
+    pub fn multiplier(&self) -> u32 {
+        self.count.max(1)
+    }
+}
+
+/// Sent for every block the ball breaks; the score is `points * multiplier`.
+#[derive(Message)]
+pub struct BlockScored {
+    pub points: u32,
+    pub multiplier: u32,
+    /// Where the block was, for the score popup.
+    pub position: Vec2,
+}
+
+/// Sent whenever the combo multiplier changes, including when it drops back to x1.
+#[derive(Message)]
+pub struct ComboChanged {
+    pub multiplier: u32,
+}
+
+#[derive(Component)]
+pub struct ComboText;
+
+/// Floating "+N" over a broken block, with the seconds it has left.
+#[derive(Component)]
+pub struct ScorePopup(f32);
+
+pub struct ScoringPlugin;
+
+impl Plugin for ScoringPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameScore>()
+            .init_resource::<Combo>()
+            .init_resource::<EntityPool<ScorePopup>>()
+            .add_message::<BlockScored>()
+            .add_message::<ComboChanged>()
+            .add_systems(OnEnter(GameState::Playing), pool::clear_pool::<ScorePopup>)
+            .add_systems(
+                Update,
+                (
+                    break_combo,
+                    (apply_block_scores, spawn_score_popups),
+                    float_score_popups,
+                    update_score_text.run_if(resource_changed::<GameScore>),
+                    update_combo_text,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Points a block is worth at the given ball speed, scaling linearly from
+/// `BASE_BLOCK_POINTS` at start speed to `MAX_BLOCK_POINTS` at max speed.
+pub fn speed_points(speed: f32, config: &GameConfig) -> u32 {
+    let t = config.speed_fraction(speed);
+    BASE_BLOCK_POINTS + (t * (MAX_BLOCK_POINTS - BASE_BLOCK_POINTS) as f32).round() as u32
+}
+
+pub fn spawn_score_hud(commands: &mut Commands, score: &GameScore) {
+    commands.spawn((
+        Text::new(format!("Score: {}", score.0)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(40.0),
+            top: Val::Px(30.0),
+            ..default()
+        },
+        Score,
+        GameEntity,
+    ));
+    commands.spawn((
+        Text::new(""),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(40.0),
+            top: Val::Px(65.0),
+            ..default()
+        },
+        ComboText,
+        GameEntity,
+    ));
+}
+
+fn break_combo(
+    mut paddle_hits: MessageReader<BallHitPaddle>,
+    mut combo: ResMut<Combo>,
+    mut combo_events: MessageWriter<ComboChanged>,
+) {
+    if paddle_hits.read().count() > 0 && combo.count > 0 {
+        combo.count = 0;
+        combo_events.write(ComboChanged { multiplier: 1 });
+    }
+}
+
+fn apply_block_scores(
+    mut score_events: MessageReader<BlockScored>,
+    mut score: ResMut<GameScore>,
+) {
+    for event in score_events.read() {
+        score.0 += event.points * event.multiplier;
+    }
+}
+
+fn spawn_score_popups(
+    mut commands: Commands,
+    mut pool: ResMut<EntityPool<ScorePopup>>,
+    mut score_events: MessageReader<BlockScored>,
+) {
+    for event in score_events.read() {
+        pool.spawn(
+            &mut commands,
+            (
+                Text2d(format!("+{}", event.points * event.multiplier)),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Transform::from_translation(event.position.extend(3.0)),
+                ScorePopup(POPUP_LIFETIME),
+                GameEntity,
+            ),
+        );
+    }
+}
+
+fn float_score_popups(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut pool: ResMut<EntityPool<ScorePopup>>,
+    mut popup_query: Query<(Entity, &mut ScorePopup, &mut Transform, &mut TextColor)>,
+) {
+    for (entity, mut popup, mut transform, mut color) in &mut popup_query {
+        popup.0 -= time.delta_secs();
+        if popup.0 <= 0.0 {
+            pool.release(&mut commands, entity);
+            continue;
+        }
+        transform.translation.y += POPUP_RISE_SPEED * time.delta_secs();
+        color.0 = Color::WHITE.with_alpha(popup.0 / POPUP_LIFETIME);
+    }
+}
+
+// Bonuses are added straight to `GameScore`, so the HUD follows the resource rather
+// than the events.
+fn update_score_text(score: Res<GameScore>, mut text_query: Query<&mut Text, With<Score>>) {
+    for mut text in &mut text_query {
+        **text = format!("Score: {}", score.0);
+    }
+}
+
+fn update_combo_text(
+    mut combo_events: MessageReader<ComboChanged>,
+    mut text_query: Query<&mut Text, With<ComboText>>,
+) {
+    let Some(event) = combo_events.read().last() else {
+        return;
+    };
+    for mut text in &mut text_query {
+        **text = if event.multiplier > 1 {
+            format!("Combo x{}", event.multiplier)
+        } else {
+            String::new()
+        };
+    }
+}
@@ -0,0 +1,90 @@
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{save_to_disk, Screenshot};
+
+pub const SCREENSHOT_DIR: &str = "screenshots";
+/// Seconds the "Saved ..." message stays on screen.
+const TOAST_DURATION: f32 = 2.0;
+
+/// Short-lived message in the bottom-left corner confirming a capture was saved.
+#[derive(Component)]
+pub struct Toast {
+    remaining: f32,
+}
+
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (take_screenshot, fade_toast));
+    }
+}
+
+fn take_screenshot(
+    input: Res<ButtonInput<Key>>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !input.just_pressed(Key::F12) {
+        return;
+    }
+    if let Err(err) = fs::create_dir_all(SCREENSHOT_DIR) {
+        error!("failed to create {SCREENSHOT_DIR}: {err}");
+        return;
+    }
+
+    let path = format!("{SCREENSHOT_DIR}/pong-{}.png", timestamp());
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_to_disk(path.clone()));
+    show_toast(&mut commands, &toast_query, format!("Saved {path}"));
+}
+
+/// Milliseconds since the Unix epoch, used to give captures unique file names.
+pub fn timestamp() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since_epoch| since_epoch.as_millis())
+}
+
+/// Replaces any toast currently on screen with `message`.
+pub fn show_toast(commands: &mut Commands, toast_query: &Query<Entity, With<Toast>>, message: String) {
+    for entity in toast_query {
+        commands.entity(entity).despawn();
+    }
+    commands.spawn((
+        Text::new(message),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(40.0),
+            bottom: Val::Px(30.0),
+            ..default()
+        },
+        Toast {
+            remaining: TOAST_DURATION,
+        },
+    ));
+}
+
+// Uses real time so the toast still goes away while the game is frame-stepped.
+fn fade_toast(
+    time: Res<Time<Real>>,
+    mut commands: Commands,
+    mut toast_query: Query<(Entity, &mut Toast, &mut TextColor)>,
+) {
+    for (entity, mut toast, mut color) in &mut toast_query {
+        toast.remaining -= time.delta_secs();
+        if toast.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+        } else {
+            color.0 = Color::WHITE.with_alpha((toast.remaining / TOAST_DURATION * 2.0).min(1.0));
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::fs;
+use std::mem;
+
+use bevy::prelude::*;
+use mlua::{Function, IntoLuaMulti, Lua};
+
+use crate::config::GameConfig;
+use crate::level::{CurrentLevel, LevelClock, Levels};
+use crate::mods::ModRegistry;
+use crate::projectile::{self, ProjectileEffect};
+use crate::screenshot::{self, Toast};
+use crate::{debug, pause, spawn_block, Ball, BallCollision, BlockDestroyed, GameState, Serving, Velocity};
+
+/// Level logic written in Lua, loaded from the level's `script` file when it starts.
+///
+/// A script can define any of these hooks:
+/// - `on_block_destroyed(x, y)`, after a block breaks
+/// - `on_timer(seconds)`, once for every whole second spent on the level
+///
+/// and call these from them, or from its top level as the level starts:
+/// - `spawn_block(x, y [, health])`
+/// - `set_gravity(x, y)`, a constant pull on the ball in units per second squared
+/// - `show_message(text)`
+/// - `fire_projectile(x, y [, speed])`, a shot falling straight down that shrinks the
+///   paddle for a few seconds if it hits, for boss-style levels
+struct LevelScript {
+    lua: Lua,
+}
+
+/// Something a script asked for, carried out once the script returns.
+enum ScriptAction {
+    SpawnBlock { position: Vec2, health: Option<u32> },
+    SetGravity(Vec2),
+    ShowMessage(String),
+    FireProjectile { position: Vec2, speed: Option<f32> },
+}
+
+/// Pull applied to the ball, zero unless a level script sets it.
+#[derive(Resource, Default)]
+pub struct Gravity(pub Vec2);
+
+pub struct ScriptingPlugin;
+
+impl Plugin for ScriptingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Gravity>()
+            .add_systems(OnEnter(GameState::Playing), load_level_script)
+            .add_systems(
+                Update,
+                (run_block_destroyed_hooks, run_timer_hooks, apply_script_actions)
+                    .chain()
+                    .run_if(in_state(GameState::Playing).and(pause::not_paused)),
+            )
+            .add_systems(
+                FixedUpdate,
+                apply_gravity
+                    .before(BallCollision)
+                    .run_if(in_state(GameState::Playing).and(debug::simulation_running).and(pause::not_paused)),
+            );
+    }
+}
+
+fn queue_action(lua: &Lua, action: ScriptAction) {
+    if let Some(mut actions) = lua.app_data_mut::<Vec<ScriptAction>>() {
+        actions.push(action);
+    }
+}
+
+fn create_lua(source: &str, name: &str) -> mlua::Result<Lua> {
+    let lua = Lua::new();
+    lua.set_app_data(Vec::<ScriptAction>::new());
+
+    let globals = lua.globals();
+    globals.set(
+        "spawn_block",
+        lua.create_function(|lua, (x, y, health): (f32, f32, Option<u32>)| {
+            queue_action(lua, ScriptAction::SpawnBlock { position: Vec2::new(x, y), health });
+            Ok(())
+        })?,
+    )?;
+    globals.set(
+        "set_gravity",
+        lua.create_function(|lua, (x, y): (f32, f32)| {
+            queue_action(lua, ScriptAction::SetGravity(Vec2::new(x, y)));
+            Ok(())
+        })?,
+    )?;
+    globals.set(
+        "show_message",
+        lua.create_function(|lua, text: String| {
+            queue_action(lua, ScriptAction::ShowMessage(text));
+            Ok(())
+        })?,
+    )?;
+
+    globals.set(
+        "fire_projectile",
+        lua.create_function(|lua, (x, y, speed): (f32, f32, Option<f32>)| {
+            queue_action(lua, ScriptAction::FireProjectile { position: Vec2::new(x, y), speed });
+            Ok(())
+        })?,
+    )?;
+
+    lua.load(source).set_name(name).exec()?;
+    Ok(lua)
+}
+
+// Exclusive, since the Lua state can only live on the main thread.
+fn load_level_script(world: &mut World) {
+    world.resource_mut::<Gravity>().0 = Vec2::ZERO;
+    world.remove_non_send_resource::<LevelScript>();
+
+    let levels = world.resource::<Levels>();
+    let Some(path) = levels.get(world.resource::<CurrentLevel>().0).script.clone() else {
+        return;
+    };
+    let path = world.resource::<ModRegistry>().file_path(&path);
+    let name = path.display().to_string();
+    let lua = fs::read_to_string(&path)
+        .map_err(|err| err.to_string())
+        .and_then(|source| create_lua(&source, &name).map_err(|err| err.to_string()));
+    match lua {
+        Ok(lua) => world.insert_non_send_resource(LevelScript { lua }),
+        Err(err) => error!("failed to load level script {name}: {err}"),
+    }
+}
+
+fn call_hook(script: &LevelScript, name: &str, args: impl IntoLuaMulti) {
+    let result = script
+        .lua
+        .globals()
+        .get::<Option<Function>>(name)
+        .and_then(|hook| hook.map_or(Ok(()), |hook| hook.call::<()>(args)));
+    if let Err(err) = result {
+        error!("level script hook {name} failed: {err}");
+    }
+}
+
+fn run_block_destroyed_hooks(script: Option<NonSend<LevelScript>>, mut destroyed: MessageReader<BlockDestroyed>) {
+    let Some(script) = script else {
+        destroyed.clear();
+        return;
+    };
+    for event in destroyed.read() {
+        call_hook(&script, "on_block_destroyed", (event.position.x, event.position.y));
+    }
+}
+
+fn run_timer_hooks(script: Option<NonSend<LevelScript>>, clock: Res<LevelClock>, mut last_second: Local<u32>) {
+    let second = clock.elapsed as u32;
+    // The clock starts over with every level.
+    if second < *last_second {
+        *last_second = second;
+    }
+    let Some(script) = script else {
+        *last_second = second;
+        return;
+    };
+    for elapsed in *last_second + 1..=second {
+        call_hook(&script, "on_timer", elapsed);
+    }
+    *last_second = second;
+}
+
+fn apply_script_actions(
+    script: Option<NonSend<LevelScript>>,
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut gravity: ResMut<Gravity>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    let Some(script) = script else {
+        return;
+    };
+    let Some(mut queued) = script.lua.app_data_mut::<Vec<ScriptAction>>() else {
+        return;
+    };
+    for action in mem::take(&mut *queued) {
+        match action {
+            ScriptAction::SpawnBlock { position, health } => {
+                spawn_block(&mut commands, position, health.unwrap_or(config.block_hp).max(1), 1);
+            }
+            ScriptAction::SetGravity(pull) => gravity.0 = pull,
+            ScriptAction::ShowMessage(text) => screenshot::show_toast(&mut commands, &toast_query, text),
+            ScriptAction::FireProjectile { position, speed } => {
+                let speed = speed.unwrap_or(projectile::DEFAULT_PROJECTILE_SPEED);
+                projectile::spawn_projectile(
+                    &mut commands,
+                    position,
+                    Vec2::new(0.0, -speed),
+                    projectile::PROJECTILE_SIZE,
+                    projectile::PROJECTILE_COLOR,
+                    ProjectileEffect::Shrink,
+                );
+            }
+        }
+    }
+}
+
+fn apply_gravity(
+    time: Res<Time>,
+    gravity: Res<Gravity>,
+    mut ball_query: Query<&mut Velocity, (With<Ball>, Without<Serving>)>,
+) {
+    if gravity.0 == Vec2::ZERO {
+        return;
+    }
+    for mut velocity in &mut ball_query {
+        velocity.0 += gravity.0 * time.delta_secs();
+    }
+}
@@ -0,0 +1,291 @@
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::game_over::GameOverScreen;
+use crate::level::CurrentLevel;
+use crate::menu::{MenuActivated, MenuItem};
+use crate::pause::Paused;
+use crate::replay::ReplayPlayback;
+use crate::screenshot::{self, Toast};
+use crate::serve;
+use crate::{GameEntity, GameState, SplashScreen, StartButton, WinScreen};
+
+/// Crockford's base 32, which leaves out letters that are easy to misread.
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// Characters in a seed code, not counting the dash in the middle.
+const CODE_LENGTH: usize = 6;
+/// Seeds fit in the bits a code can spell out.
+const SEED_BITS: u32 = 5 * CODE_LENGTH as u32;
+
+const ENTRY_Y: f32 = 10.0;
+const ENTRY_SIZE: Vec2 = Vec2::new(360.0, 44.0);
+const ENTRY_ORDER: u32 = 100;
+const RESULT_SEED_Y: f32 = -320.0;
+
+/// Seed the current run's serves are dealt from, shown as a short code that can be
+/// typed on the splash screen to play the same run again.
+#[derive(Resource, Default)]
+pub struct RunSeed(pub u32);
+
+/// Seed typed on the splash screen, used for every run started from it.
+#[derive(Resource, Default)]
+struct ChosenSeed(Option<u32>);
+
+/// Characters typed so far while the "Play seed" entry is open.
+#[derive(Resource, Default)]
+struct SeedEntry(Option<String>);
+
+#[derive(Component)]
+struct SeedEntryText;
+
+#[derive(Component)]
+struct PauseSeedText;
+
+pub struct SeedPlugin;
+
+impl Plugin for SeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunSeed>()
+            .init_resource::<ChosenSeed>()
+            .init_resource::<SeedEntry>()
+            .add_systems(OnEnter(GameState::Splash), (reset_chosen_seed, spawn_seed_entry))
+            .add_systems(Update, enter_seed.run_if(in_state(GameState::Splash)))
+            .add_systems(OnEnter(GameState::Playing), pick_run_seed.before(serve::reseed_serves))
+            .add_systems(
+                Update,
+                show_pause_seed.run_if(in_state(GameState::Playing).and(resource_changed::<Paused>)),
+            )
+            .add_systems(OnEnter(GameState::GameWon), spawn_win_seed)
+            .add_systems(OnEnter(GameState::GameOver), spawn_game_over_seed);
+    }
+}
+
+/// `seed` spelled out as a code like `4F7-K2Q`.
+pub fn code(seed: u32) -> String {
+    let chars: Vec<char> = (0..CODE_LENGTH)
+        .rev()
+        .map(|i| CODE_ALPHABET[((seed >> (5 * i)) & 31) as usize] as char)
+        .collect();
+    let (first, second) = chars.split_at(CODE_LENGTH / 2);
+    format!("{}-{}", first.iter().collect::<String>(), second.iter().collect::<String>())
+}
+
+/// The seed a code spells, ignoring case and dashes and reading the letters Crockford
+/// treats as lookalikes (O, I and L) as the digits they resemble.
+pub fn parse_code(code: &str) -> Option<u32> {
+    let chars: Vec<char> = code.chars().filter(|c| *c != '-').collect();
+    if chars.len() != CODE_LENGTH {
+        return None;
+    }
+    chars.into_iter().try_fold(0u32, |seed, c| {
+        let c = match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        };
+        let value = CODE_ALPHABET.iter().position(|&letter| letter as char == c)?;
+        Some((seed << 5) | value as u32)
+    })
+}
+
+/// Run condition for splash screen shortcuts, which would otherwise go off while a
+/// code is being typed.
+pub fn not_entering_seed(entry: Res<SeedEntry>) -> bool {
+    entry.0.is_none()
+}
+
+fn entry_label(entry: &SeedEntry) -> String {
+    match &entry.0 {
+        Some(typed) => format!("Seed: {typed}_"),
+        None => "Play seed...".to_string(),
+    }
+}
+
+fn reset_chosen_seed(mut chosen: ResMut<ChosenSeed>, mut entry: ResMut<SeedEntry>) {
+    chosen.0 = None;
+    entry.0 = None;
+}
+
+fn spawn_seed_entry(mut commands: Commands, entry: Res<SeedEntry>) {
+    commands.spawn((
+        Text2d(entry_label(&entry)),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, ENTRY_Y, 2.0),
+        MenuItem {
+            order: ENTRY_ORDER,
+            size: ENTRY_SIZE,
+        },
+        SeedEntryText,
+        SplashScreen,
+    ));
+}
+
+// Activating the entry opens it; activating it again with a whole code starts the run.
+fn enter_seed(
+    input: Res<ButtonInput<Key>>,
+    mut activated: MessageReader<MenuActivated>,
+    mut entry: ResMut<SeedEntry>,
+    mut chosen: ResMut<ChosenSeed>,
+    mut text_query: Query<(Entity, &mut Text2d), With<SeedEntryText>>,
+    mut commands: Commands,
+    splash_query: Query<Entity, Or<(With<SplashScreen>, With<StartButton>)>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    let confirmed = activated
+        .read()
+        .any(|MenuActivated(entity)| text_query.contains(*entity));
+    let Some(typed) = entry.0.as_mut() else {
+        if confirmed {
+            entry.0 = Some(String::new());
+            for (_, mut text) in &mut text_query {
+                text.0 = entry_label(&entry);
+            }
+        }
+        return;
+    };
+
+    if input.just_pressed(Key::Escape) {
+        entry.0 = None;
+    } else if confirmed {
+        match parse_code(typed) {
+            Some(seed) => {
+                entry.0 = None;
+                chosen.0 = Some(seed);
+                crate::leave_splash(&mut commands, &splash_query, &mut next_state);
+                return;
+            }
+            None => {
+                screenshot::show_toast(&mut commands, &toast_query, "Not a seed code".to_string());
+            }
+        }
+    } else {
+        let keys = input.get_just_pressed().filter(|_| !input.pressed(Key::Control));
+        for key in keys {
+            match key {
+                Key::Character(text) => {
+                    typed.extend(text.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-'));
+                }
+                Key::Backspace => {
+                    typed.pop();
+                }
+                _ => {}
+            }
+        }
+        *typed = typed.chars().take(CODE_LENGTH + 1).collect();
+    }
+    for (_, mut text) in &mut text_query {
+        text.0 = entry_label(&entry);
+    }
+}
+
+// Same rule as the speedrun timer: only starting on the first level is a new run.
+fn pick_run_seed(
+    current_level: Res<CurrentLevel>,
+    chosen: Res<ChosenSeed>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut run_seed: ResMut<RunSeed>,
+) {
+    if current_level.0 != 0 || playback.is_some() {
+        return;
+    }
+    run_seed.0 = chosen.0.unwrap_or_else(|| rand::rng().random_range(0..1 << SEED_BITS));
+}
+
+fn seed_label(seed: &RunSeed) -> String {
+    format!("Seed: {}", code(seed.0))
+}
+
+// A UI node rather than part of the pause overlay, so photo mode hides it with the HUD.
+fn show_pause_seed(
+    paused: Res<Paused>,
+    run_seed: Res<RunSeed>,
+    mut commands: Commands,
+    text_query: Query<Entity, With<PauseSeedText>>,
+) {
+    for entity in &text_query {
+        commands.entity(entity).despawn();
+    }
+    if !paused.0 {
+        return;
+    }
+    commands.spawn((
+        Text::new(seed_label(&run_seed)),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(40.0),
+            bottom: Val::Px(30.0),
+            ..default()
+        },
+        PauseSeedText,
+        GameEntity,
+    ));
+}
+
+fn spawn_result_seed<M: Component>(commands: &mut Commands, run_seed: &RunSeed, marker: M) {
+    commands.spawn((
+        Text2d(seed_label(run_seed)),
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, RESULT_SEED_Y, 2.0),
+        marker,
+    ));
+}
+
+fn spawn_win_seed(mut commands: Commands, run_seed: Res<RunSeed>) {
+    spawn_result_seed(&mut commands, &run_seed, WinScreen);
+}
+
+fn spawn_game_over_seed(mut commands: Commands, run_seed: Res<RunSeed>) {
+    spawn_result_seed(&mut commands, &run_seed, GameOverScreen);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_round_trips() {
+        for seed in [0, 1, 31, 32, 123_456, (1 << SEED_BITS) - 1] {
+            assert_eq!(parse_code(&code(seed)), Some(seed), "seed {seed}");
+        }
+        assert_eq!(code(0), "000-000");
+        assert_eq!(code((1 << SEED_BITS) - 1), "ZZZ-ZZZ");
+    }
+
+    #[test]
+    fn lookalike_letters_read_as_digits() {
+        assert_eq!(parse_code("OIL-000"), parse_code("011-000"));
+        assert_eq!(parse_code("oil-lio"), parse_code("011-110"));
+    }
+
+    #[test]
+    fn case_and_dashes_are_ignored() {
+        let seed = parse_code("4F7-K2Q");
+        assert!(seed.is_some());
+        assert_eq!(parse_code("4f7-k2q"), seed);
+        assert_eq!(parse_code("4F7K2Q"), seed);
+        assert_eq!(parse_code("4F-7K-2Q"), seed);
+    }
+
+    #[test]
+    fn rejects_bad_codes() {
+        assert_eq!(parse_code(""), None);
+        assert_eq!(parse_code("4F7-K2"), None);
+        assert_eq!(parse_code("4F7-K2QA"), None);
+        // U is left out of the alphabet, and isn't read as anything else.
+        assert_eq!(parse_code("4F7-K2U"), None);
+        assert_eq!(parse_code("4F7 K2Q"), None);
+        assert_eq!(parse_code("4F7-K2!"), None);
+    }
+}
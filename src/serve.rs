@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::level::CurrentLevel;
+use crate::replay::ReplayPlayback;
+use crate::seed::RunSeed;
+use crate::{GameState, Serving};
+
+/// Range of launch angles, in radians either side of straight up, a serve starts at.
+const SERVE_ANGLE_MIN: f32 = std::f32::consts::FRAC_PI_8;
+const SERVE_ANGLE_MAX: f32 = std::f32::consts::FRAC_PI_3;
+
+/// Hands out the starting aim for each serve: a random angle within the serve cone,
+/// alternating sides from one serve to the next. Seeded per level from the run's seed,
+/// so a replay or a run with the same seed deals the same serves again.
+#[derive(Resource)]
+pub struct ServeDeal {
+    pub seed: u64,
+    rng: StdRng,
+    serves: u32,
+}
+
+impl Default for ServeDeal {
+    fn default() -> Self {
+        ServeDeal::seeded(0)
+    }
+}
+
+impl ServeDeal {
+    fn seeded(seed: u64) -> Self {
+        ServeDeal {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            serves: 0,
+        }
+    }
+
+    pub fn next(&mut self) -> Serving {
+        let side = if self.serves % 2 == 0 { 1.0 } else { -1.0 };
+        self.serves += 1;
+        Serving {
+            aim: side * self.rng.random_range(SERVE_ANGLE_MIN..=SERVE_ANGLE_MAX),
+        }
+    }
+}
+
+pub struct ServePlugin;
+
+impl Plugin for ServePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServeDeal>()
+            .add_systems(OnEnter(GameState::Playing), reseed_serves.before(crate::setup_game));
+    }
+}
+
+// A replay brings its own seed.
+pub fn reseed_serves(
+    mut deal: ResMut<ServeDeal>,
+    playback: Option<Res<ReplayPlayback>>,
+    run_seed: Res<RunSeed>,
+    current_level: Res<CurrentLevel>,
+) {
+    let seed = match playback {
+        Some(playback) => playback.header().serve_seed,
+        None => (u64::from(run_seed.0) << 32) | current_level.0 as u64,
+    };
+    *deal = ServeDeal::seeded(seed);
+}
@@ -0,0 +1,127 @@
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::accessibility::Palette;
+use crate::config::Difficulty;
+use crate::controls::{ControlScheme, InputProfile};
+use crate::mutators::Mutator;
+use crate::skins::BallSkin;
+
+const SETTINGS_PATH: &str = "settings.ron";
+
+/// Player preferences that survive between runs, stored as RON next to the game.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Settings {
+    pub fullscreen: bool,
+    pub vsync: bool,
+    /// Lets the window be resized by dragging its edges.
+    pub resizable_window: bool,
+    /// Upper bound on frames per second; `None` leaves the frame rate uncapped.
+    pub fps_cap: Option<u32>,
+    /// Multiplier applied to all UI on top of the OS scale factor.
+    pub ui_scale: f32,
+    /// Shows the run timer and level splits in the corner of the screen.
+    pub speedrun_timer: bool,
+    /// Difficulty picked on the splash screen, remembered for the next launch.
+    pub difficulty: Difficulty,
+    /// Block colors; see `Palette` for the colorblind-friendly presets.
+    pub palette: Palette,
+    /// Dark flat background, outlined ball, paddle and blocks, and no decorative effects.
+    pub high_contrast: bool,
+    /// Turns off moving and flashing effects that don't affect gameplay.
+    pub reduced_motion: bool,
+    /// Speed of the whole simulation, from 0.6 to 1.0. Runs below full speed are marked
+    /// in the high scores.
+    pub game_speed: f32,
+    /// Multiplier on the ball's size, independent of difficulty.
+    pub ball_scale: f32,
+    /// Ball skin picked on the splash screen.
+    pub ball_skin: BallSkin,
+    /// Multiplier on the paddle's width, applied on top of the difficulty's width.
+    pub paddle_scale: f32,
+    /// Input device that drives the paddle alongside the keyboard.
+    pub control_scheme: ControlScheme,
+    /// Lets the paddle move up and down a little off its row, to meet the ball early.
+    pub advanced_controls: bool,
+    /// Keyboard bindings, one profile per local player.
+    pub input_profiles: Vec<InputProfile>,
+    /// Gamepad vibration on paddle hits, broken blocks and lost balls.
+    pub rumble: bool,
+    /// Volume of the announcer's callouts, from 0.0 (off) to 1.0.
+    pub voice_volume: f32,
+    /// Volume of sound effects such as menu clicks, from 0.0 (off) to 1.0.
+    pub sfx_volume: f32,
+    /// Shows the ghost of the fastest saved clear of each level.
+    pub ghost: bool,
+    /// Content packs under `mods/` that have been turned off, by folder name.
+    pub disabled_mods: Vec<String>,
+    /// Set once the tutorial has been finished or skipped, so it isn't run again unasked.
+    pub tutorial_done: bool,
+    /// Goes back to the splash screen after two minutes without input on the end and
+    /// menu screens, for a game left running unattended.
+    pub idle_timeout: bool,
+    /// Rule changes turned on for every run; see `Mutator`.
+    pub mutators: Vec<Mutator>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            fullscreen: false,
+            vsync: true,
+            resizable_window: true,
+            fps_cap: None,
+            ui_scale: 1.0,
+            speedrun_timer: false,
+            difficulty: Difficulty::Normal,
+            palette: Palette::Standard,
+            high_contrast: false,
+            reduced_motion: false,
+            game_speed: 1.0,
+            ball_scale: 1.0,
+            ball_skin: BallSkin::Ferris,
+            paddle_scale: 1.0,
+            control_scheme: ControlScheme::Keyboard,
+            advanced_controls: false,
+            input_profiles: InputProfile::defaults(),
+            rumble: true,
+            voice_volume: 0.75,
+            sfx_volume: 0.75,
+            ghost: true,
+            disabled_mods: Vec::new(),
+            tutorial_done: false,
+            idle_timeout: false,
+            mutators: Vec::new(),
+        }
+    }
+}
+
+impl Settings {
+    /// Reads the settings file, falling back to defaults if it is missing or malformed.
+    pub fn load() -> Self {
+        fs::read_to_string(SETTINGS_PATH)
+            .ok()
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let contents = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(err) => {
+                error!("failed to serialize settings: {err}");
+                return;
+            }
+        };
+        if let Err(err) = fs::write(SETTINGS_PATH, contents) {
+            error!("failed to write {SETTINGS_PATH}: {err}");
+        }
+    }
+
+    pub fn mutator(&self, mutator: Mutator) -> bool {
+        self.mutators.contains(&mutator)
+    }
+}
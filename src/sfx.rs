@@ -0,0 +1,106 @@
+use bevy::asset::UntypedAssetId;
+use bevy::audio::Volume;
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+
+use crate::announcer::VOLUME_STEPS;
+use crate::mods::ModRegistry;
+use crate::screenshot::{self, Toast};
+use crate::settings::Settings;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sfx {
+    MenuMove,
+    MenuConfirm,
+    MenuBack,
+    /// Played when a level's word is spelled.
+    Fanfare,
+}
+
+impl Sfx {
+    const ALL: [Sfx; 4] = [Sfx::MenuMove, Sfx::MenuConfirm, Sfx::MenuBack, Sfx::Fanfare];
+
+    /// Path under `assets/`, or a content pack, of the sound.
+    fn path(self) -> &'static str {
+        match self {
+            Sfx::MenuMove => "sfx/menu_move.wav",
+            Sfx::MenuConfirm => "sfx/menu_confirm.wav",
+            Sfx::MenuBack => "sfx/menu_back.wav",
+            Sfx::Fanfare => "sfx/fanfare.wav",
+        }
+    }
+}
+
+/// Plays a sound effect at the SFX volume.
+#[derive(Message)]
+pub struct PlaySfx(pub Sfx);
+
+/// Sounds kept loaded for the whole session so they play the moment they're asked for.
+#[derive(Resource)]
+pub struct SfxSounds(Vec<(Sfx, Handle<AudioSource>)>);
+
+impl SfxSounds {
+    pub fn ids(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        self.0.iter().map(|(_, handle)| handle.id().untyped())
+    }
+}
+
+pub struct SfxPlugin;
+
+impl Plugin for SfxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<PlaySfx>()
+            .add_systems(Startup, load_sounds)
+            .add_systems(Update, (cycle_sfx_volume, play_sounds).chain());
+    }
+}
+
+fn load_sounds(mut commands: Commands, asset_server: Res<AssetServer>, registry: Res<ModRegistry>) {
+    let sounds = Sfx::ALL
+        .into_iter()
+        .map(|sfx| (sfx, asset_server.load(registry.asset_path(sfx.path()))))
+        .collect();
+    commands.insert_resource(SfxSounds(sounds));
+}
+
+fn cycle_sfx_volume(
+    input: Res<ButtonInput<Key>>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !input.pressed(Key::Control) || !input.just_pressed(Key::Character("e".into())) {
+        return;
+    }
+    let current = VOLUME_STEPS
+        .iter()
+        .position(|step| *step >= settings.sfx_volume)
+        .unwrap_or(0);
+    settings.sfx_volume = VOLUME_STEPS[(current + 1) % VOLUME_STEPS.len()];
+    settings.save();
+    let message = format!("Sound effects volume {}%", (settings.sfx_volume * 100.0).round());
+    screenshot::show_toast(&mut commands, &toast_query, message);
+}
+
+fn play_sounds(
+    settings: Res<Settings>,
+    sounds: Option<Res<SfxSounds>>,
+    mut requests: MessageReader<PlaySfx>,
+    mut commands: Commands,
+) {
+    for PlaySfx(sfx) in requests.read() {
+        let Some(sounds) = sounds.as_ref() else {
+            continue;
+        };
+        if settings.sfx_volume <= 0.0 {
+            continue;
+        }
+        let Some((_, source)) = sounds.0.iter().find(|(sound, _)| sound == sfx) else {
+            continue;
+        };
+        commands.spawn((
+            AudioPlayer::new(source.clone()),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(settings.sfx_volume)),
+        ));
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use crate::collision::Side;
+
+pub const SHIELD_COLOR: Color = Color::srgb(0.75, 0.78, 0.85);
+/// How far the plate reaches into the block from its shielded face.
+pub const SHIELD_THICKNESS: f32 = 5.0;
+
+/// A metal plate on one face of a block. Hits on that face bounce off without doing any
+/// damage, so the block has to be broken from another side. Placed in a level's rows
+/// with `_` (bottom), `^` (top), `[` (left) or `]` (right).
+#[derive(Component, Clone, Copy)]
+pub struct Shield(pub Side);
+
+impl Shield {
+    pub fn from_cell(cell: char) -> Option<Self> {
+        let side = match cell {
+            '_' => Side::Bottom,
+            '^' => Side::Top,
+            '[' => Side::Left,
+            ']' => Side::Right,
+            _ => return None,
+        };
+        Some(Shield(side))
+    }
+
+    /// The level cell this shield is placed with, for saving it back out.
+    pub fn cell(self) -> char {
+        match self.0 {
+            Side::Bottom => '_',
+            Side::Top => '^',
+            Side::Left => '[',
+            Side::Right => ']',
+        }
+    }
+
+    /// Whether a hit on `side` of the block lands on the plate.
+    pub fn blocks(self, side: Side) -> bool {
+        self.0 == side
+    }
+
+    /// Corners (min, max) of the plate on a block centered at `center` with half size
+    /// `half`.
+    pub fn plate(self, center: Vec2, half: Vec2) -> (Vec2, Vec2) {
+        let (min, max) = (center - half, center + half);
+        match self.0 {
+            Side::Bottom => (min, Vec2::new(max.x, min.y + SHIELD_THICKNESS)),
+            Side::Top => (Vec2::new(min.x, max.y - SHIELD_THICKNESS), max),
+            Side::Left => (min, Vec2::new(min.x + SHIELD_THICKNESS, max.y)),
+            Side::Right => (Vec2::new(max.x - SHIELD_THICKNESS, min.y), max),
+        }
+    }
+}
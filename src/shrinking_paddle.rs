@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+use crate::mutators::Mutator;
+use crate::settings::Settings;
+use crate::{BlockDestroyed, GameState, Paddle, PaddleWidth};
+
+const BLOCKS_PER_STEP: u32 = 10;
+/// Share of its width the paddle loses every `BLOCKS_PER_STEP` blocks.
+const NARROWING_STEP: f32 = 0.05;
+/// Narrowest the paddle gets, as a share of its full width.
+const NARROWING_FLOOR: f32 = 0.6;
+
+/// Blocks broken so far on the current level.
+#[derive(Resource, Default)]
+struct LevelBlocksBroken(u32);
+
+/// The shrinking-paddle mutator, which narrows the paddle as blocks break. Each level
+/// starts over at full width.
+pub struct ShrinkingPaddlePlugin;
+
+impl Plugin for ShrinkingPaddlePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelBlocksBroken>()
+            .add_systems(OnEnter(GameState::Playing), reset_blocks_broken)
+            .add_systems(
+                Update,
+                narrow_paddle.run_if(
+                    in_state(GameState::Playing)
+                        .and(|settings: Res<Settings>| settings.mutator(Mutator::ShrinkingPaddle)),
+                ),
+            );
+    }
+}
+
+fn reset_blocks_broken(mut broken: ResMut<LevelBlocksBroken>) {
+    broken.0 = 0;
+}
+
+fn narrow_paddle(
+    mut destroyed: MessageReader<BlockDestroyed>,
+    mut broken: ResMut<LevelBlocksBroken>,
+    mut paddle_query: Query<&mut PaddleWidth, With<Paddle>>,
+) {
+    let count = destroyed.read().count() as u32;
+    if count == 0 {
+        return;
+    }
+    broken.0 += count;
+    let steps = (broken.0 / BLOCKS_PER_STEP) as f32;
+    let narrowing = (1.0 - NARROWING_STEP * steps).max(NARROWING_FLOOR);
+    for mut width in &mut paddle_query {
+        if width.narrowing != narrowing {
+            width.narrowing = narrowing;
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use std::fs;
+
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Difficulty;
+use crate::display::GameCamera;
+use crate::menu::{MenuActivated, MenuItem};
+use crate::mods::ModRegistry;
+use crate::progression::PlayerProgress;
+use crate::screenshot::{self, Toast};
+use crate::settings::Settings;
+use crate::{touch, GameState};
+
+const UNLOCKS_PATH: &str = "unlocks.ron";
+
+const SKIN_TEXT_Y: f32 = -245.0;
+const SKIN_TEXT_SIZE: Vec2 = Vec2::new(360.0, 30.0);
+const GOLD: Color = Color::srgb(1.0, 0.84, 0.0);
+const OCEAN: Color = Color::srgb(0.35, 0.65, 1.0);
+/// Player level that unlocks the Ocean Ferris.
+const OCEAN_LEVEL: u32 = 3;
+
+/// Look of the ball, picked on the splash screen from the ones unlocked so far.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BallSkin {
+    #[default]
+    Ferris,
+    /// A plain white square, like the original Pong.
+    Classic,
+    GoldenFerris,
+    OceanFerris,
+}
+
+impl BallSkin {
+    const ALL: [BallSkin; 4] = [
+        BallSkin::Ferris,
+        BallSkin::Classic,
+        BallSkin::GoldenFerris,
+        BallSkin::OceanFerris,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            BallSkin::Ferris => "Ferris",
+            BallSkin::Classic => "Classic",
+            BallSkin::GoldenFerris => "Golden Ferris",
+            BallSkin::OceanFerris => "Ocean Ferris",
+        }
+    }
+
+    /// Color the ball's sprite is tinted with.
+    pub fn tint(self) -> Color {
+        match self {
+            BallSkin::Ferris | BallSkin::Classic => Color::WHITE,
+            BallSkin::GoldenFerris => GOLD,
+            BallSkin::OceanFerris => OCEAN,
+        }
+    }
+
+    /// Sprite for a ball of the given size.
+    pub fn sprite(self, asset_server: &AssetServer, registry: &ModRegistry, size: Vec2) -> Sprite {
+        let ferris = || asset_server.load(registry.asset_path("ferris.png"));
+        match self {
+            BallSkin::Ferris => Sprite {
+                image: ferris(),
+                custom_size: Some(size),
+                ..default()
+            },
+            BallSkin::Classic => Sprite {
+                color: Color::WHITE,
+                custom_size: Some(size),
+                ..default()
+            },
+            BallSkin::GoldenFerris | BallSkin::OceanFerris => Sprite {
+                image: ferris(),
+                color: self.tint(),
+                custom_size: Some(size),
+                ..default()
+            },
+        }
+    }
+}
+
+/// Skins earned so far, kept next to the game like the high scores. Ferris is always
+/// available.
+///
+/// - Classic: clear any level
+/// - Golden Ferris: win on Hard
+/// - Ocean Ferris: reach player level `OCEAN_LEVEL`
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+struct UnlockedSkins {
+    skins: Vec<BallSkin>,
+}
+
+impl UnlockedSkins {
+    fn load() -> Self {
+        fs::read_to_string(UNLOCKS_PATH)
+            .ok()
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        let contents = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(err) => {
+                error!("failed to serialize unlocks: {err}");
+                return;
+            }
+        };
+        if let Err(err) = fs::write(UNLOCKS_PATH, contents) {
+            error!("failed to write {UNLOCKS_PATH}: {err}");
+        }
+    }
+
+    fn contains(&self, skin: BallSkin) -> bool {
+        skin == BallSkin::Ferris || self.skins.contains(&skin)
+    }
+
+    /// Unlocks `skin`, returning whether it's new.
+    fn unlock(&mut self, skin: BallSkin) -> bool {
+        if self.contains(skin) {
+            return false;
+        }
+        self.skins.push(skin);
+        self.save();
+        true
+    }
+}
+
+#[derive(Component)]
+struct SkinText;
+
+pub struct SkinsPlugin;
+
+impl Plugin for SkinsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(UnlockedSkins::load())
+            .add_systems(Startup, drop_locked_skin)
+            .add_systems(OnEnter(GameState::Splash), spawn_skin_text)
+            .add_systems(Update, select_skin.run_if(in_state(GameState::Splash)))
+            .add_systems(OnExit(GameState::Splash), despawn_skin_text)
+            .add_systems(OnEnter(GameState::LevelComplete), unlock_classic)
+            .add_systems(OnEnter(GameState::GameWon), unlock_golden_ferris)
+            .add_systems(Update, unlock_level_rewards.run_if(resource_changed::<PlayerProgress>));
+    }
+}
+
+// A skin picked in a settings file from another machine may not be unlocked here.
+fn drop_locked_skin(unlocked: Res<UnlockedSkins>, mut settings: ResMut<Settings>) {
+    if !unlocked.contains(settings.ball_skin) {
+        settings.ball_skin = BallSkin::Ferris;
+    }
+}
+
+fn skin_label(settings: &Settings) -> String {
+    format!("Ball: {}", settings.ball_skin.name())
+}
+
+fn spawn_skin_text(mut commands: Commands, settings: Res<Settings>) {
+    commands.spawn((
+        Text2d(skin_label(&settings)),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, SKIN_TEXT_Y, 2.0),
+        MenuItem {
+            order: 2,
+            size: SKIN_TEXT_SIZE,
+        },
+        SkinText,
+    ));
+}
+
+fn select_skin(
+    touches: Res<Touches>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    mut activated: MessageReader<MenuActivated>,
+    unlocked: Res<UnlockedSkins>,
+    mut settings: ResMut<Settings>,
+    mut text_query: Query<(Entity, &mut Text2d), With<SkinText>>,
+) {
+    let confirmed = activated
+        .read()
+        .any(|MenuActivated(entity)| text_query.contains(*entity));
+    let tapped = camera_query.single().is_ok_and(|(camera, camera_transform)| {
+        touch::tapped_within(
+            &touches,
+            camera,
+            camera_transform,
+            Vec2::new(0.0, SKIN_TEXT_Y),
+            SKIN_TEXT_SIZE,
+        )
+    });
+    if !confirmed && !tapped {
+        return;
+    }
+
+    let current = BallSkin::ALL
+        .iter()
+        .position(|skin| *skin == settings.ball_skin)
+        .unwrap_or(0);
+    let next = (1..=BallSkin::ALL.len())
+        .map(|offset| BallSkin::ALL[(current + offset) % BallSkin::ALL.len()])
+        .find(|skin| unlocked.contains(*skin))
+        .unwrap_or_default();
+    settings.ball_skin = next;
+    settings.save();
+    for (_, mut text) in &mut text_query {
+        text.0 = skin_label(&settings);
+    }
+}
+
+fn despawn_skin_text(mut commands: Commands, text_query: Query<Entity, With<SkinText>>) {
+    for entity in &text_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn announce_unlock(commands: &mut Commands, toast_query: &Query<Entity, With<Toast>>, skin: BallSkin) {
+    let message = format!("Unlocked ball: {}", skin.name());
+    screenshot::show_toast(commands, toast_query, message);
+}
+
+fn unlock_classic(
+    mut unlocked: ResMut<UnlockedSkins>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if unlocked.unlock(BallSkin::Classic) {
+        announce_unlock(&mut commands, &toast_query, BallSkin::Classic);
+    }
+}
+
+fn unlock_golden_ferris(
+    settings: Res<Settings>,
+    mut unlocked: ResMut<UnlockedSkins>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if settings.difficulty == Difficulty::Hard && unlocked.unlock(BallSkin::GoldenFerris) {
+        announce_unlock(&mut commands, &toast_query, BallSkin::GoldenFerris);
+    }
+}
+
+fn unlock_level_rewards(
+    progress: Res<PlayerProgress>,
+    mut unlocked: ResMut<UnlockedSkins>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if progress.level() >= OCEAN_LEVEL && unlocked.unlock(BallSkin::OceanFerris) {
+        announce_unlock(&mut commands, &toast_query, BallSkin::OceanFerris);
+    }
+}
@@ -0,0 +1,249 @@
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::bullet_time::BulletTime;
+use crate::hazard::Hazard;
+use crate::letters::{self, Letter};
+use crate::level::{CurrentLevel, LevelClock};
+use crate::scoring::{Combo, GameScore};
+use crate::screenshot::{self, Toast};
+use crate::scripting::Gravity;
+use crate::shield::Shield;
+use crate::{spawn_block, Ball, Block, BlockHealth, BlockPoints, BumpEnergy, GameState, Paddle, Serving, Spin, Velocity};
+
+const SNAPSHOT_DIR: &str = "snapshots";
+
+#[derive(Serialize, Deserialize)]
+struct BallState {
+    position: (f32, f32),
+    velocity: (f32, f32),
+    spin: f32,
+    /// Serve aim while the ball is still on the paddle.
+    serving: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BlockState {
+    position: (f32, f32),
+    health: u32,
+    #[serde(default)]
+    hazard: bool,
+    #[serde(default = "default_points")]
+    points: u32,
+    /// The level cell the block's shield is placed with, if it has one.
+    #[serde(default)]
+    shield: Option<char>,
+    #[serde(default)]
+    letter: Option<char>,
+}
+
+fn default_points() -> u32 {
+    1
+}
+
+/// Everything needed to put a level back the way it was, for bug reports and test
+/// fixtures. Saved with Ctrl+S and loaded back, from the newest file, with Ctrl+L.
+///
+/// Random effects such as force-zone streaks aren't seeded, so they won't replay exactly.
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    level: usize,
+    level_time: f32,
+    score: u32,
+    combo: u32,
+    bump_energy: f32,
+    focus: f32,
+    gravity: (f32, f32),
+    paddle_x: Option<f32>,
+    balls: Vec<BallState>,
+    blocks: Vec<BlockState>,
+}
+
+pub struct SnapshotPlugin;
+
+impl Plugin for SnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (export_snapshot, import_snapshot).run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+fn to_tuple(vector: Vec2) -> (f32, f32) {
+    (vector.x, vector.y)
+}
+
+fn from_tuple((x, y): (f32, f32)) -> Vec2 {
+    Vec2::new(x, y)
+}
+
+fn latest_snapshot() -> Option<PathBuf> {
+    // File names carry a timestamp, so the newest sorts last.
+    fs::read_dir(SNAPSHOT_DIR)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .max()
+}
+
+fn export_snapshot(
+    input: Res<ButtonInput<Key>>,
+    current_level: Res<CurrentLevel>,
+    clock: Res<LevelClock>,
+    score: Res<GameScore>,
+    combo: Res<Combo>,
+    energy: Res<BumpEnergy>,
+    bullet_time: Res<BulletTime>,
+    gravity: Res<Gravity>,
+    paddle_query: Query<&Transform, With<Paddle>>,
+    ball_query: Query<(&Transform, &Velocity, &Spin, Option<&Serving>), With<Ball>>,
+    block_query: Query<
+        (&Transform, &BlockHealth, &BlockPoints, Has<Hazard>, Option<&Shield>, Option<&Letter>),
+        With<Block>,
+    >,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !input.pressed(Key::Control) || !input.just_pressed(Key::Character("s".into())) {
+        return;
+    }
+
+    let snapshot = Snapshot {
+        level: current_level.0,
+        level_time: clock.elapsed,
+        score: score.0,
+        combo: combo.count,
+        bump_energy: energy.0,
+        focus: bullet_time.focus,
+        gravity: to_tuple(gravity.0),
+        paddle_x: paddle_query.iter().next().map(|transform| transform.translation.x),
+        balls: ball_query
+            .iter()
+            .map(|(transform, velocity, spin, serving)| BallState {
+                position: to_tuple(transform.translation.truncate()),
+                velocity: to_tuple(velocity.0),
+                spin: spin.0,
+                serving: serving.map(|serving| serving.aim),
+            })
+            .collect(),
+        blocks: block_query
+            .iter()
+            .map(|(transform, health, points, hazard, shield, letter)| BlockState {
+                position: to_tuple(transform.translation.truncate()),
+                health: health.0,
+                hazard,
+                points: points.0,
+                shield: shield.map(|shield| shield.cell()),
+                letter: letter.map(|letter| letter.0),
+            })
+            .collect(),
+    };
+
+    let contents = match serde_json::to_string_pretty(&snapshot) {
+        Ok(contents) => contents,
+        Err(err) => {
+            error!("failed to serialize snapshot: {err}");
+            return;
+        }
+    };
+    if let Err(err) = fs::create_dir_all(SNAPSHOT_DIR) {
+        error!("failed to create {SNAPSHOT_DIR}: {err}");
+        return;
+    }
+    let path = format!("{SNAPSHOT_DIR}/pong-{}.json", screenshot::timestamp());
+    match fs::write(&path, contents) {
+        Ok(()) => screenshot::show_toast(&mut commands, &toast_query, format!("Saved {path}")),
+        Err(err) => error!("failed to write {path}: {err}"),
+    }
+}
+
+fn import_snapshot(
+    input: Res<ButtonInput<Key>>,
+    current_level: Res<CurrentLevel>,
+    mut clock: ResMut<LevelClock>,
+    mut score: ResMut<GameScore>,
+    mut combo: ResMut<Combo>,
+    mut energy: ResMut<BumpEnergy>,
+    mut bullet_time: ResMut<BulletTime>,
+    mut gravity: ResMut<Gravity>,
+    mut paddle_query: Query<&mut Transform, (With<Paddle>, Without<Ball>)>,
+    mut ball_query: Query<(Entity, &mut Transform, &mut Velocity, &mut Spin), With<Ball>>,
+    block_query: Query<Entity, With<Block>>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !input.pressed(Key::Control) || !input.just_pressed(Key::Character("l".into())) {
+        return;
+    }
+    let Some(path) = latest_snapshot() else {
+        screenshot::show_toast(&mut commands, &toast_query, format!("No snapshots in {SNAPSHOT_DIR}"));
+        return;
+    };
+    let snapshot = fs::read_to_string(&path)
+        .map_err(|err| err.to_string())
+        .and_then(|contents| serde_json::from_str::<Snapshot>(&contents).map_err(|err| err.to_string()));
+    let snapshot = match snapshot {
+        Ok(snapshot) => snapshot,
+        Err(err) => {
+            error!("failed to load {}: {err}", path.display());
+            return;
+        }
+    };
+    // Entities beyond the blocks and balls come from the level file, so only snapshots of
+    // the level being played can be restored.
+    if snapshot.level != current_level.0 {
+        let message = format!("Snapshot is from level {}", snapshot.level + 1);
+        screenshot::show_toast(&mut commands, &toast_query, message);
+        return;
+    }
+
+    clock.elapsed = snapshot.level_time;
+    score.0 = snapshot.score;
+    combo.count = snapshot.combo;
+    energy.0 = snapshot.bump_energy;
+    bullet_time.focus = snapshot.focus;
+    gravity.0 = from_tuple(snapshot.gravity);
+    if let Some(x) = snapshot.paddle_x {
+        for mut transform in &mut paddle_query {
+            transform.translation.x = x;
+        }
+    }
+
+    // Extra balls in the snapshot have nothing to go on, and extra balls in play are left
+    // as they are.
+    for (ball, state) in ball_query.iter_mut().zip(&snapshot.balls) {
+        let (entity, mut transform, mut velocity, mut spin) = ball;
+        let position = from_tuple(state.position);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+        velocity.0 = from_tuple(state.velocity);
+        spin.0 = state.spin;
+        match state.serving {
+            Some(aim) => commands.entity(entity).insert(Serving { aim }),
+            None => commands.entity(entity).remove::<Serving>(),
+        };
+    }
+
+    for entity in &block_query {
+        commands.entity(entity).despawn();
+    }
+    for block in &snapshot.blocks {
+        let entity = spawn_block(&mut commands, from_tuple(block.position), block.health, block.points);
+        if block.hazard {
+            commands.entity(entity).insert(Hazard);
+        }
+        if let Some(shield) = block.shield.and_then(Shield::from_cell) {
+            commands.entity(entity).insert(shield);
+        }
+        if let Some(letter) = block.letter {
+            letters::spawn_letter(&mut commands, entity, letter);
+        }
+    }
+    screenshot::show_toast(&mut commands, &toast_query, format!("Loaded {}", path.display()));
+}
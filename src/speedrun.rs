@@ -0,0 +1,294 @@
+use std::fs;
+
+use bevy::input::keyboard::Key;
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::cheats::Cheats;
+use crate::debug::DebugTimeScale;
+use crate::level::CurrentLevel;
+use crate::settings::Settings;
+use crate::GameState;
+
+const SPLITS_PATH: &str = "splits.ron";
+
+const GOLD: Color = Color::srgb(1.0, 0.84, 0.0);
+const AHEAD: Color = Color::srgb(0.3, 0.9, 0.4);
+const BEHIND: Color = Color::srgb(0.95, 0.3, 0.3);
+
+/// The current run. `splits` holds the cumulative time at each level clear.
+#[derive(Resource, Default)]
+pub struct RunTimer {
+    pub running: bool,
+    pub elapsed: f32,
+    pub splits: Vec<f32>,
+    /// Whether the run counts towards the personal best: it started on the first level
+    /// and has been played without cheats or the debug time scale.
+    pub ranked: bool,
+}
+
+/// Splits of the fastest completed run, plus the best time ever seen for each level.
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+struct PersonalBest {
+    splits: Vec<f32>,
+    best_segments: Vec<f32>,
+}
+
+impl PersonalBest {
+    fn load() -> Self {
+        fs::read_to_string(SPLITS_PATH)
+            .ok()
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        let contents = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(err) => {
+                error!("failed to serialize splits: {err}");
+                return;
+            }
+        };
+        if let Err(err) = fs::write(SPLITS_PATH, contents) {
+            error!("failed to write {SPLITS_PATH}: {err}");
+        }
+    }
+}
+
+#[derive(Component)]
+struct TimerText;
+
+#[derive(Component)]
+struct SplitText;
+
+#[derive(Component)]
+struct SpeedrunHud;
+
+pub struct SpeedrunPlugin;
+
+impl Plugin for SpeedrunPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunTimer>()
+            .insert_resource(PersonalBest::load())
+            .add_systems(Startup, spawn_speedrun_hud)
+            .add_systems(OnEnter(GameState::Playing), reset_on_new_run)
+            .add_systems(
+                Update,
+                (start_on_first_input, tick_run_timer)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnEnter(GameState::LevelComplete), record_split)
+            .add_systems(OnEnter(GameState::GameWon), finish_run)
+            .add_systems(
+                Update,
+                (
+                    toggle_speedrun_timer,
+                    update_hud_visibility.run_if(resource_changed::<Settings>),
+                    update_timer_text.run_if(resource_changed::<RunTimer>),
+                ),
+            );
+    }
+}
+
+// Rounded to hundredths before splitting off the minutes, so 59.999 shows as 1:00.00
+// rather than 0:60.00.
+fn format_time(seconds: f32) -> String {
+    let hundredths = (seconds * 100.0).round() as u32;
+    let (minutes, hundredths) = (hundredths / 6000, hundredths % 6000);
+    format!("{minutes}:{:02}.{:02}", hundredths / 100, hundredths % 100)
+}
+
+fn hud_visibility(settings: &Settings) -> Visibility {
+    if settings.speedrun_timer {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+fn spawn_speedrun_hud(mut commands: Commands, settings: Res<Settings>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(40.0),
+                top: Val::Px(30.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexEnd,
+                ..default()
+            },
+            hud_visibility(&settings),
+            SpeedrunHud,
+        ))
+        .with_children(|parent| {
+            parent.spawn((Text::new(format_time(0.0)), TimerText));
+            parent.spawn((Text::new(""), TextColor(AHEAD), SplitText));
+        });
+}
+
+fn toggle_speedrun_timer(input: Res<ButtonInput<Key>>, mut settings: ResMut<Settings>) {
+    if input.just_pressed(Key::F6) {
+        settings.speedrun_timer = !settings.speedrun_timer;
+        settings.save();
+    }
+}
+
+fn update_hud_visibility(
+    settings: Res<Settings>,
+    mut hud_query: Query<&mut Visibility, With<SpeedrunHud>>,
+) {
+    for mut visibility in &mut hud_query {
+        *visibility = hud_visibility(&settings);
+    }
+}
+
+// Entering `Playing` on the first level means a fresh run, either from the splash
+// screen or from restarting.
+fn reset_on_new_run(
+    current_level: Res<CurrentLevel>,
+    mut timer: ResMut<RunTimer>,
+    mut split_query: Query<&mut Text, With<SplitText>>,
+) {
+    if current_level.0 != 0 {
+        return;
+    }
+    *timer = RunTimer {
+        ranked: true,
+        ..default()
+    };
+    for mut text in &mut split_query {
+        text.clear();
+    }
+}
+
+fn start_on_first_input(
+    input: Res<ButtonInput<Key>>,
+    touches: Res<Touches>,
+    mut timer: ResMut<RunTimer>,
+) {
+    if !timer.running
+        && timer.splits.is_empty()
+        && (input.get_just_pressed().next().is_some() || touches.any_just_pressed())
+    {
+        timer.running = true;
+    }
+}
+
+// Only ticks during play, so time spent reading the level breakdown doesn't count. It
+// counts real time, so slowing the game down makes for a longer run rather than a
+// shorter one.
+fn tick_run_timer(
+    real_time: Res<Time<Real>>,
+    virtual_time: Res<Time<Virtual>>,
+    debug_scale: Res<DebugTimeScale>,
+    cheats: Res<Cheats>,
+    mut timer: ResMut<RunTimer>,
+) {
+    // Paused frames have no virtual delta and leave the timer, and its text, untouched.
+    if !timer.running || virtual_time.delta_secs() <= 0.0 {
+        return;
+    }
+    timer.elapsed += real_time.delta_secs();
+    if cheats.any() || debug_scale.0 != 1.0 {
+        timer.ranked = false;
+    }
+}
+
+fn record_split(
+    mut timer: ResMut<RunTimer>,
+    mut best: ResMut<PersonalBest>,
+    mut split_query: Query<(&mut Text, &mut TextColor), With<SplitText>>,
+) {
+    if !timer.running {
+        return;
+    }
+    let index = timer.splits.len();
+    let previous = timer.splits.last().copied().unwrap_or(0.0);
+    let segment = timer.elapsed - previous;
+    timer.splits.push(timer.elapsed);
+
+    let gold = timer.ranked
+        && best
+            .best_segments
+            .get(index)
+            .is_none_or(|best_segment| segment < *best_segment);
+    if gold {
+        if index < best.best_segments.len() {
+            best.best_segments[index] = segment;
+        } else {
+            best.best_segments.push(segment);
+        }
+        best.save();
+    }
+
+    let (label, color) = match best.splits.get(index).filter(|_| timer.ranked) {
+        Some(pb_split) => {
+            let delta = timer.elapsed - pb_split;
+            let color = if gold {
+                GOLD
+            } else if delta < 0.0 {
+                AHEAD
+            } else {
+                BEHIND
+            };
+            (format!("{}{:.2}", if delta < 0.0 { "-" } else { "+" }, delta.abs()), color)
+        }
+        None => (format_time(timer.elapsed), if gold { GOLD } else { AHEAD }),
+    };
+    for (mut text, mut text_color) in &mut split_query {
+        **text = format!("Split {}: {label}", index + 1);
+        text_color.0 = color;
+    }
+}
+
+fn finish_run(mut timer: ResMut<RunTimer>, mut best: ResMut<PersonalBest>) {
+    if !timer.running {
+        return;
+    }
+    timer.running = false;
+
+    // Only a run over the same levels compares against the personal best.
+    let is_pb = timer.ranked
+        && (best.splits.is_empty()
+            || (best.splits.len() == timer.splits.len()
+                && best.splits.last().is_some_and(|pb_total| timer.elapsed < *pb_total)));
+    if is_pb {
+        best.splits = timer.splits.clone();
+        best.save();
+    }
+}
+
+fn update_timer_text(timer: Res<RunTimer>, mut text_query: Query<&mut Text, With<TimerText>>) {
+    for mut text in &mut text_query {
+        **text = format_time(timer.elapsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn under_a_second() {
+        assert_eq!(format_time(0.0), "0:00.00");
+        assert_eq!(format_time(0.5), "0:00.50");
+    }
+
+    #[test]
+    fn over_a_minute() {
+        assert_eq!(format_time(75.25), "1:15.25");
+        assert_eq!(format_time(3600.0), "60:00.00");
+    }
+
+    #[test]
+    fn rounds_to_hundredths() {
+        assert_eq!(format_time(1.234), "0:01.23");
+        assert_eq!(format_time(1.236), "0:01.24");
+        assert_eq!(format_time(59.999), "1:00.00");
+        assert_eq!(format_time(9.996), "0:10.00");
+    }
+}
@@ -0,0 +1,88 @@
+use bevy::math::bounding::Aabb2d;
+use bevy::prelude::*;
+
+use crate::{Collider, GameState, Paddle, PADDLE_HEIGHT};
+
+/// Space between the two halves of a split paddle, wide enough for the ball to fall
+/// through.
+pub const SPLIT_PADDLE_GAP: f32 = 60.0;
+
+/// A paddle made of two half-width segments side by side with a gap in the middle, so
+/// nothing can be caught dead center.
+///
+/// The paddle's `Collider` spans both segments and the gap, which keeps movement and
+/// clamping as they are; anything the ball or a projectile can touch goes through
+/// `parts` instead. The paddle's own sprite is hidden and each segment draws itself.
+#[derive(Component)]
+pub struct SplitPaddle;
+
+/// One half of a split paddle, on the left (-1.0) or right (1.0) of the gap.
+#[derive(Component)]
+struct Segment(f32);
+
+pub struct SplitPaddlePlugin;
+
+impl Plugin for SplitPaddlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, layout_segments.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// Full width of a paddle whose segments are each half of `width`.
+pub fn span(width: f32, split: bool) -> f32 {
+    if split {
+        width + SPLIT_PADDLE_GAP
+    } else {
+        width
+    }
+}
+
+/// The boxes a paddle centered at `center` can be hit on.
+pub fn parts(center: Vec2, collider: &Collider, split: bool) -> Vec<Aabb2d> {
+    if !split {
+        return vec![collider.aabb(center)];
+    }
+    let half_size = Vec2::new(segment_width(collider) / 2.0, collider.size.y / 2.0);
+    [-1.0, 1.0]
+        .into_iter()
+        .map(|side| Aabb2d::new(center + Vec2::X * segment_offset(collider) * side, half_size))
+        .collect()
+}
+
+fn segment_width(collider: &Collider) -> f32 {
+    (collider.size.x - SPLIT_PADDLE_GAP) / 2.0
+}
+
+fn segment_offset(collider: &Collider) -> f32 {
+    (SPLIT_PADDLE_GAP + segment_width(collider)) / 2.0
+}
+
+/// Splits `paddle` in two. Its own sprite is left as it is, so it should be spawned clear.
+pub fn split(commands: &mut Commands, paddle: Entity) {
+    commands.entity(paddle).insert(SplitPaddle).with_children(|parent| {
+        for side in [-1.0, 1.0] {
+            parent.spawn((
+                Sprite {
+                    color: Color::WHITE,
+                    ..default()
+                },
+                Transform::default(),
+                Segment(side),
+            ));
+        }
+    });
+}
+
+// Follows the paddle's collider, so anything that resizes the paddle resizes the halves.
+fn layout_segments(
+    paddle_query: Query<&Collider, (With<Paddle>, With<SplitPaddle>)>,
+    mut segment_query: Query<(&Segment, &ChildOf, &mut Transform, &mut Sprite)>,
+) {
+    for (segment, child_of, mut transform, mut sprite) in &mut segment_query {
+        let Ok(collider) = paddle_query.get(child_of.parent()) else {
+            continue;
+        };
+        transform.translation.x = segment_offset(collider) * segment.0;
+        sprite.custom_size = Some(Vec2::new(segment_width(collider), PADDLE_HEIGHT));
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game_over::Lives;
+use crate::level::{CurrentLevel, LevelClock, LevelCompleteScreen, Levels};
+use crate::replay::ReplayPlayback;
+use crate::scoring::ComboChanged;
+use crate::GameState;
+
+const STARS_PATH: &str = "stars.ron";
+/// Combo multiplier a level's best streak has to reach to count towards its rating.
+const STAR_COMBO: u32 = 5;
+
+/// Best rating earned on each level, keyed by name so adding or removing content packs
+/// doesn't shift them.
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct StarRecord {
+    levels: HashMap<String, u32>,
+}
+
+impl StarRecord {
+    fn load() -> Self {
+        fs::read_to_string(STARS_PATH)
+            .ok()
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        let contents = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(err) => {
+                error!("failed to serialize stars: {err}");
+                return;
+            }
+        };
+        if let Err(err) = fs::write(STARS_PATH, contents) {
+            error!("failed to write {STARS_PATH}: {err}");
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.levels.values().sum()
+    }
+
+    /// Keeps `stars` for `level` if it beats the best so far, returning whether it did.
+    fn record(&mut self, level: &str, stars: u32) -> bool {
+        let best = self.levels.entry(level.to_string()).or_default();
+        if stars <= *best {
+            return false;
+        }
+        *best = stars;
+        self.save();
+        true
+    }
+}
+
+/// How the level being played is going, for grading it once it's cleared.
+#[derive(Resource, Default)]
+struct LevelPerformance {
+    lives_at_start: u32,
+    longest_combo: u32,
+}
+
+pub struct StarsPlugin;
+
+impl Plugin for StarsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(StarRecord::load())
+            .init_resource::<LevelPerformance>()
+            .add_systems(OnEnter(GameState::Playing), start_grading)
+            .add_systems(Update, track_combo.run_if(in_state(GameState::Playing)))
+            .add_systems(OnEnter(GameState::LevelComplete), grade_level);
+    }
+}
+
+fn start_grading(lives: Res<Lives>, mut performance: ResMut<LevelPerformance>) {
+    *performance = LevelPerformance {
+        lives_at_start: lives.0,
+        longest_combo: 0,
+    };
+}
+
+fn track_combo(mut combo_events: MessageReader<ComboChanged>, mut performance: ResMut<LevelPerformance>) {
+    for event in combo_events.read() {
+        performance.longest_combo = performance.longest_combo.max(event.multiplier);
+    }
+}
+
+/// One star for the clear itself, up to three for how many of these it managed: beating
+/// the target time, losing no lives, and reaching a x`STAR_COMBO` combo.
+fn grade_level(
+    mut commands: Commands,
+    levels: Res<Levels>,
+    current: Res<CurrentLevel>,
+    clock: Res<LevelClock>,
+    lives: Res<Lives>,
+    performance: Res<LevelPerformance>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut record: ResMut<StarRecord>,
+) {
+    let level = levels.get(current.0);
+    let met = [
+        clock.elapsed <= level.target_time,
+        lives.0 >= performance.lives_at_start,
+        performance.longest_combo >= STAR_COMBO,
+    ];
+    let stars = (met.iter().filter(|met| **met).count() as u32).max(1);
+
+    // A replay re-earns a rating that was already recorded.
+    let new_best = playback.is_none() && record.record(&level.name, stars);
+    let mut label = format!("Rating: {stars} of 3 stars");
+    if new_best {
+        label.push_str(" - new best!");
+    }
+    commands.spawn((
+        Text2d(label),
+        Transform::from_xyz(0.0, 0.0, 4.0),
+        LevelCompleteScreen,
+    ));
+}
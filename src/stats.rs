@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+
+use crate::collision::{BallHitBlock, BallHitPaddle, Side};
+use crate::level::CurrentLevel;
+use crate::scoring::ComboChanged;
+use crate::screenshot::{self, Toast};
+use crate::{BallLost, BlockDestroyed, Bumped, GameEntity, GameState};
+
+const SUMMARY_TOP_Y: f32 = 200.0;
+const SUMMARY_LINE_SPACING: f32 = 36.0;
+/// Return streaks worth calling out when they're reached.
+const STREAK_MILESTONES: [u32; 4] = [10, 25, 50, 100];
+
+/// Tallies for the current run, shown on the screen that ends it.
+#[derive(Resource, Default)]
+pub struct RunStats {
+    /// Seconds spent playing, leaving out pauses and the screens between levels.
+    pub time: f32,
+    pub blocks_broken: u32,
+    pub longest_combo: u32,
+    pub top_speed: f32,
+    pub bumps: u32,
+    pub paddle_hits: u32,
+    pub balls_lost: u32,
+    /// Paddle returns since the ball was last lost.
+    pub return_streak: u32,
+    pub longest_return_streak: u32,
+}
+
+impl RunStats {
+    /// Share of the balls that came down to the paddle and were sent back up.
+    fn accuracy(&self) -> Option<f32> {
+        let chances = self.paddle_hits + self.balls_lost;
+        (chances > 0).then(|| self.paddle_hits as f32 / chances as f32)
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let accuracy = match self.accuracy() {
+            Some(accuracy) => format!("{:.0}%", accuracy * 100.0),
+            None => "-".to_string(),
+        };
+        vec![
+            format!("Time: {:.1}s", self.time),
+            format!("Blocks broken: {}", self.blocks_broken),
+            format!("Longest combo: x{}", self.longest_combo.max(1)),
+            format!("Top speed: {:.0}", self.top_speed),
+            format!("Bumps used: {}", self.bumps),
+            format!("Paddle accuracy: {accuracy}"),
+            format!("Longest return streak: {}", self.longest_return_streak),
+        ]
+    }
+}
+
+/// Sent when the return streak reaches one of `STREAK_MILESTONES`.
+#[derive(Message)]
+pub struct ReturnStreakMilestone(pub u32);
+
+#[derive(Component)]
+struct StreakText;
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>()
+            .add_message::<ReturnStreakMilestone>()
+            .add_systems(OnEnter(GameState::Playing), (reset_on_new_run, spawn_streak_text).chain())
+            .add_systems(
+                Update,
+                (
+                    tick_run_time,
+                    count_events,
+                    announce_streak_milestones,
+                    update_streak_text.run_if(resource_changed::<RunStats>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+fn streak_label(stats: &RunStats) -> String {
+    format!("Streak: {} (best {})", stats.return_streak, stats.longest_return_streak)
+}
+
+/// Spawns the run's breakdown, one line per stat, tagged with `marker` so the screen
+/// showing it can clear it away.
+pub fn spawn_summary<M: Component + Clone>(commands: &mut Commands, stats: &RunStats, marker: M, z: f32) {
+    for (i, line) in stats.lines().into_iter().enumerate() {
+        commands.spawn((
+            Text2d(line),
+            TextFont {
+                font_size: 24.0,
+                ..default()
+            },
+            Transform::from_xyz(0.0, SUMMARY_TOP_Y - i as f32 * SUMMARY_LINE_SPACING, z),
+            marker.clone(),
+        ));
+    }
+}
+
+// Same rule as the speedrun timer: only starting on the first level is a new run.
+fn reset_on_new_run(current_level: Res<CurrentLevel>, mut stats: ResMut<RunStats>) {
+    if current_level.0 == 0 {
+        *stats = RunStats::default();
+    }
+}
+
+fn spawn_streak_text(mut commands: Commands, stats: Res<RunStats>) {
+    commands.spawn((
+        Text::new(streak_label(&stats)),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(40.0),
+            top: Val::Px(250.0),
+            ..default()
+        },
+        StreakText,
+        GameEntity,
+    ));
+}
+
+fn tick_run_time(time: Res<Time>, mut stats: ResMut<RunStats>) {
+    if time.delta_secs() > 0.0 {
+        stats.time += time.delta_secs();
+    }
+}
+
+fn count_events(
+    mut destroyed: MessageReader<BlockDestroyed>,
+    mut combo_events: MessageReader<ComboChanged>,
+    mut paddle_hits: MessageReader<BallHitPaddle>,
+    mut block_hits: MessageReader<BallHitBlock>,
+    mut bumps: MessageReader<Bumped>,
+    mut lost_events: MessageReader<BallLost>,
+    mut milestones: MessageWriter<ReturnStreakMilestone>,
+    mut stats: ResMut<RunStats>,
+) {
+    stats.blocks_broken += destroyed.read().count() as u32;
+    stats.bumps += bumps.read().count() as u32;
+    for event in combo_events.read() {
+        stats.longest_combo = stats.longest_combo.max(event.multiplier);
+    }
+    for event in paddle_hits.read() {
+        stats.paddle_hits += 1;
+        stats.top_speed = stats.top_speed.max(event.speed);
+        // Clipping the paddle's end doesn't send the ball back, so it doesn't count.
+        if matches!(event.side, Side::Top | Side::Bottom) {
+            stats.return_streak += 1;
+            stats.longest_return_streak = stats.longest_return_streak.max(stats.return_streak);
+            if STREAK_MILESTONES.contains(&stats.return_streak) {
+                milestones.write(ReturnStreakMilestone(stats.return_streak));
+            }
+        }
+    }
+    let lost = lost_events.read().count() as u32;
+    if lost > 0 {
+        stats.balls_lost += lost;
+        stats.return_streak = 0;
+    }
+    for event in block_hits.read() {
+        stats.top_speed = stats.top_speed.max(event.speed);
+    }
+}
+
+fn announce_streak_milestones(
+    mut milestones: MessageReader<ReturnStreakMilestone>,
+    mut commands: Commands,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    for milestone in milestones.read() {
+        screenshot::show_toast(&mut commands, &toast_query, format!("{} returns!", milestone.0));
+    }
+}
+
+fn update_streak_text(stats: Res<RunStats>, mut text_query: Query<&mut Text, With<StreakText>>) {
+    for mut text in &mut text_query {
+        **text = streak_label(&stats);
+    }
+}
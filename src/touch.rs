@@ -0,0 +1,54 @@
+use bevy::input::touch::{Touch, Touches};
+use bevy::prelude::*;
+
+/// How far (in logical pixels) a finger may travel and still count as a tap rather than a drag.
+const TAP_MAX_TRAVEL: f32 = 12.0;
+
+/// Converts a window position (touch or cursor) to world coordinates.
+// Window positions are in window coordinates, while `viewport_to_world_2d` expects
+// coordinates relative to the (letterboxed) viewport.
+pub fn to_world(camera: &Camera, camera_transform: &GlobalTransform, position: Vec2) -> Option<Vec2> {
+    let viewport_min = camera
+        .logical_viewport_rect()
+        .map_or(Vec2::ZERO, |rect| rect.min);
+    camera
+        .viewport_to_world_2d(camera_transform, position - viewport_min)
+        .ok()
+}
+
+fn is_tap(touch: &Touch) -> bool {
+    touch.distance().length() < TAP_MAX_TRAVEL
+}
+
+/// Horizontal distance, in world units, that all active fingers moved this frame.
+pub fn drag_x(touches: &Touches, camera: &Camera, camera_transform: &GlobalTransform) -> f32 {
+    touches
+        .iter()
+        .filter_map(|touch| {
+            let current = to_world(camera, camera_transform, touch.position())?;
+            let previous = to_world(camera, camera_transform, touch.previous_position())?;
+            Some(current.x - previous.x)
+        })
+        .sum()
+}
+
+/// Whether a finger was lifted this frame without having been dragged.
+pub fn tapped(touches: &Touches) -> bool {
+    touches.iter_just_released().any(is_tap)
+}
+
+/// Whether a tap landed inside a rectangle given in world coordinates.
+pub fn tapped_within(
+    touches: &Touches,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    center: Vec2,
+    size: Vec2,
+) -> bool {
+    let area = Rect::from_center_size(center, size);
+    touches
+        .iter_just_released()
+        .filter(|touch| is_tap(touch))
+        .filter_map(|touch| to_world(camera, camera_transform, touch.position()))
+        .any(|world| area.contains(world))
+}
@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+
+/// Gap between dots when drawing a predicted path.
+const DOT_SPACING: f32 = 18.0;
+const DOT_RADIUS: f32 = 2.5;
+
+/// Traces a straight-line path from `origin` along `direction`, reflecting off the
+/// edges of a box centered on the origin with the given half extents.
+///
+/// Returns the polyline's points, starting at `origin`, and stops after `max_bounces`
+/// reflections or `max_distance` units of travel, whichever comes first. Blocks and the
+/// paddle are not considered.
+pub fn predict_path(
+    origin: Vec2,
+    direction: Vec2,
+    half_extents: Vec2,
+    max_distance: f32,
+    max_bounces: usize,
+) -> Vec<Vec2> {
+    let mut points = vec![origin];
+    let Some(mut direction) = direction.try_normalize() else {
+        return points;
+    };
+    let mut position = origin;
+    let mut remaining = max_distance;
+
+    for _ in 0..=max_bounces {
+        let time_to_edge = |position: f32, direction: f32, half_extent: f32| {
+            if direction > 0.0 {
+                (half_extent - position) / direction
+            } else if direction < 0.0 {
+                (-half_extent - position) / direction
+            } else {
+                f32::INFINITY
+            }
+        };
+        let tx = time_to_edge(position.x, direction.x, half_extents.x).max(0.0);
+        let ty = time_to_edge(position.y, direction.y, half_extents.y).max(0.0);
+        let t = tx.min(ty).min(remaining);
+
+        position += direction * t;
+        points.push(position);
+        remaining -= t;
+        if remaining <= 0.0 {
+            break;
+        }
+
+        if tx <= ty {
+            direction.x = -direction.x;
+        }
+        if ty <= tx {
+            direction.y = -direction.y;
+        }
+    }
+    points
+}
+
+/// Draws a polyline as evenly spaced dots.
+pub fn draw_dotted(gizmos: &mut Gizmos, points: &[Vec2], color: Color) {
+    let mut carry = 0.0;
+    for segment in points.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+        let length = start.distance(end);
+        let Some(direction) = (end - start).try_normalize() else {
+            continue;
+        };
+        let mut distance = carry;
+        while distance <= length {
+            gizmos.circle_2d(start + direction * distance, DOT_RADIUS, color);
+            distance += DOT_SPACING;
+        }
+        carry = distance - length;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOX: Vec2 = Vec2::new(100.0, 100.0);
+
+    #[test]
+    fn straight_path_short_of_the_walls() {
+        let points = predict_path(Vec2::ZERO, Vec2::new(0.0, 2.0), BOX, 50.0, 3);
+        assert_eq!(points, vec![Vec2::ZERO, Vec2::new(0.0, 50.0)]);
+    }
+
+    #[test]
+    fn reflects_off_a_wall() {
+        let points = predict_path(Vec2::ZERO, Vec2::X, BOX, 150.0, 3);
+        assert_eq!(points, vec![Vec2::ZERO, Vec2::new(100.0, 0.0), Vec2::new(50.0, 0.0)]);
+    }
+
+    #[test]
+    fn stops_after_max_bounces() {
+        let points = predict_path(Vec2::ZERO, Vec2::X, BOX, 1000.0, 1);
+        assert_eq!(points, vec![Vec2::ZERO, Vec2::new(100.0, 0.0), Vec2::new(-100.0, 0.0)]);
+    }
+
+    #[test]
+    fn stops_after_max_distance() {
+        let points = predict_path(Vec2::ZERO, Vec2::X, BOX, 250.0, 10);
+        assert_eq!(points, vec![Vec2::ZERO, Vec2::new(100.0, 0.0), Vec2::new(-50.0, 0.0)]);
+    }
+
+    #[test]
+    fn no_direction_stays_put() {
+        assert_eq!(predict_path(Vec2::ZERO, Vec2::ZERO, BOX, 100.0, 3), vec![Vec2::ZERO]);
+    }
+}
@@ -0,0 +1,267 @@
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+
+use crate::cheats;
+use crate::debug::FrameStep;
+use crate::level::CurrentLevel;
+use crate::pause::{self, Paused};
+use crate::replay::ReplayPlayback;
+use crate::screenshot::{self, Toast};
+use crate::seed;
+use crate::settings::Settings;
+use crate::{
+    leave_splash, Ball, Bumped, GameEntity, GameState, Paddle, Serving, SplashScreen, StartButton, Velocity,
+};
+
+/// How far the paddle has to travel before the movement lesson counts as done.
+const MOVE_DISTANCE: f32 = 120.0;
+/// Seconds before reaching the paddle that the ball is frozen for the bump lesson.
+const BUMP_LEAD_TIME: f32 = 0.08;
+const PROMPT_Y: f32 = 120.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum TutorialStep {
+    Move,
+    Serve,
+    Bump,
+}
+
+impl TutorialStep {
+    fn prompt(self, waiting_for_bump: bool) -> &'static str {
+        match self {
+            TutorialStep::Move => "Move the paddle left and right\n(Tab: skip tutorial)",
+            TutorialStep::Serve => "Press Space to serve the ball",
+            TutorialStep::Bump if waiting_for_bump => "Press Space now to bump the ball!",
+            TutorialStep::Bump => "Bump the ball with Space just as it reaches the paddle\nfor extra speed",
+        }
+    }
+}
+
+/// Lessons laid over the first level: the next one waits until the player has done what
+/// the current one asks. Runs on the first play, and whenever it's picked on the splash
+/// screen.
+#[derive(Resource)]
+pub struct Tutorial {
+    step: Option<TutorialStep>,
+    /// Where the paddle was when the movement lesson started.
+    paddle_start: Option<f32>,
+    /// Set while the ball is frozen just above the paddle for the bump lesson.
+    waiting_for_bump: bool,
+}
+
+impl Tutorial {
+    fn new(run: bool) -> Self {
+        Tutorial {
+            step: run.then_some(TutorialStep::Move),
+            paddle_start: None,
+            waiting_for_bump: false,
+        }
+    }
+
+    /// Whether `level` is played with the tutorial over it.
+    pub fn guides(&self, level: usize) -> bool {
+        self.step.is_some() && level == 0
+    }
+}
+
+#[derive(Component)]
+struct TutorialPrompt;
+
+#[derive(Component)]
+struct TutorialHint;
+
+pub struct TutorialPlugin;
+
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        let done = app.world().resource::<Settings>().tutorial_done;
+        app.insert_resource(Tutorial::new(!done))
+            .add_systems(OnEnter(GameState::Splash), spawn_tutorial_hint)
+            .add_systems(OnExit(GameState::Splash), despawn_tutorial_hint)
+            .add_systems(
+                Update,
+                start_tutorial
+                    .after(cheats::detect_cheat_codes)
+                    .run_if(in_state(GameState::Splash).and(seed::not_entering_seed).and(cheats::not_typing_code)),
+            )
+            .add_systems(OnEnter(GameState::Playing), spawn_prompt.run_if(guiding))
+            .add_systems(
+                Update,
+                (advance_tutorial, skip_tutorial, update_prompt)
+                    .chain()
+                    .run_if(in_state(GameState::Playing).and(guiding)),
+            )
+            .add_systems(OnEnter(GameState::LevelComplete), finish_tutorial.run_if(guiding))
+            .add_systems(OnEnter(GameState::GameOver), finish_tutorial.run_if(guiding));
+    }
+}
+
+// Replays of the first level play without it.
+fn guiding(tutorial: Res<Tutorial>, current_level: Res<CurrentLevel>, playback: Option<Res<ReplayPlayback>>) -> bool {
+    tutorial.guides(current_level.0) && playback.is_none()
+}
+
+fn spawn_tutorial_hint(mut commands: Commands) {
+    commands.spawn((
+        Text2d("T: Tutorial".to_string()),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, 305.0, 2.0),
+        TutorialHint,
+    ));
+}
+
+fn despawn_tutorial_hint(mut commands: Commands, hint_query: Query<Entity, With<TutorialHint>>) {
+    for entity in &hint_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn start_tutorial(
+    input: Res<ButtonInput<Key>>,
+    mut tutorial: ResMut<Tutorial>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut commands: Commands,
+    splash_query: Query<Entity, Or<(With<SplashScreen>, With<StartButton>)>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if input.pressed(Key::Control) || !input.just_pressed(Key::Character("t".into())) {
+        return;
+    }
+    *tutorial = Tutorial::new(true);
+    current_level.0 = 0;
+    leave_splash(&mut commands, &splash_query, &mut next_state);
+}
+
+fn spawn_prompt(mut commands: Commands, mut tutorial: ResMut<Tutorial>) {
+    // The level starts over with a quick restart, and the lesson with it.
+    tutorial.paddle_start = None;
+    tutorial.waiting_for_bump = false;
+    let Some(step) = tutorial.step else {
+        return;
+    };
+    commands.spawn((
+        Text2d(step.prompt(false).to_string()),
+        TextFont {
+            font_size: 24.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, PROMPT_Y, 5.0),
+        TutorialPrompt,
+        GameEntity,
+    ));
+}
+
+fn end_tutorial(tutorial: &mut Tutorial, settings: &mut Settings) {
+    *tutorial = Tutorial::new(false);
+    settings.tutorial_done = true;
+    settings.save();
+}
+
+fn advance_tutorial(
+    mut tutorial: ResMut<Tutorial>,
+    paddle_query: Query<&Transform, With<Paddle>>,
+    ball_query: Query<(&Transform, &Velocity, Has<Serving>), With<Ball>>,
+    mut bumped: MessageReader<Bumped>,
+    paused: Res<Paused>,
+    frame_step: Res<FrameStep>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    let bumped = bumped.read().count() > 0;
+    let Some(step) = tutorial.step else {
+        return;
+    };
+    let Some(paddle) = paddle_query.iter().next() else {
+        return;
+    };
+    let ball = ball_query.iter().next();
+
+    match step {
+        TutorialStep::Move => {
+            let start = *tutorial.paddle_start.get_or_insert(paddle.translation.x);
+            if (paddle.translation.x - start).abs() >= MOVE_DISTANCE {
+                tutorial.step = Some(TutorialStep::Serve);
+            }
+        }
+        TutorialStep::Serve => {
+            if ball.is_some_and(|(_, _, serving)| !serving) {
+                tutorial.step = Some(TutorialStep::Bump);
+            }
+        }
+        TutorialStep::Bump if tutorial.waiting_for_bump => {
+            // The press is held over the pause, so it connects as soon as play resumes.
+            if bumped {
+                tutorial.waiting_for_bump = false;
+                tutorial.step = None;
+                pause::sync_virtual_time(&paused, &frame_step, &mut virtual_time);
+            }
+        }
+        TutorialStep::Bump => {
+            let Some((transform, velocity, false)) = ball else {
+                return;
+            };
+            let gap = transform.translation.y - paddle.translation.y;
+            if velocity.0.y < 0.0 && gap > 0.0 && gap < -velocity.0.y * BUMP_LEAD_TIME + 40.0 {
+                tutorial.waiting_for_bump = true;
+                virtual_time.pause();
+            }
+        }
+    }
+}
+
+fn skip_tutorial(
+    input: Res<ButtonInput<Key>>,
+    mut tutorial: ResMut<Tutorial>,
+    mut settings: ResMut<Settings>,
+    paused: Res<Paused>,
+    frame_step: Res<FrameStep>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut commands: Commands,
+    prompt_query: Query<Entity, With<TutorialPrompt>>,
+) {
+    if !input.just_pressed(Key::Tab) {
+        return;
+    }
+    if tutorial.waiting_for_bump {
+        pause::sync_virtual_time(&paused, &frame_step, &mut virtual_time);
+    }
+    end_tutorial(&mut tutorial, &mut settings);
+    for entity in &prompt_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn update_prompt(
+    mut tutorial: ResMut<Tutorial>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+    mut prompt_query: Query<(Entity, &mut Text2d), With<TutorialPrompt>>,
+    toast_query: Query<Entity, With<Toast>>,
+) {
+    if !tutorial.is_changed() {
+        return;
+    }
+    match tutorial.step {
+        Some(step) => {
+            for (_, mut text) in &mut prompt_query {
+                text.0 = step.prompt(tutorial.waiting_for_bump).to_string();
+            }
+        }
+        // Every lesson done; the rest of the level plays as normal.
+        None => {
+            for (entity, _) in &prompt_query {
+                commands.entity(entity).despawn();
+            }
+            end_tutorial(&mut tutorial, &mut settings);
+            let message = "Tutorial complete. Break every block to clear the level!".to_string();
+            screenshot::show_toast(&mut commands, &toast_query, message);
+        }
+    }
+}
+
+// Leaving the level any other way ends the tutorial too.
+fn finish_tutorial(mut tutorial: ResMut<Tutorial>, mut settings: ResMut<Settings>) {
+    end_tutorial(&mut tutorial, &mut settings);
+}
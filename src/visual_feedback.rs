@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+
+use crate::accessibility::{self, Palette};
+use crate::cheats::Cheats;
+use crate::config::GameConfig;
+use crate::pool::{self, EntityPool};
+use crate::settings::Settings;
+use crate::{pause, Ball, GameEntity, GameState, Paddle, PaddleVelocity, Serving, Velocity, PADDLE_SPEED};
+
+/// Seconds between the ghosts a trail leaves.
+const TRAIL_INTERVAL: f32 = 0.03;
+/// Seconds a ghost takes to fade out.
+const TRAIL_LIFETIME: f32 = 0.2;
+/// Opacity of a freshly left ghost.
+const TRAIL_ALPHA: f32 = 0.45;
+/// How far the ball's own tint moves toward the heat color at full speed; the rest is
+/// kept from its skin.
+const BALL_HEAT_TINT: f32 = 0.6;
+/// Share of the keyboard speed the paddle has to reach to leave a trail.
+const PADDLE_TRAIL_SPEED: f32 = 0.95;
+const PADDLE_TRAIL_COLOR: Color = Color::srgb(0.8, 0.9, 1.0);
+
+/// Leaves fading copies of an entity's sprite behind it while `active`, tinted `color`.
+/// Whatever owns the entity decides when it's worth showing.
+#[derive(Component)]
+pub struct Trail {
+    pub active: bool,
+    pub color: Color,
+    since_last: f32,
+}
+
+impl Default for Trail {
+    fn default() -> Self {
+        Trail {
+            active: false,
+            color: Color::WHITE,
+            since_last: 0.0,
+        }
+    }
+}
+
+/// One copy left behind by a `Trail`, with the seconds it has been fading.
+#[derive(Component)]
+pub struct TrailGhost {
+    age: f32,
+    color: Color,
+}
+
+/// Feedback on how the game is going that's worked out in one place: the ball heats up
+/// from cool to hot as it nears top speed and leaves a trail, and so does the paddle
+/// when it's swung at full speed.
+pub struct VisualFeedbackPlugin;
+
+impl Plugin for VisualFeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EntityPool<TrailGhost>>()
+            .add_systems(OnEnter(GameState::Playing), pool::clear_pool::<TrailGhost>)
+            .add_systems(
+                Update,
+                (
+                    (attach_trails, heat_balls, streak_paddles).chain(),
+                    spawn_trail_ghosts.run_if(accessibility::decorative_effects),
+                    fade_trail_ghosts,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing).and(pause::not_paused)),
+            );
+    }
+}
+
+/// Color for `heat` between 0.0 (cool) and 1.0 (hot) in the current palette.
+pub fn heat_color(palette: Palette, heat: f32) -> Color {
+    let [cool, warm, hot] = palette.heat_colors();
+    let heat = heat.clamp(0.0, 1.0);
+    if heat < 0.5 {
+        cool.mix(&warm, heat * 2.0)
+    } else {
+        warm.mix(&hot, heat * 2.0 - 1.0)
+    }
+}
+
+fn attach_trails(
+    mut commands: Commands,
+    trailless_query: Query<Entity, (Or<(With<Ball>, With<Paddle>)>, Without<Trail>)>,
+) {
+    for entity in &trailless_query {
+        commands.entity(entity).insert(Trail::default());
+    }
+}
+
+// The rainbow cheat colors the ball itself, so only the trail heats up while it's on.
+fn heat_balls(
+    config: Res<GameConfig>,
+    settings: Res<Settings>,
+    cheats: Res<Cheats>,
+    mut ball_query: Query<(&Velocity, &mut Sprite, &mut Trail, Has<Serving>), With<Ball>>,
+) {
+    let base = settings.ball_skin.tint();
+    for (velocity, mut sprite, mut trail, serving) in &mut ball_query {
+        let heat = config.speed_fraction(velocity.0.length());
+        let color = heat_color(settings.palette, heat);
+        if !cheats.rainbow_ball {
+            sprite.color = base.mix(&color, heat * BALL_HEAT_TINT);
+        }
+        trail.color = color;
+        trail.active = !serving;
+    }
+}
+
+// Keyboard movement is at a fixed speed, so full speed is measured against that; the
+// mouse and touch swing faster still and count too.
+fn streak_paddles(mut paddle_query: Query<(&PaddleVelocity, &mut Trail), With<Paddle>>) {
+    let full_speed = PADDLE_SPEED * PADDLE_TRAIL_SPEED;
+    for (velocity, mut trail) in &mut paddle_query {
+        trail.color = PADDLE_TRAIL_COLOR;
+        trail.active = velocity.0.abs() >= full_speed;
+    }
+}
+
+fn spawn_trail_ghosts(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut pool: ResMut<EntityPool<TrailGhost>>,
+    mut trail_query: Query<(&Transform, &Sprite, &mut Trail), Without<TrailGhost>>,
+) {
+    for (transform, sprite, mut trail) in &mut trail_query {
+        if !trail.active {
+            trail.since_last = 0.0;
+            continue;
+        }
+        trail.since_last += time.delta_secs();
+        if trail.since_last < TRAIL_INTERVAL {
+            continue;
+        }
+        trail.since_last = 0.0;
+        let color = trail.color.with_alpha(TRAIL_ALPHA);
+        pool.spawn(
+            &mut commands,
+            (
+                Sprite {
+                    image: sprite.image.clone(),
+                    color,
+                    custom_size: sprite.custom_size,
+                    ..default()
+                },
+                Transform {
+                    translation: transform.translation.with_z(transform.translation.z - 0.1),
+                    ..*transform
+                },
+                TrailGhost { age: 0.0, color },
+                GameEntity,
+            ),
+        );
+    }
+}
+
+fn fade_trail_ghosts(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut pool: ResMut<EntityPool<TrailGhost>>,
+    mut ghost_query: Query<(Entity, &mut Sprite, &mut TrailGhost)>,
+) {
+    for (entity, mut sprite, mut ghost) in &mut ghost_query {
+        ghost.age += time.delta_secs();
+        if ghost.age >= TRAIL_LIFETIME {
+            pool.release(&mut commands, entity);
+            continue;
+        }
+        let remaining = 1.0 - ghost.age / TRAIL_LIFETIME;
+        sprite.color = ghost.color.with_alpha(ghost.color.alpha() * remaining);
+    }
+}